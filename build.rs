@@ -0,0 +1,20 @@
+//! Build script: embeds the git commit hash for `space-recorder version`.
+
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs/heads");
+
+    // Source tarballs and crates.io builds have no git checkout; leave the hash unset
+    let hash = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok());
+
+    if let Some(hash) = hash {
+        println!("cargo:rustc-env=SPACE_RECORDER_GIT_HASH={}", hash.trim());
+    }
+}
@@ -4,6 +4,9 @@
 //! per terminal character cell. This gives the highest detail mode for
 //! rendering camera frames.
 
+use super::downsample::CellColor;
+use crate::camera::Frame;
+
 /// Braille base character (U+2800, empty braille pattern).
 pub const BRAILLE_BASE: char = '\u{2800}';
 
@@ -52,6 +55,57 @@ pub fn grid_to_braille(grid: [[bool; 4]; 2]) -> char {
     char::from_u32(BRAILLE_BASE as u32 + code as u32).unwrap_or(BRAILLE_BASE)
 }
 
+/// Convert a 2x4 luminance block to a braille character.
+///
+/// Dots whose brightness is at or above `threshold` are lit, so a lower
+/// threshold lights more dots.
+///
+/// # Arguments
+/// * `grid` - A 2x4 brightness array where grid[x][y] is the dot at (x,y)
+/// * `threshold` - Brightness threshold (0-255) for dot activation
+pub fn render_with_threshold(grid: [[u8; 4]; 2], threshold: u8) -> char {
+    grid_to_braille(grid.map(|column| column.map(|brightness| brightness >= threshold)))
+}
+
+/// Source pixel index for each of the 2x4 dots of the braille cell at (cx, cy).
+///
+/// Dots that fall outside the image are None.
+fn block_indices(
+    cx: u16,
+    cy: u16,
+    scale_x: f32,
+    scale_y: f32,
+    img_width: u32,
+    img_height: u32,
+) -> [[Option<usize>; 4]; 2] {
+    let mut indices = [[None; 4]; 2];
+    for dy in 0..4 {
+        for dx in 0..2 {
+            // Map braille subpixel to source image pixel
+            let bx = cx as u32 * 2 + dx;
+            let by = cy as u32 * 4 + dy;
+            let src_x = (bx as f32 * scale_x) as u32;
+            let src_y = (by as f32 * scale_y) as u32;
+
+            if src_x < img_width && src_y < img_height {
+                indices[dx as usize][dy as usize] = Some((src_y * img_width + src_x) as usize);
+            }
+        }
+    }
+    indices
+}
+
+/// Brightness of each dot in a block; pixels outside the image stay dark either way.
+fn block_brightness(gray: &[u8], indices: &[[Option<usize>; 4]; 2], invert: bool) -> [[u8; 4]; 2] {
+    indices.map(|column| {
+        column.map(|idx| match idx.and_then(|i| gray.get(i)) {
+            Some(&b) if invert => 255 - b,
+            Some(&b) => b,
+            None => 0,
+        })
+    })
+}
+
 /// Render grayscale data as braille characters.
 ///
 /// Each braille character represents a 2x4 pixel area. Pixels above the threshold
@@ -78,102 +132,144 @@ pub fn render(
     threshold: u8,
     invert: bool,
 ) -> Vec<char> {
+    let mut result = Vec::new();
+    render_into(
+        gray,
+        img_width,
+        img_height,
+        char_width,
+        char_height,
+        threshold,
+        invert,
+        &mut result,
+    );
+    result
+}
+
+/// Render grayscale data as braille characters into an existing buffer.
+///
+/// This is the allocation-free version for use in hot paths.
+#[allow(clippy::too_many_arguments)]
+pub fn render_into(
+    gray: &[u8],
+    img_width: u32,
+    img_height: u32,
+    char_width: u16,
+    char_height: u16,
+    threshold: u8,
+    invert: bool,
+    buffer: &mut Vec<char>,
+) -> usize {
+    buffer.clear();
+
     if char_width == 0 || char_height == 0 || img_width == 0 || img_height == 0 || gray.is_empty() {
-        return Vec::new();
+        return 0;
     }
 
-    // Each braille character represents 2x4 pixels
-    // Calculate pixel dimensions for the braille grid
-    let braille_pixel_width = char_width as u32 * 2;
-    let braille_pixel_height = char_height as u32 * 4;
-
-    // Scale factors from source image to braille pixel grid
-    let scale_x = img_width as f32 / braille_pixel_width as f32;
-    let scale_y = img_height as f32 / braille_pixel_height as f32;
+    let output_size = (char_width as usize) * (char_height as usize);
+    buffer.reserve(output_size);
 
-    let mut result = Vec::with_capacity((char_width as usize) * (char_height as usize));
+    // Scale factors from source image to the 2x4-per-cell braille pixel grid
+    let scale_x = img_width as f32 / (char_width as u32 * 2) as f32;
+    let scale_y = img_height as f32 / (char_height as u32 * 4) as f32;
 
     for cy in 0..char_height {
         for cx in 0..char_width {
-            let mut grid = [[false; 4]; 2];
-
-            // Sample 2x4 pixels for this braille character
-            for dy in 0..4 {
-                for dx in 0..2 {
-                    // Map braille subpixel to source image pixel
-                    let bx = cx as u32 * 2 + dx;
-                    let by = cy as u32 * 4 + dy;
-                    let src_x = (bx as f32 * scale_x) as u32;
-                    let src_y = (by as f32 * scale_y) as u32;
-
-                    // Bounds check
-                    if src_x < img_width && src_y < img_height {
-                        let idx = (src_y * img_width + src_x) as usize;
-                        if idx < gray.len() {
-                            let brightness = if invert { 255 - gray[idx] } else { gray[idx] };
-                            grid[dx as usize][dy as usize] = brightness >= threshold;
-                        }
-                    }
-                }
-            }
-
-            result.push(grid_to_braille(grid));
+            let indices = block_indices(cx, cy, scale_x, scale_y, img_width, img_height);
+            let grid = block_brightness(gray, &indices, invert);
+            buffer.push(render_with_threshold(grid, threshold));
         }
     }
 
-    result
+    output_size
 }
 
-/// Render grayscale data as braille characters into an existing buffer.
+/// Render braille characters plus a representative color for each glyph.
 ///
-/// This is the allocation-free version for use in hot paths.
+/// The color is the average of the pixels under the lit dots, so a glyph is
+/// tinted by what it actually draws rather than by the whole cell. Cells with
+/// no lit dots fall back to the average of all their pixels.
+///
+/// # Arguments
+/// * `frame` - The RGB or grayscale frame the `gray` buffer came from
+/// * `gray` - Grayscale pixel data for `frame`
+/// * `char_width` - Desired output width in characters
+/// * `char_height` - Desired output height in characters
+/// * `threshold` - Brightness threshold (0-255) for dot activation
+/// * `invert` - If true, invert brightness before thresholding
+/// * `chars` - Buffer for the braille characters
+/// * `colors` - Buffer for the glyph colors, one per character
+///
+/// # Returns
+/// The number of cells written to each buffer.
 #[allow(clippy::too_many_arguments)]
-pub fn render_into(
+pub fn render_colored_into(
+    frame: &Frame,
     gray: &[u8],
-    img_width: u32,
-    img_height: u32,
     char_width: u16,
     char_height: u16,
     threshold: u8,
     invert: bool,
-    buffer: &mut Vec<char>,
+    chars: &mut Vec<char>,
+    colors: &mut Vec<CellColor>,
 ) -> usize {
-    buffer.clear();
+    chars.clear();
+    colors.clear();
 
+    let (img_width, img_height) = (frame.width, frame.height);
     if char_width == 0 || char_height == 0 || img_width == 0 || img_height == 0 || gray.is_empty() {
         return 0;
     }
 
     let output_size = (char_width as usize) * (char_height as usize);
-    buffer.reserve(output_size);
+    chars.reserve(output_size);
+    colors.reserve(output_size);
 
-    let braille_pixel_width = char_width as u32 * 2;
-    let braille_pixel_height = char_height as u32 * 4;
-    let scale_x = img_width as f32 / braille_pixel_width as f32;
-    let scale_y = img_height as f32 / braille_pixel_height as f32;
+    let bpp = frame.bytes_per_pixel();
+    let scale_x = img_width as f32 / (char_width as u32 * 2) as f32;
+    let scale_y = img_height as f32 / (char_height as u32 * 4) as f32;
 
     for cy in 0..char_height {
         for cx in 0..char_width {
-            let mut grid = [[false; 4]; 2];
-
-            for dy in 0..4 {
-                for dx in 0..2 {
-                    let bx = cx as u32 * 2 + dx;
-                    let by = cy as u32 * 4 + dy;
-                    let src_x = (bx as f32 * scale_x) as u32;
-                    let src_y = (by as f32 * scale_y) as u32;
-
-                    if src_x < img_width && src_y < img_height {
-                        let idx = (src_y * img_width + src_x) as usize;
-                        if idx < gray.len() {
-                            let brightness = if invert { 255 - gray[idx] } else { gray[idx] };
-                            grid[dx as usize][dy as usize] = brightness >= threshold;
-                        }
+            let indices = block_indices(cx, cy, scale_x, scale_y, img_width, img_height);
+            let grid = block_brightness(gray, &indices, invert);
+            chars.push(render_with_threshold(grid, threshold));
+
+            // (r, g, b, count) over lit and unlit dots
+            let mut lit = [0u32; 4];
+            let mut unlit = [0u32; 4];
+            for dx in 0..2 {
+                for dy in 0..4 {
+                    let Some(pixel) =
+                        indices[dx][dy].and_then(|i| frame.data.get(i * bpp..i * bpp + bpp))
+                    else {
+                        continue;
+                    };
+                    let sample = [
+                        pixel[0] as u32,
+                        pixel[bpp / 2] as u32,
+                        pixel[bpp - 1] as u32,
+                        1,
+                    ];
+                    let sums = if grid[dx][dy] >= threshold {
+                        &mut lit
+                    } else {
+                        &mut unlit
+                    };
+                    for (sum, value) in sums.iter_mut().zip(sample) {
+                        *sum += value;
                     }
                 }
             }
-
-            buffer.push(grid_to_braille(grid));
+            let sums = if lit[3] > 0 { lit } else { unlit };
+            colors.push(match sums[3] {
+                0 => CellColor::default(),
+                n => CellColor {
+                    r: (sums[0] / n) as u8,
+                    g: (sums[1] / n) as u8,
+                    b: (sums[2] / n) as u8,
+                },
+            });
         }
     }
 
@@ -227,6 +323,54 @@ mod tests {
         assert!(buffer.is_empty());
     }
 
+    #[test]
+    fn test_render_with_threshold_black_and_white_blocks() {
+        assert_eq!(render_with_threshold([[0; 4]; 2], 128), '\u{2800}');
+        assert_eq!(render_with_threshold([[255; 4]; 2], 128), '\u{28FF}');
+    }
+
+    #[test]
+    fn test_render_with_threshold_controls_lit_dots() {
+        // Left column mid-gray, right column bright
+        let grid = [[100; 4], [200; 4]];
+        assert_eq!(render_with_threshold(grid, 50), '\u{28FF}');
+        // Only the right column (dots 4, 5, 6, 8) at a higher threshold
+        assert_eq!(render_with_threshold(grid, 150), '\u{28B8}');
+        assert_eq!(render_with_threshold(grid, 250), BRAILLE_BASE);
+    }
+
+    #[test]
+    fn test_render_colored_uses_lit_dot_colors() {
+        use crate::camera::FrameFormat;
+        use std::time::Instant;
+
+        // 2x4 frame: left column dark red, right column bright green
+        let mut data = Vec::new();
+        for _ in 0..4 {
+            data.extend_from_slice(&[60, 0, 0, 0, 255, 0]);
+        }
+        let frame = Frame {
+            data,
+            width: 2,
+            height: 4,
+            format: FrameFormat::Rgb,
+            timestamp: Instant::now(),
+        };
+        let gray = crate::ascii::to_grayscale(&frame);
+        let mut chars = Vec::new();
+        let mut colors = Vec::new();
+
+        let written = render_colored_into(&frame, &gray, 1, 1, 100, false, &mut chars, &mut colors);
+        assert_eq!(written, 1);
+        assert_eq!(chars[0], '\u{28B8}');
+        assert_eq!((colors[0].r, colors[0].g, colors[0].b), (0, 255, 0));
+
+        // Nothing lit: fall back to the whole cell's average
+        render_colored_into(&frame, &gray, 1, 1, 255, false, &mut chars, &mut colors);
+        assert_eq!(chars[0], BRAILLE_BASE);
+        assert_eq!((colors[0].r, colors[0].g, colors[0].b), (30, 127, 0));
+    }
+
     #[test]
     fn test_render_basic() {
         // 2x4 white pixels should produce a full braille character
@@ -1,5 +1,7 @@
 //! Character set definitions for ASCII rendering.
 
+use std::sync::Arc;
+
 /// Standard ASCII density ramp (10 levels).
 /// Characters ordered from darkest (space) to brightest (@).
 /// Works well on dark terminals.
@@ -14,10 +16,50 @@ pub const BLOCKS_CHARSET: &[char] = &[' ', '░', '▒', '▓', '█'];
 /// Clean, less noisy look.
 pub const MINIMAL_CHARSET: &[char] = &[' ', '.', ':', '#'];
 
+/// Extended ASCII density ramp (70 levels).
+/// Paul Bourke's classic `$@B%8&WM#*...` ramp, reversed to run from darkest
+/// (space) to brightest (`$`). Avoids banding on large modals.
+pub const EXTENDED_CHARSET: &[char] = &[
+    ' ', '.', '\'', '`', '^', '"', ',', ':', ';', 'I', 'l', '!', 'i', '>', '<', '~', '+', '_', '-',
+    '?', ']', '[', '}', '{', '1', ')', '(', '|', '\\', '/', 't', 'f', 'j', 'r', 'x', 'n', 'u', 'v',
+    'c', 'z', 'X', 'Y', 'U', 'J', 'C', 'L', 'Q', '0', 'O', 'Z', 'm', 'w', 'q', 'p', 'd', 'b', 'k',
+    'h', 'a', 'o', '*', '#', 'M', 'W', '&', '8', '%', 'B', '@', '$',
+];
+
+/// Error returned when a custom density ramp is unusable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CharSetError {
+    /// The ramp has fewer than two characters, so it can't express any contrast
+    RampTooShort(usize),
+}
+
+impl std::fmt::Display for CharSetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CharSetError::RampTooShort(len) => write!(
+                f,
+                "custom charset needs at least 2 characters (dark to light), got {}",
+                len
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CharSetError {}
+
+/// Split a user-supplied density ramp into characters, ordered dark to light.
+pub fn parse_ramp(ramp: &str) -> Result<Vec<char>, CharSetError> {
+    let chars: Vec<char> = ramp.chars().collect();
+    if chars.len() < 2 {
+        return Err(CharSetError::RampTooShort(chars.len()));
+    }
+    Ok(chars)
+}
+
 /// Character set type for ASCII rendering.
 ///
 /// Allows cycling through different character sets with hotkeys.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum CharSet {
     /// Standard ASCII density ramp (10 levels)
     #[default]
@@ -28,31 +70,56 @@ pub enum CharSet {
     Minimal,
     /// Braille character set for highest resolution
     Braille,
+    /// Extended ASCII density ramp (70 levels)
+    Extended,
+    /// User-supplied density ramp, ordered dark to light
+    Custom(Arc<[char]>),
 }
 
 impl CharSet {
+    /// Create a custom charset from a density ramp like `" .:-=+*#%@"`.
+    pub fn from_ramp(ramp: &str) -> Result<Self, CharSetError> {
+        parse_ramp(ramp).map(|chars| CharSet::Custom(chars.into()))
+    }
+
+    /// All built-in character sets, in cycle order.
+    pub fn all() -> &'static [CharSet] {
+        &[
+            CharSet::Standard,
+            CharSet::Blocks,
+            CharSet::Minimal,
+            CharSet::Braille,
+            CharSet::Extended,
+        ]
+    }
+
     /// Get the character slice for this charset.
     ///
     /// Note: For Braille, this returns an empty slice since braille
     /// rendering uses a different algorithm (braille::render).
-    pub fn chars(&self) -> &'static [char] {
+    pub fn chars(&self) -> &[char] {
         match self {
             CharSet::Standard => STANDARD_CHARSET,
             CharSet::Blocks => BLOCKS_CHARSET,
             CharSet::Minimal => MINIMAL_CHARSET,
             CharSet::Braille => &[], // Braille uses different rendering
+            CharSet::Extended => EXTENDED_CHARSET,
+            CharSet::Custom(chars) => chars,
         }
     }
 
     /// Cycle to the next character set.
     ///
-    /// Order: Standard -> Blocks -> Minimal -> Braille -> Standard
-    pub fn next(&self) -> Self {
-        match self {
-            CharSet::Standard => CharSet::Blocks,
-            CharSet::Blocks => CharSet::Minimal,
-            CharSet::Minimal => CharSet::Braille,
-            CharSet::Braille => CharSet::Standard,
+    /// Order: Standard -> Blocks -> Minimal -> Braille -> Extended ->
+    /// `custom` (if it's a `Custom` ramp) -> Standard.
+    pub fn next(&self, custom: Option<&CharSet>) -> Self {
+        match (self, custom) {
+            (CharSet::Standard, _) => CharSet::Blocks,
+            (CharSet::Blocks, _) => CharSet::Minimal,
+            (CharSet::Minimal, _) => CharSet::Braille,
+            (CharSet::Braille, _) => CharSet::Extended,
+            (CharSet::Extended, Some(custom @ CharSet::Custom(_))) => custom.clone(),
+            (CharSet::Extended | CharSet::Custom(_), _) => CharSet::Standard,
         }
     }
 
@@ -63,6 +130,20 @@ impl CharSet {
             CharSet::Blocks => "blocks",
             CharSet::Minimal => "minimal",
             CharSet::Braille => "braille",
+            CharSet::Extended => "extended",
+            CharSet::Custom(_) => "custom",
+        }
+    }
+
+    /// Get a short description of the charset.
+    pub fn description(&self) -> &'static str {
+        match self {
+            CharSet::Standard => "10-level ASCII density ramp",
+            CharSet::Blocks => "Unicode block characters (5 levels)",
+            CharSet::Minimal => "4-level clean look",
+            CharSet::Braille => "Braille patterns, 2x4 dots per cell (highest detail)",
+            CharSet::Extended => "70-level ASCII density ramp (smooth gradients)",
+            CharSet::Custom(_) => "User-supplied density ramp, dark to light",
         }
     }
 
@@ -5,6 +5,9 @@
 /// This is used to correct the aspect ratio when rendering.
 pub const DEFAULT_CHAR_ASPECT_RATIO: f32 = 2.0;
 
+/// Accepted range for a user-supplied cell width/height ratio (`--char-aspect`).
+pub const CELL_ASPECT_RANGE: std::ops::RangeInclusive<f32> = 0.3..=3.0;
+
 /// Calculate output dimensions that preserve aspect ratio for terminal display.
 ///
 /// Terminal characters are typically ~2x taller than wide, so a naive
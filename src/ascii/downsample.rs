@@ -1,5 +1,6 @@
 //! Downsampling algorithms for converting pixel data to character grids.
 
+use super::dimensions::DEFAULT_CHAR_ASPECT_RATIO;
 use crate::camera::Frame;
 
 /// RGB color for downsampled cells.
@@ -40,50 +41,259 @@ pub fn downsample(
     char_width: u16,
     char_height: u16,
 ) -> Vec<u8> {
+    let mut result = Vec::new();
     // Handle edge cases
     if char_width == 0 || char_height == 0 || img_width == 0 || img_height == 0 || gray.is_empty() {
-        return Vec::new();
+        return result;
     }
 
-    // Calculate the size of each cell in pixels (as floats for accurate mapping)
     let cell_w = img_width as f32 / char_width as f32;
     let cell_h = img_height as f32 / char_height as f32;
+    average_cells(
+        gray,
+        img_width,
+        (cell_w, cell_h),
+        char_width,
+        char_height,
+        &mut result,
+    );
+    result
+}
 
-    let mut result = Vec::with_capacity((char_width as usize) * (char_height as usize));
+/// Downsample a grayscale image into an existing buffer to avoid allocation.
+///
+/// This is the allocation-free version of `downsample` for use in hot paths.
+///
+/// # Arguments
+/// * `gray` - Grayscale pixel data (one byte per pixel, row-major order)
+/// * `img_width` - Width of the source image in pixels
+/// * `img_height` - Height of the source image in pixels
+/// * `char_width` - Desired output width in characters
+/// * `char_height` - Desired output height in characters
+/// * `buffer` - A mutable buffer to store the result
+///
+/// With the `rayon` feature, frames of at least `PARALLEL_MIN_PIXELS` are
+/// split across threads by output row; the result is identical.
+///
+/// # Returns
+/// The number of brightness values written to the buffer.
+pub fn downsample_into(
+    gray: &[u8],
+    img_width: u32,
+    img_height: u32,
+    char_width: u16,
+    char_height: u16,
+    buffer: &mut Vec<u8>,
+) -> usize {
+    buffer.clear();
 
-    for cy in 0..char_height {
-        for cx in 0..char_width {
-            // Calculate pixel bounds for this cell
-            let start_x = (cx as f32 * cell_w) as u32;
-            let end_x = ((cx + 1) as f32 * cell_w) as u32;
-            let start_y = (cy as f32 * cell_h) as u32;
-            let end_y = ((cy + 1) as f32 * cell_h) as u32;
+    // Handle edge cases
+    if char_width == 0 || char_height == 0 || img_width == 0 || img_height == 0 || gray.is_empty() {
+        return 0;
+    }
 
-            // Average brightness of all pixels in this cell
-            let mut sum = 0u32;
-            let mut count = 0u32;
+    #[cfg(feature = "rayon")]
+    if gray.len() >= PARALLEL_MIN_PIXELS {
+        return downsample_par_into(gray, img_width, img_height, char_width, char_height, buffer);
+    }
 
-            for py in start_y..end_y {
-                for px in start_x..end_x {
-                    let idx = (py * img_width + px) as usize;
-                    if idx < gray.len() {
-                        sum += gray[idx] as u32;
-                        count += 1;
-                    }
-                }
-            }
+    let cell_w = img_width as f32 / char_width as f32;
+    let cell_h = img_height as f32 / char_height as f32;
+    average_cells(
+        gray,
+        img_width,
+        (cell_w, cell_h),
+        char_width,
+        char_height,
+        buffer,
+    )
+}
+
+/// Horizontal and vertical block factors for a downsample kernel.
+///
+/// Each output cell averages `block_w` x `block_h` source pixels, so a kernel
+/// taller than wide keeps the source's proportions on terminal cells, which are
+/// themselves taller than wide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DownsampleKernel {
+    /// Source pixels per cell horizontally
+    pub block_w: u32,
+    /// Source pixels per cell vertically
+    pub block_h: u32,
+}
+
+impl Default for DownsampleKernel {
+    /// A 4x8 kernel, matching the default 2:1 cell aspect ratio.
+    fn default() -> Self {
+        Self::for_aspect(4, DEFAULT_CHAR_ASPECT_RATIO)
+    }
+}
 
-            // Store average brightness (or 0 if no pixels in cell)
-            result.push(if count > 0 { (sum / count) as u8 } else { 0 });
+impl DownsampleKernel {
+    /// Create a kernel from explicit block factors (zero is treated as 1).
+    pub fn new(block_w: u32, block_h: u32) -> Self {
+        Self {
+            block_w: block_w.max(1),
+            block_h: block_h.max(1),
         }
     }
 
+    /// Derive the vertical factor from a cell height/width ratio.
+    pub fn for_aspect(block_w: u32, char_aspect: f32) -> Self {
+        let block_h = (block_w as f32 * char_aspect).round() as u32;
+        Self::new(block_w, block_h)
+    }
+
+    /// Output grid size (cols, rows) for a source image; partial blocks at the
+    /// right and bottom edges are dropped.
+    pub fn grid_size(&self, img_width: u32, img_height: u32) -> (u16, u16) {
+        let cols = (img_width / self.block_w).min(u16::MAX as u32) as u16;
+        let rows = (img_height / self.block_h).min(u16::MAX as u32) as u16;
+        (cols, rows)
+    }
+}
+
+/// Downsample a grayscale image by averaging fixed `kernel`-sized blocks.
+///
+/// Unlike `downsample`, which fits a target grid, the grid size here follows
+/// from the kernel (see `DownsampleKernel::grid_size`).
+pub fn downsample_kernel(
+    gray: &[u8],
+    img_width: u32,
+    img_height: u32,
+    kernel: DownsampleKernel,
+) -> Vec<u8> {
+    let mut result = Vec::new();
+    downsample_kernel_into(gray, img_width, img_height, kernel, &mut result);
     result
 }
 
-/// Downsample a grayscale image into an existing buffer to avoid allocation.
+/// Downsample by fixed blocks into an existing buffer to avoid allocation.
 ///
-/// This is the allocation-free version of `downsample` for use in hot paths.
+/// # Returns
+/// The number of brightness values written to the buffer.
+pub fn downsample_kernel_into(
+    gray: &[u8],
+    img_width: u32,
+    img_height: u32,
+    kernel: DownsampleKernel,
+    buffer: &mut Vec<u8>,
+) -> usize {
+    buffer.clear();
+
+    let (cols, rows) = kernel.grid_size(img_width, img_height);
+    if cols == 0 || rows == 0 || gray.is_empty() {
+        return 0;
+    }
+
+    average_cells(
+        gray,
+        img_width,
+        (kernel.block_w as f32, kernel.block_h as f32),
+        cols,
+        rows,
+        buffer,
+    )
+}
+
+/// Source frames at least this many pixels take the parallel path.
+#[cfg(feature = "rayon")]
+pub const PARALLEL_MIN_PIXELS: usize = 320 * 240;
+
+/// Downsample with one rayon task per output row.
+///
+/// Byte-identical to the serial `downsample_into`, which calls this for large
+/// frames; exposed so callers can force the parallel path.
+///
+/// # Returns
+/// The number of brightness values written to the buffer.
+#[cfg(feature = "rayon")]
+pub fn downsample_par_into(
+    gray: &[u8],
+    img_width: u32,
+    img_height: u32,
+    char_width: u16,
+    char_height: u16,
+    buffer: &mut Vec<u8>,
+) -> usize {
+    use rayon::prelude::*;
+
+    buffer.clear();
+    if char_width == 0 || char_height == 0 || img_width == 0 || img_height == 0 || gray.is_empty() {
+        return 0;
+    }
+
+    let cell = (
+        img_width as f32 / char_width as f32,
+        img_height as f32 / char_height as f32,
+    );
+    buffer.resize((char_width as usize) * (char_height as usize), 0);
+    buffer
+        .par_chunks_mut(char_width as usize)
+        .enumerate()
+        .for_each(|(cy, row)| average_row(gray, img_width, cell, cy as u16, row));
+
+    buffer.len()
+}
+
+/// Average each `cell`-sized (width, height) region into one brightness value.
+fn average_cells(
+    gray: &[u8],
+    img_width: u32,
+    cell: (f32, f32),
+    char_width: u16,
+    char_height: u16,
+    buffer: &mut Vec<u8>,
+) -> usize {
+    let output_size = (char_width as usize) * (char_height as usize);
+    buffer.resize(output_size, 0);
+
+    for (cy, row) in buffer.chunks_mut(char_width as usize).enumerate() {
+        average_row(gray, img_width, cell, cy as u16, row);
+    }
+
+    output_size
+}
+
+/// Fill one output row (`row.len()` cells wide) of `average_cells`.
+fn average_row(gray: &[u8], img_width: u32, (cell_w, cell_h): (f32, f32), cy: u16, row: &mut [u8]) {
+    let start_y = (cy as f32 * cell_h) as u32;
+    let end_y = ((cy + 1) as f32 * cell_h) as u32;
+
+    for (cx, out) in row.iter_mut().enumerate() {
+        // Calculate pixel bounds for this cell
+        let start_x = (cx as f32 * cell_w) as u32;
+        let end_x = ((cx + 1) as f32 * cell_w) as u32;
+
+        // Average brightness of all pixels in this cell
+        let mut sum = 0u32;
+        let mut count = 0u32;
+
+        for py in start_y..end_y {
+            for px in start_x..end_x {
+                let idx = (py * img_width + px) as usize;
+                if idx < gray.len() {
+                    sum += gray[idx] as u32;
+                    count += 1;
+                }
+            }
+        }
+
+        // Store average brightness (or 0 if no pixels in cell)
+        *out = sum.checked_div(count).unwrap_or(0) as u8;
+    }
+}
+
+/// Downsample via a supersampled intermediate grid.
+///
+/// Renders the image at `factor`x the target grid into `scratch`, then
+/// box-filters each `factor`x`factor` block down into `buffer`. The finer
+/// cell boundaries reduce aliasing when the image doesn't divide evenly into
+/// the character grid. A `factor` of 0 or 1 is the same as `downsample_into`.
+///
+/// `factor` is lowered until the fine grid fits in the image, so every fine
+/// cell covers at least one pixel; any that still cover none are left out of
+/// the average rather than counted as black.
 ///
 /// # Arguments
 /// * `gray` - Grayscale pixel data (one byte per pixel, row-major order)
@@ -91,52 +301,67 @@ pub fn downsample(
 /// * `img_height` - Height of the source image in pixels
 /// * `char_width` - Desired output width in characters
 /// * `char_height` - Desired output height in characters
+/// * `factor` - Supersampling factor per axis
+/// * `scratch` - Reusable buffer for the intermediate grid
 /// * `buffer` - A mutable buffer to store the result
 ///
 /// # Returns
 /// The number of brightness values written to the buffer.
-pub fn downsample_into(
+#[allow(clippy::too_many_arguments)]
+pub fn downsample_supersampled_into(
     gray: &[u8],
     img_width: u32,
     img_height: u32,
     char_width: u16,
     char_height: u16,
+    factor: u8,
+    scratch: &mut Vec<u8>,
     buffer: &mut Vec<u8>,
 ) -> usize {
-    buffer.clear();
+    let fits =
+        (img_width / u32::from(char_width).max(1)).min(img_height / u32::from(char_height).max(1));
+    let factor = u32::from(factor).min(fits);
+    if factor <= 1 {
+        return downsample_into(gray, img_width, img_height, char_width, char_height, buffer);
+    }
 
-    // Handle edge cases
-    if char_width == 0 || char_height == 0 || img_width == 0 || img_height == 0 || gray.is_empty() {
+    let factor = factor as usize;
+    let fine_width = (char_width as usize * factor).min(u16::MAX as usize) as u16;
+    let fine_height = (char_height as usize * factor).min(u16::MAX as usize) as u16;
+
+    buffer.clear();
+    if downsample_into(
+        gray,
+        img_width,
+        img_height,
+        fine_width,
+        fine_height,
+        scratch,
+    ) == 0
+    {
         return 0;
     }
 
     let output_size = (char_width as usize) * (char_height as usize);
     buffer.reserve(output_size);
 
-    let cell_w = img_width as f32 / char_width as f32;
-    let cell_h = img_height as f32 / char_height as f32;
-
-    for cy in 0..char_height {
-        for cx in 0..char_width {
-            let start_x = (cx as f32 * cell_w) as u32;
-            let end_x = ((cx + 1) as f32 * cell_w) as u32;
-            let start_y = (cy as f32 * cell_h) as u32;
-            let end_y = ((cy + 1) as f32 * cell_h) as u32;
+    // Same cell bounds as `average_row`, to tell which fine cells are empty
+    let cell_w = img_width as f32 / fine_width as f32;
+    let cell_h = img_height as f32 / fine_height as f32;
+    let covers = |i: usize, cell: f32| ((i + 1) as f32 * cell) as u32 > (i as f32 * cell) as u32;
+    let fine_width = fine_width as usize;
 
+    for cy in 0..char_height as usize {
+        for cx in 0..char_width as usize {
             let mut sum = 0u32;
             let mut count = 0u32;
-
-            for py in start_y..end_y {
-                for px in start_x..end_x {
-                    let idx = (py * img_width + px) as usize;
-                    if idx < gray.len() {
-                        sum += gray[idx] as u32;
-                        count += 1;
-                    }
+            for fy in (cy * factor..(cy + 1) * factor).filter(|&fy| covers(fy, cell_h)) {
+                for fx in (cx * factor..(cx + 1) * factor).filter(|&fx| covers(fx, cell_w)) {
+                    sum += scratch[fy * fine_width + fx] as u32;
+                    count += 1;
                 }
             }
-
-            buffer.push(if count > 0 { (sum / count) as u8 } else { 0 });
+            buffer.push(sum.checked_div(count).unwrap_or(0) as u8);
         }
     }
 
@@ -314,10 +539,12 @@ pub fn downsample_edge_preserve(
 
 /// Downsample an RGB frame to get average colors per character cell.
 ///
-/// Each cell's color is the average of all pixels in that cell area.
+/// Each cell's color is the average of all pixels in that cell area. YUYV and
+/// NV12 frames are decoded to RGB first; pass an already decoded frame (as
+/// `AsciiPipeline` does) to skip that allocation.
 ///
 /// # Arguments
-/// * `frame` - RGB frame from the camera (3 bytes per pixel: R, G, B)
+/// * `frame` - Frame from the camera (RGB, grayscale, or YUV)
 /// * `char_width` - Desired output width in characters
 /// * `char_height` - Desired output height in characters
 /// * `buffer` - A mutable buffer to store the result
@@ -330,6 +557,11 @@ pub fn downsample_colors_into(
     char_height: u16,
     buffer: &mut Vec<CellColor>,
 ) -> usize {
+    // Chroma is shared between pixels, so YUV has to be sampled as RGB
+    if let Some(rgb) = frame.decode_yuv() {
+        return downsample_colors_into(&rgb, char_width, char_height, buffer);
+    }
+
     buffer.clear();
 
     let img_width = frame.width;
@@ -347,6 +579,7 @@ pub fn downsample_colors_into(
     let output_size = (char_width as usize) * (char_height as usize);
     buffer.reserve(output_size);
 
+    let bpp = frame.bytes_per_pixel();
     let cell_w = img_width as f32 / char_width as f32;
     let cell_h = img_height as f32 / char_height as f32;
 
@@ -364,27 +597,102 @@ pub fn downsample_colors_into(
 
             for py in start_y..end_y {
                 for px in start_x..end_x {
-                    let idx = ((py * img_width + px) * 3) as usize;
-                    if idx + 2 < frame.data.len() {
-                        sum_r += frame.data[idx] as u32;
-                        sum_g += frame.data[idx + 1] as u32;
-                        sum_b += frame.data[idx + 2] as u32;
+                    let idx = (py * img_width + px) as usize * bpp;
+                    if let Some(pixel) = frame.data.get(idx..idx + bpp) {
+                        // Grayscale frames use their single channel for all three
+                        sum_r += pixel[0] as u32;
+                        sum_g += pixel[bpp / 2] as u32;
+                        sum_b += pixel[bpp - 1] as u32;
                         count += 1;
                     }
                 }
             }
 
-            buffer.push(if count > 0 {
-                CellColor {
+            buffer.push(match count {
+                0 => CellColor::default(),
+                _ => CellColor {
                     r: (sum_r / count) as u8,
                     g: (sum_g / count) as u8,
                     b: (sum_b / count) as u8,
-                }
-            } else {
-                CellColor::default()
+                },
             });
         }
     }
 
     output_size
 }
+
+/// Nearest-neighbor sample brightness and color straight from a frame.
+///
+/// The cheapest downsample: it reads one source pixel per sampled cell
+/// instead of averaging every pixel and skips the full-frame grayscale pass
+/// by computing BT.601 luma only for the sampled pixels. With `step > 1`, one
+/// sample is taken per `step`x`step` block of cells and copied to the whole
+/// block, which reduces the effective grid resolution.
+///
+/// # Arguments
+/// * `frame` - An RGB or grayscale frame from the camera
+/// * `char_width` - Desired output width in characters
+/// * `char_height` - Desired output height in characters
+/// * `step` - Cells per sample along each axis (1 = one sample per cell)
+/// * `brightness` - Buffer for brightness values (0-255), one per cell
+/// * `colors` - Buffer for cell colors, one per cell
+///
+/// # Returns
+/// The number of cells written to each buffer.
+pub fn downsample_nearest_into(
+    frame: &Frame,
+    char_width: u16,
+    char_height: u16,
+    step: u16,
+    brightness: &mut Vec<u8>,
+    colors: &mut Vec<CellColor>,
+) -> usize {
+    brightness.clear();
+    colors.clear();
+
+    if char_width == 0
+        || char_height == 0
+        || frame.width == 0
+        || frame.height == 0
+        || frame.data.is_empty()
+    {
+        return 0;
+    }
+
+    let output_size = (char_width as usize) * (char_height as usize);
+    brightness.reserve(output_size);
+    colors.reserve(output_size);
+
+    let step = step.max(1);
+    let bpp = frame.bytes_per_pixel();
+    let cell_w = frame.width as f32 / char_width as f32;
+    let cell_h = frame.height as f32 / char_height as f32;
+
+    for cy in 0..char_height {
+        // Sample the center of the first cell in each step block
+        let block_y = cy - cy % step;
+        let py = (((block_y as f32 + 0.5) * cell_h) as u32).min(frame.height - 1);
+
+        for cx in 0..char_width {
+            let block_x = cx - cx % step;
+            let px = (((block_x as f32 + 0.5) * cell_w) as u32).min(frame.width - 1);
+
+            let idx = (py * frame.width + px) as usize * bpp;
+            let color = match frame.data.get(idx..idx + bpp) {
+                Some(pixel) => CellColor {
+                    r: pixel[0],
+                    g: pixel[bpp / 2],
+                    b: pixel[bpp - 1],
+                },
+                None => CellColor::default(),
+            };
+            let luma = (299 * color.r as u32 + 587 * color.g as u32 + 114 * color.b as u32) / 1000;
+
+            brightness.push(luma as u8);
+            colors.push(color);
+        }
+    }
+
+    output_size
+}
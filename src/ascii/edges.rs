@@ -50,6 +50,18 @@ pub const STRUCTURE_CHARSET_ASCII: StructureCharset = StructureCharset {
     diagonal_up: [' ', '.', '/', '/', '#'],
 };
 
+/// Default `apply_edge_detection` cutoff: keep every gradient magnitude.
+pub const DEFAULT_EDGE_THRESHOLD: f32 = 0.0;
+
+/// Default `map_structure_aware` cutoff: cells need an edge strength of ~50/255
+/// before a directional character replaces the density character.
+pub const DEFAULT_STRUCTURE_THRESHOLD: f32 = 50.0 / 255.0;
+
+/// Convert a 0.0-1.0 threshold to a 0-255 magnitude cutoff.
+fn magnitude_cutoff(threshold: f32) -> u8 {
+    (threshold.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
 /// Analyze gradient direction and magnitude for a cell.
 ///
 /// Returns (magnitude, direction) where magnitude is 0-255 and direction
@@ -102,6 +114,7 @@ fn get_structure_char(
     brightness: u8,
     direction: EdgeDirection,
     edge_strength: u8,
+    edge_cutoff: u8,
     charset: &StructureCharset,
 ) -> char {
     // Map brightness to 5 levels (0-4)
@@ -110,9 +123,7 @@ fn get_structure_char(
 
     // Blend between smooth and edge character based on edge strength
     // If edge is strong, use directional char; if weak, use smooth char
-    const EDGE_BLEND_THRESHOLD: u8 = 50;
-
-    if edge_strength < EDGE_BLEND_THRESHOLD {
+    if edge_strength < edge_cutoff {
         charset.smooth[level]
     } else {
         match direction {
@@ -143,17 +154,20 @@ fn get_structure_char(
 /// * `gray` - Grayscale pixel data (one byte per pixel, row-major order)
 /// * `width` - Width of the image in pixels
 /// * `height` - Height of the image in pixels
+/// * `threshold` - Gradient magnitude cutoff (0.0-1.0); weaker gradients are
+///   zeroed. Use `DEFAULT_EDGE_THRESHOLD` to keep every magnitude.
 ///
 /// # Returns
 /// A vector of edge magnitudes (0-255), same dimensions as input.
 /// Edge pixels (1-pixel border) are set to 0 since the kernel can't be applied there.
-pub fn apply_edge_detection(gray: &[u8], width: u32, height: u32) -> Vec<u8> {
+pub fn apply_edge_detection(gray: &[u8], width: u32, height: u32, threshold: f32) -> Vec<u8> {
     // Handle edge cases
     if width < 3 || height < 3 || gray.len() < (width * height) as usize {
         return gray.to_vec();
     }
 
     let mut edges = vec![0u8; gray.len()];
+    let cutoff = magnitude_cutoff(threshold);
 
     // Sobel kernels
     // Gx detects vertical edges (horizontal gradient)
@@ -184,7 +198,9 @@ pub fn apply_edge_detection(gray: &[u8], width: u32, height: u32) -> Vec<u8> {
             // Using integer approximation: |gx| + |gy| is faster than sqrt(gx² + gy²)
             // and produces similar visual results for edge detection
             let magnitude = (gx.abs() + gy.abs()).min(255) as u8;
-            edges[(y * width + x) as usize] = magnitude;
+            if magnitude >= cutoff {
+                edges[(y * width + x) as usize] = magnitude;
+            }
         }
     }
 
@@ -205,9 +221,13 @@ pub fn apply_edge_detection(gray: &[u8], width: u32, height: u32) -> Vec<u8> {
 /// * `char_height` - Desired output height in characters
 /// * `charset` - Structure-aware character set to use
 /// * `use_gamma` - Whether to apply gamma correction
+/// * `threshold` - Edge strength (0.0-1.0) a cell needs before a directional
+///   character replaces the density character. Lower values draw more structure;
+///   `DEFAULT_STRUCTURE_THRESHOLD` matches the original behavior.
 ///
 /// # Returns
 /// A vector of characters, one per cell, in row-major order.
+#[allow(clippy::too_many_arguments)]
 pub fn map_structure_aware(
     gray: &[u8],
     img_width: u32,
@@ -216,6 +236,7 @@ pub fn map_structure_aware(
     char_height: u16,
     charset: &StructureCharset,
     use_gamma: bool,
+    threshold: f32,
 ) -> Vec<char> {
     if char_width == 0 || char_height == 0 || img_width < 3 || img_height < 3 || gray.is_empty() {
         return vec![' '; (char_width as usize) * (char_height as usize)];
@@ -223,6 +244,7 @@ pub fn map_structure_aware(
 
     let cell_w = img_width as f32 / char_width as f32;
     let cell_h = img_height as f32 / char_height as f32;
+    let edge_cutoff = magnitude_cutoff(threshold);
 
     // Sobel kernels
     let sobel_x: [[i32; 3]; 3] = [[-1, 0, 1], [-2, 0, 2], [-1, 0, 1]];
@@ -277,11 +299,7 @@ pub fn map_structure_aware(
                 }
             }
 
-            let brightness = if count > 0 {
-                (brightness_sum / count) as u8
-            } else {
-                0
-            };
+            let brightness = brightness_sum.checked_div(count).unwrap_or(0) as u8;
             let brightness = if use_gamma {
                 gamma_correct(brightness)
             } else {
@@ -301,7 +319,7 @@ pub fn map_structure_aware(
             };
 
             let (edge_strength, direction) = analyze_gradient(avg_gx, avg_gy);
-            let ch = get_structure_char(brightness, direction, edge_strength, charset);
+            let ch = get_structure_char(brightness, direction, edge_strength, edge_cutoff, charset);
             result.push(ch);
         }
     }
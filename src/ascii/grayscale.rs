@@ -1,5 +1,7 @@
 //! RGB to grayscale conversion using ITU-R BT.601 luminance formula.
 
+#[cfg(feature = "rayon")]
+use super::downsample::PARALLEL_MIN_PIXELS;
 use crate::camera::Frame;
 
 /// Convert an RGB frame to grayscale using ITU-R BT.601 luminance formula.
@@ -13,11 +15,18 @@ use crate::camera::Frame;
 /// - B: 114/1000
 ///
 /// # Arguments
-/// * `frame` - An RGB frame from the camera
+/// * `frame` - An RGB, grayscale, YUYV, or NV12 frame from the camera
+///
+/// Grayscale frames are already luma and are copied through unchanged, and
+/// YUV frames contribute their Y samples (see `Frame::to_luma`).
 ///
 /// # Returns
 /// A vector of grayscale values (0-255), one per pixel
 pub fn to_grayscale(frame: &Frame) -> Vec<u8> {
+    if let Some(luma) = frame.to_luma() {
+        return luma.into_owned();
+    }
+
     // Pre-allocate with exact capacity
     let pixel_count = (frame.width * frame.height) as usize;
     let mut gray = Vec::with_capacity(pixel_count);
@@ -25,39 +34,90 @@ pub fn to_grayscale(frame: &Frame) -> Vec<u8> {
     // Process RGB triplets using integer math for speed
     // Coefficients scaled by 1000: 299 + 587 + 114 = 1000
     for rgb in frame.data.chunks_exact(3) {
-        let r = rgb[0] as u32;
-        let g = rgb[1] as u32;
-        let b = rgb[2] as u32;
-        // ITU-R BT.601 luminance formula with integer math
-        let luminance = (299 * r + 587 * g + 114 * b) / 1000;
-        gray.push(luminance as u8);
+        gray.push(luma(rgb));
     }
 
     gray
 }
 
+/// ITU-R BT.601 luminance of one RGB triplet, with integer math.
+#[inline]
+fn luma(rgb: &[u8]) -> u8 {
+    let r = rgb[0] as u32;
+    let g = rgb[1] as u32;
+    let b = rgb[2] as u32;
+    ((299 * r + 587 * g + 114 * b) / 1000) as u8
+}
+
 /// Convert an RGB frame to grayscale in-place, reusing an existing buffer.
 ///
 /// This avoids allocation when called repeatedly (e.g., each frame).
 ///
 /// # Arguments
-/// * `frame` - An RGB frame from the camera
+/// * `frame` - An RGB, grayscale, YUYV, or NV12 frame from the camera
 /// * `buffer` - A mutable buffer to store grayscale values
 ///
+/// With the `rayon` feature, frames of at least `PARALLEL_MIN_PIXELS` are
+/// converted in parallel row chunks; the result is identical.
+///
 /// # Returns
 /// The number of pixels written to the buffer
 pub fn to_grayscale_into(frame: &Frame, buffer: &mut Vec<u8>) -> usize {
     let pixel_count = (frame.width * frame.height) as usize;
     buffer.clear();
+
+    if let Some(luma) = frame.to_luma() {
+        buffer.extend_from_slice(&luma);
+        return pixel_count;
+    }
+
+    #[cfg(feature = "rayon")]
+    if pixel_count >= PARALLEL_MIN_PIXELS {
+        return to_grayscale_par_into(frame, buffer);
+    }
+
     buffer.reserve(pixel_count);
 
     for rgb in frame.data.chunks_exact(3) {
-        let r = rgb[0] as u32;
-        let g = rgb[1] as u32;
-        let b = rgb[2] as u32;
-        let luminance = (299 * r + 587 * g + 114 * b) / 1000;
-        buffer.push(luminance as u8);
+        buffer.push(luma(rgb));
+    }
+
+    pixel_count
+}
+
+/// Convert an RGB frame to grayscale with one rayon task per row chunk.
+///
+/// Byte-identical to `to_grayscale`; `to_grayscale_into` calls this for large
+/// frames.
+///
+/// # Returns
+/// The number of pixels written to the buffer
+#[cfg(feature = "rayon")]
+pub fn to_grayscale_par_into(frame: &Frame, buffer: &mut Vec<u8>) -> usize {
+    use rayon::prelude::*;
+
+    /// Rows converted per task, to keep scheduling overhead low.
+    const ROWS_PER_CHUNK: usize = 16;
+
+    let pixel_count = (frame.width * frame.height) as usize;
+    buffer.clear();
+
+    if let Some(luma) = frame.to_luma() {
+        buffer.extend_from_slice(&luma);
+        return pixel_count;
     }
 
+    // Match the serial path on short buffers: only whole triplets count
+    let chunk = (frame.width as usize * ROWS_PER_CHUNK).max(1);
+    buffer.resize(frame.data.len() / 3, 0);
+    buffer
+        .par_chunks_mut(chunk)
+        .zip(frame.data.par_chunks(chunk * 3))
+        .for_each(|(out, rgb)| {
+            for (pixel, rgb) in out.iter_mut().zip(rgb.chunks_exact(3)) {
+                *pixel = luma(rgb);
+            }
+        });
+
     pixel_count
 }
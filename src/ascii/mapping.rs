@@ -3,6 +3,38 @@
 /// Standard display gamma (sRGB).
 pub const GAMMA: f32 = 2.2;
 
+/// Dithering applied when mapping brightness to characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DitherMode {
+    /// Plain gamma-corrected mapping
+    #[default]
+    None,
+    /// Bayer-matrix ordered dithering
+    Ordered,
+    /// Floyd-Steinberg error diffusion
+    FloydSteinberg,
+}
+
+impl DitherMode {
+    /// Cycle to the next mode: None -> Ordered -> FloydSteinberg -> None.
+    pub fn next(&self) -> Self {
+        match self {
+            DitherMode::None => DitherMode::Ordered,
+            DitherMode::Ordered => DitherMode::FloydSteinberg,
+            DitherMode::FloydSteinberg => DitherMode::None,
+        }
+    }
+
+    /// Get a human-readable name for the mode.
+    pub fn name(&self) -> &'static str {
+        match self {
+            DitherMode::None => "none",
+            DitherMode::Ordered => "ordered",
+            DitherMode::FloydSteinberg => "floyd-steinberg",
+        }
+    }
+}
+
 /// Precomputed gamma correction lookup table for fast mapping.
 /// Maps linear brightness [0-255] to perceptually-corrected brightness [0-255].
 /// Formula: output = (input/255)^(1/2.2) * 255
@@ -34,6 +66,34 @@ pub fn gamma_correct(linear: u8) -> u8 {
     GAMMA_LUT[linear as usize]
 }
 
+/// Gamma values visited by `next_gamma`, in order.
+pub const GAMMA_STEPS: [f32; 5] = [1.0, 1.5, 2.0, GAMMA, 2.5];
+
+/// The gamma step after `gamma`, wrapping back to the first step.
+pub fn next_gamma(gamma: f32) -> f32 {
+    GAMMA_STEPS
+        .iter()
+        .copied()
+        .find(|&step| step > gamma + 0.01)
+        .unwrap_or(GAMMA_STEPS[0])
+}
+
+/// Build a gamma correction lookup table: output = (input/255)^(1/gamma) * 255.
+///
+/// `GAMMA` returns the precomputed table, so the default mapping is unchanged;
+/// 1.0 is the identity. Non-positive values are treated as 1.0.
+pub fn gamma_lut(gamma: f32) -> [u8; 256] {
+    if gamma == GAMMA {
+        return GAMMA_LUT;
+    }
+    let exponent = if gamma > 0.0 { 1.0 / gamma as f64 } else { 1.0 };
+    let mut lut = [0u8; 256];
+    for (i, out) in lut.iter_mut().enumerate() {
+        *out = ((i as f64 / 255.0).powf(exponent) * 255.0).round() as u8;
+    }
+    lut
+}
+
 /// Map brightness values to ASCII characters.
 ///
 /// Converts a grid of brightness values (0-255) to characters from the
@@ -115,6 +175,10 @@ pub fn map_to_chars_into(
 /// accurate brightness mapping. This produces better results for photographic
 /// images by accounting for human visual perception.
 ///
+/// Gamma is applied before inversion. Inverting first would push mid-tones
+/// through the steep end of the curve and map them to nearly the same glyph
+/// either way, so the inverted image would lose its contrast.
+///
 /// # Arguments
 /// * `brightness` - Brightness values (0-255), one per character cell
 /// * `charset` - Character set to use, ordered from darkest to brightest
@@ -131,8 +195,8 @@ pub fn map_to_chars_gamma(brightness: &[u8], charset: &[char], invert: bool) ->
     brightness
         .iter()
         .map(|&b| {
-            let b = if invert { 255 - b } else { b };
             let corrected = gamma_correct(b);
+            let corrected = if invert { 255 - corrected } else { corrected };
             let idx = (corrected as usize * (levels - 1)) / 255;
             charset[idx]
         })
@@ -157,6 +221,23 @@ pub fn map_to_chars_gamma_into(
     charset: &[char],
     invert: bool,
     buffer: &mut Vec<char>,
+) -> usize {
+    map_to_chars_gamma_lut_into(brightness, charset, invert, &GAMMA_LUT, buffer)
+}
+
+/// Map brightness values to ASCII characters through a runtime gamma table.
+///
+/// Same as `map_to_chars_gamma_into` but with a table from `gamma_lut`, so the
+/// gamma can change while running.
+///
+/// # Returns
+/// The number of characters written to the buffer.
+pub fn map_to_chars_gamma_lut_into(
+    brightness: &[u8],
+    charset: &[char],
+    invert: bool,
+    lut: &[u8; 256],
+    buffer: &mut Vec<char>,
 ) -> usize {
     buffer.clear();
 
@@ -169,8 +250,8 @@ pub fn map_to_chars_gamma_into(
     let levels = charset.len();
 
     for &b in brightness {
-        let b = if invert { 255 - b } else { b };
-        let corrected = gamma_correct(b);
+        let corrected = lut[b as usize];
+        let corrected = if invert { 255 - corrected } else { corrected };
         let idx = (corrected as usize * (levels - 1)) / 255;
         buffer.push(charset[idx]);
     }
@@ -178,6 +259,20 @@ pub fn map_to_chars_gamma_into(
     brightness.len()
 }
 
+/// Map brightness values to ASCII characters using Floyd-Steinberg dithering.
+///
+/// Same as [`map_to_chars_floyd_steinberg`]; kept under its original name.
+pub fn map_to_chars_dithered(
+    brightness: &[u8],
+    width: u16,
+    height: u16,
+    charset: &[char],
+    invert: bool,
+    use_gamma: bool,
+) -> Vec<char> {
+    map_to_chars_floyd_steinberg(brightness, width, height, charset, invert, use_gamma)
+}
+
 /// Map brightness values to ASCII characters using Floyd-Steinberg dithering.
 ///
 /// Dithering distributes quantization error to neighboring pixels, creating
@@ -189,12 +284,12 @@ pub fn map_to_chars_gamma_into(
 /// * `width` - Width of the character grid
 /// * `height` - Height of the character grid
 /// * `charset` - Character set to use, ordered from darkest to brightest
-/// * `invert` - If true, invert brightness before mapping
+/// * `invert` - If true, invert brightness (after gamma) before mapping
 /// * `use_gamma` - If true, apply gamma correction before dithering
 ///
 /// # Returns
 /// A vector of characters, one per input brightness value.
-pub fn map_to_chars_dithered(
+pub fn map_to_chars_floyd_steinberg(
     brightness: &[u8],
     width: u16,
     height: u16,
@@ -202,8 +297,44 @@ pub fn map_to_chars_dithered(
     invert: bool,
     use_gamma: bool,
 ) -> Vec<char> {
+    let mut error = Vec::new();
+    let mut result = Vec::new();
+    map_to_chars_floyd_steinberg_into(
+        brightness,
+        width,
+        height,
+        charset,
+        invert,
+        use_gamma,
+        &mut error,
+        &mut result,
+    );
+    result
+}
+
+/// Floyd-Steinberg dithering into existing buffers.
+///
+/// This is the allocation-free version for use in hot paths; `error` is
+/// scratch space for the diffused error values.
+///
+/// # Returns
+/// The number of characters written to the buffer.
+#[allow(clippy::too_many_arguments)]
+pub fn map_to_chars_floyd_steinberg_into(
+    brightness: &[u8],
+    width: u16,
+    height: u16,
+    charset: &[char],
+    invert: bool,
+    use_gamma: bool,
+    error: &mut Vec<i16>,
+    buffer: &mut Vec<char>,
+) -> usize {
+    buffer.clear();
+
     if charset.is_empty() || width == 0 || height == 0 {
-        return vec![' '; brightness.len()];
+        buffer.resize(brightness.len(), ' ');
+        return buffer.len();
     }
 
     let w = width as usize;
@@ -211,16 +342,15 @@ pub fn map_to_chars_dithered(
     let levels = charset.len();
 
     // Work buffer with signed values for error diffusion
-    let mut buffer: Vec<i16> = brightness
-        .iter()
-        .map(|&b| {
-            let b = if invert { 255 - b } else { b };
-            let b = if use_gamma { gamma_correct(b) } else { b };
-            b as i16
-        })
-        .collect();
+    error.clear();
+    error.extend(brightness.iter().map(|&b| {
+        let b = if use_gamma { gamma_correct(b) } else { b };
+        let b = if invert { 255 - b } else { b };
+        b as i16
+    }));
+    error.resize(w * h, 0);
 
-    let mut result = vec![' '; w * h];
+    buffer.resize(w * h, ' ');
 
     // Floyd-Steinberg error diffusion pattern:
     //       [*] 7/16
@@ -228,37 +358,37 @@ pub fn map_to_chars_dithered(
     for y in 0..h {
         for x in 0..w {
             let idx = y * w + x;
-            let old_val = buffer[idx].clamp(0, 255) as u8;
+            let old_val = error[idx].clamp(0, 255) as u8;
 
             // Quantize to nearest character level
             let char_idx = (old_val as usize * (levels - 1)) / 255;
-            result[idx] = charset[char_idx];
+            buffer[idx] = charset[char_idx];
 
             // Calculate quantized value (what this character represents)
-            let new_val = (char_idx * 255 / (levels - 1)) as i16;
-            let error = buffer[idx] - new_val;
+            let new_val = (char_idx * 255 / (levels - 1).max(1)) as i16;
+            let err = error[idx] - new_val;
 
             // Distribute error to neighbors
             // Right: 7/16
             if x + 1 < w {
-                buffer[idx + 1] += error * 7 / 16;
+                error[idx + 1] += err * 7 / 16;
             }
             // Bottom-left: 3/16
             if y + 1 < h && x > 0 {
-                buffer[idx + w - 1] += error * 3 / 16;
+                error[idx + w - 1] += err * 3 / 16;
             }
             // Bottom: 5/16
             if y + 1 < h {
-                buffer[idx + w] += error * 5 / 16;
+                error[idx + w] += err * 5 / 16;
             }
             // Bottom-right: 1/16
             if y + 1 < h && x + 1 < w {
-                buffer[idx + w + 1] += error / 16;
+                error[idx + w + 1] += err / 16;
             }
         }
     }
 
-    result
+    buffer.len()
 }
 
 /// Map brightness values to ASCII characters using ordered (Bayer) dithering.
@@ -271,7 +401,7 @@ pub fn map_to_chars_dithered(
 /// * `brightness` - Brightness values (0-255), one per character cell
 /// * `width` - Width of the character grid
 /// * `charset` - Character set to use, ordered from darkest to brightest
-/// * `invert` - If true, invert brightness before mapping
+/// * `invert` - If true, invert brightness (after gamma) before mapping
 /// * `use_gamma` - If true, apply gamma correction before dithering
 ///
 /// # Returns
@@ -283,8 +413,30 @@ pub fn map_to_chars_ordered_dither(
     invert: bool,
     use_gamma: bool,
 ) -> Vec<char> {
+    let mut result = Vec::new();
+    map_to_chars_ordered_dither_into(brightness, width, charset, invert, use_gamma, &mut result);
+    result
+}
+
+/// Ordered (Bayer) dithering into an existing buffer.
+///
+/// This is the allocation-free version for use in hot paths.
+///
+/// # Returns
+/// The number of characters written to the buffer.
+pub fn map_to_chars_ordered_dither_into(
+    brightness: &[u8],
+    width: u16,
+    charset: &[char],
+    invert: bool,
+    use_gamma: bool,
+    buffer: &mut Vec<char>,
+) -> usize {
+    buffer.clear();
+
     if charset.is_empty() || width == 0 {
-        return vec![' '; brightness.len()];
+        buffer.resize(brightness.len(), ' ');
+        return buffer.len();
     }
 
     // 4x4 Bayer matrix (normalized to 0-255 range)
@@ -301,23 +453,21 @@ pub fn map_to_chars_ordered_dither(
     // Threshold spread based on number of levels
     let spread = 255 / levels as i16;
 
-    brightness
-        .iter()
-        .enumerate()
-        .map(|(i, &b)| {
-            let b = if invert { 255 - b } else { b };
-            let b = if use_gamma { gamma_correct(b) } else { b };
+    buffer.extend(brightness.iter().enumerate().map(|(i, &b)| {
+        let b = if use_gamma { gamma_correct(b) } else { b };
+        let b = if invert { 255 - b } else { b };
 
-            let x = i % w;
-            let y = i / w;
-            let threshold = BAYER_4X4[y % 4][x % 4];
+        let x = i % w;
+        let y = i / w;
+        let threshold = BAYER_4X4[y % 4][x % 4];
 
-            // Add threshold offset (scaled by spread) to brightness
-            let adjusted = (b as i16) + (threshold - 128) * spread / 256;
-            let adjusted = adjusted.clamp(0, 255) as u8;
+        // Add threshold offset (scaled by spread) to brightness
+        let adjusted = (b as i16) + (threshold - 128) * spread / 256;
+        let adjusted = adjusted.clamp(0, 255) as u8;
 
-            let idx = (adjusted as usize * (levels - 1)) / 255;
-            charset[idx]
-        })
-        .collect()
+        let idx = (adjusted as usize * (levels - 1)) / 255;
+        charset[idx]
+    }));
+
+    buffer.len()
 }
@@ -7,6 +7,8 @@
 //! 2. **Downsampling** - Reduce resolution to character grid
 //! 3. **Character mapping** - Map brightness to ASCII characters
 //! 4. **Edge detection** - Optional Sobel filter for sharper output
+//! 5. **Auto-levels** - Optional contrast stretch for dim footage
+//! 6. **Sharpen** - Optional unsharp mask before downsampling
 //!
 //! # Character Sets
 //!
@@ -15,32 +17,54 @@
 //! - `Blocks` - Unicode block characters
 //! - `Minimal` - 4-level clean look
 //! - `Braille` - Highest resolution using braille patterns
+//! - `Extended` - 70-level ASCII density ramp for large modals
+//! - `Custom` - User-supplied density ramp
 
 pub mod braille;
 mod charset;
 mod dimensions;
 mod downsample;
 mod edges;
+mod filters;
 mod grayscale;
 mod mapping;
+mod pipeline;
+mod snapshot;
 
 // Re-export all public items for backwards compatibility
-pub use charset::{BLOCKS_CHARSET, CharSet, MINIMAL_CHARSET, STANDARD_CHARSET};
+pub use charset::{
+    BLOCKS_CHARSET, CharSet, CharSetError, EXTENDED_CHARSET, MINIMAL_CHARSET, STANDARD_CHARSET,
+    parse_ramp,
+};
 pub use dimensions::{
-    DEFAULT_CHAR_ASPECT_RATIO, calculate_dimensions, calculate_dimensions_with_aspect,
+    CELL_ASPECT_RANGE, DEFAULT_CHAR_ASPECT_RATIO, calculate_dimensions,
+    calculate_dimensions_with_aspect,
 };
 pub use downsample::{
-    CellColor, downsample, downsample_colors_into, downsample_contrast, downsample_edge_preserve,
-    downsample_into,
+    CellColor, DownsampleKernel, downsample, downsample_colors_into, downsample_contrast,
+    downsample_edge_preserve, downsample_into, downsample_kernel, downsample_kernel_into,
+    downsample_nearest_into, downsample_supersampled_into,
 };
 pub use edges::{
-    EdgeDirection, STRUCTURE_CHARSET, STRUCTURE_CHARSET_ASCII, StructureCharset,
-    apply_edge_detection, map_structure_aware,
+    DEFAULT_EDGE_THRESHOLD, DEFAULT_STRUCTURE_THRESHOLD, EdgeDirection, STRUCTURE_CHARSET,
+    STRUCTURE_CHARSET_ASCII, StructureCharset, apply_edge_detection, map_structure_aware,
+};
+pub use filters::{
+    DEFAULT_CLIP_PERCENT, DEFAULT_SHARPEN_AMOUNT, auto_levels, unsharp, unsharp_into,
 };
 pub use grayscale::{to_grayscale, to_grayscale_into};
 pub use mapping::{
-    GAMMA, gamma_correct, map_to_chars, map_to_chars_dithered, map_to_chars_gamma,
-    map_to_chars_gamma_into, map_to_chars_into, map_to_chars_ordered_dither,
+    DitherMode, GAMMA, GAMMA_STEPS, gamma_correct, gamma_lut, map_to_chars, map_to_chars_dithered,
+    map_to_chars_floyd_steinberg, map_to_chars_floyd_steinberg_into, map_to_chars_gamma,
+    map_to_chars_gamma_into, map_to_chars_gamma_lut_into, map_to_chars_into,
+    map_to_chars_ordered_dither, map_to_chars_ordered_dither_into, next_gamma,
+};
+pub use pipeline::{AsciiPipeline, DEFAULT_BRAILLE_THRESHOLD};
+pub use snapshot::{SnapshotSettings, capture_ascii_snapshot, render_snapshot};
+#[cfg(feature = "rayon")]
+pub use {
+    downsample::{PARALLEL_MIN_PIXELS, downsample_par_into},
+    grayscale::to_grayscale_par_into,
 };
 
 // Re-export braille functions at the module level for convenience
@@ -49,5 +73,7 @@ pub use braille::BRAILLE_BASE;
 #[allow(unused_imports)]
 pub use braille::grid_to_braille;
 pub use braille::render as render_braille;
+pub use braille::render_colored_into as render_braille_colored_into;
 #[allow(unused_imports)]
 pub use braille::render_into as render_braille_into;
+pub use braille::render_with_threshold as render_braille_with_threshold;
@@ -0,0 +1,424 @@
+//! Reusable camera frame to ASCII frame pipeline.
+
+use super::braille;
+use super::charset::CharSet;
+use super::downsample::{
+    CellColor, downsample_colors_into, downsample_nearest_into, downsample_supersampled_into,
+};
+use super::filters::{DEFAULT_CLIP_PERCENT, auto_levels, unsharp_into};
+use super::grayscale::to_grayscale_into;
+use super::mapping::{
+    DitherMode, GAMMA, gamma_lut, map_to_chars_floyd_steinberg_into, map_to_chars_gamma_lut_into,
+    map_to_chars_into, map_to_chars_ordered_dither_into,
+};
+use std::sync::Arc;
+
+use crate::camera::{Frame, FrameFormat};
+use crate::terminal::{self, AsciiFrame};
+
+/// Default braille dot threshold - lower = more dots = more detail.
+pub const DEFAULT_BRAILLE_THRESHOLD: u8 = 80;
+
+/// Cells per nearest-neighbor sample along each axis in performance mode.
+const PERF_GRID_STEP: u16 = 2;
+
+/// Owns the scratch buffers for converting camera frames to ASCII.
+///
+/// Every buffer (grayscale, supersample grid, brightness, colors, and the
+/// output frame itself) is cleared and refilled in place, so allocations are
+/// amortized to zero: once a render at a given frame and grid size has run,
+/// further renders at that size don't allocate. Buffers only grow when the
+/// camera resolution or grid size increases.
+#[derive(Debug)]
+pub struct AsciiPipeline {
+    /// Invert brightness so dense glyphs mean dark pixels
+    pub invert: bool,
+    /// Supersampling factor for the downsample step (1 = off)
+    pub supersample: u8,
+    /// Nearest-neighbor sampling on a reduced grid, no gamma
+    pub perf: bool,
+    /// Dithering for the density charsets (ignored by braille and perf mode)
+    pub dither: DitherMode,
+    /// Brightness at which braille dots light up
+    pub braille_threshold: u8,
+    /// Stretch the grayscale frame's contrast before mapping (ignored by perf mode)
+    pub auto_levels: bool,
+    /// Unsharp mask strength applied to the grayscale frame (0.0 = off, ignored by perf mode)
+    pub sharpen: f32,
+    /// Display gamma for the density charsets (ignored by braille and perf mode)
+    pub gamma: f32,
+    /// Lookup table for `gamma`, and the gamma it was built for
+    gamma_table: ([u8; 256], f32),
+    gray: Vec<u8>,
+    blurred: Vec<u8>,
+    supersampled: Vec<u8>,
+    brightness: Vec<u8>,
+    dither_error: Vec<i16>,
+    colors: Vec<CellColor>,
+    /// RGB pixels of the last YUV frame
+    rgb: Vec<u8>,
+    /// Back buffer, filled by the next render
+    frame: Arc<AsciiFrame>,
+    /// Front buffer, last handed out by `render_shared`
+    front: Arc<AsciiFrame>,
+}
+
+impl Default for AsciiPipeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AsciiPipeline {
+    /// Create a pipeline with empty buffers, no inversion, no supersampling,
+    /// no dithering, and perf mode off.
+    pub fn new() -> Self {
+        Self {
+            invert: false,
+            supersample: 1,
+            perf: false,
+            dither: DitherMode::None,
+            braille_threshold: DEFAULT_BRAILLE_THRESHOLD,
+            auto_levels: false,
+            sharpen: 0.0,
+            gamma: GAMMA,
+            gamma_table: (gamma_lut(GAMMA), GAMMA),
+            gray: Vec::new(),
+            blurred: Vec::new(),
+            supersampled: Vec::new(),
+            brightness: Vec::new(),
+            dither_error: Vec::new(),
+            colors: Vec::new(),
+            rgb: Vec::new(),
+            frame: Arc::default(),
+            front: Arc::default(),
+        }
+    }
+
+    /// Render a camera frame to a `cols` x `rows` colored ASCII frame.
+    ///
+    /// YUYV and NV12 frames are decoded to RGB for the color stages.
+    ///
+    /// Braille renders at 2x4 subpixel resolution. Other charsets use a
+    /// (supersampled) area-average downsample with the runtime gamma and the
+    /// selected dithering, or the nearest-neighbor fast path when `perf` is set.
+    pub fn render_into(
+        &mut self,
+        frame: &Frame,
+        cols: u16,
+        rows: u16,
+        charset: &CharSet,
+    ) -> &AsciiFrame {
+        // Colors are sampled from RGB, while luma still comes straight from
+        // a YUV frame's Y plane
+        let source = frame;
+        let mut decoded = Frame {
+            data: std::mem::take(&mut self.rgb),
+            width: 0,
+            height: 0,
+            format: FrameFormat::Rgb,
+            timestamp: source.timestamp,
+        };
+        let frame = if source.decode_yuv_into(&mut decoded) {
+            &decoded
+        } else {
+            source
+        };
+
+        // Fill the back buffer in place; if someone still holds it, start afresh
+        let mut out = Arc::get_mut(&mut self.frame)
+            .map(std::mem::take)
+            .unwrap_or_default();
+
+        if self.perf && !charset.is_braille() {
+            downsample_nearest_into(
+                frame,
+                cols,
+                rows,
+                PERF_GRID_STEP,
+                &mut self.brightness,
+                &mut self.colors,
+            );
+            map_to_chars_into(
+                &self.brightness,
+                charset.chars(),
+                self.invert,
+                &mut out.chars,
+            );
+        } else if charset.is_braille() {
+            // Braille tints each glyph by the pixels under its lit dots
+            self.grayscale(source);
+            braille::render_colored_into(
+                frame,
+                &self.gray,
+                cols,
+                rows,
+                self.braille_threshold,
+                self.invert,
+                &mut out.chars,
+                &mut self.colors,
+            );
+        } else {
+            downsample_colors_into(frame, cols, rows, &mut self.colors);
+            self.grayscale(source);
+
+            downsample_supersampled_into(
+                &self.gray,
+                frame.width,
+                frame.height,
+                cols,
+                rows,
+                self.supersample,
+                &mut self.supersampled,
+                &mut self.brightness,
+            );
+            if self.gamma_table.1 != self.gamma {
+                self.gamma_table = (gamma_lut(self.gamma), self.gamma);
+            }
+            let lut = &self.gamma_table.0;
+            let ramp = charset.chars();
+            match self.dither {
+                DitherMode::None => {
+                    map_to_chars_gamma_lut_into(
+                        &self.brightness,
+                        ramp,
+                        self.invert,
+                        lut,
+                        &mut out.chars,
+                    );
+                }
+                DitherMode::Ordered | DitherMode::FloydSteinberg => {
+                    // Dither in gamma space, before inversion like the plain mapping
+                    for value in self.brightness.iter_mut() {
+                        *value = lut[*value as usize];
+                    }
+                    if self.dither == DitherMode::Ordered {
+                        map_to_chars_ordered_dither_into(
+                            &self.brightness,
+                            cols,
+                            ramp,
+                            self.invert,
+                            false,
+                            &mut out.chars,
+                        );
+                    } else {
+                        map_to_chars_floyd_steinberg_into(
+                            &self.brightness,
+                            cols,
+                            rows,
+                            ramp,
+                            self.invert,
+                            false,
+                            &mut self.dither_error,
+                            &mut out.chars,
+                        );
+                    }
+                }
+            }
+        }
+
+        let colors = out.colors.get_or_insert_with(Vec::new);
+        colors.clear();
+        colors.extend(self.colors.iter().map(|c| terminal::CellColor {
+            r: c.r,
+            g: c.g,
+            b: c.b,
+        }));
+        out.width = cols;
+        out.height = rows;
+        self.rgb = decoded.data;
+
+        match Arc::get_mut(&mut self.frame) {
+            Some(back) => *back = out,
+            None => self.frame = Arc::new(out),
+        }
+        &self.frame
+    }
+
+    /// Render like `render_into`, then hand the frame out to share.
+    ///
+    /// The output is double-buffered: the frame returned here becomes the
+    /// front buffer and the next render fills the other one. A caller that
+    /// lets go of the previous frame when it takes a new one costs no
+    /// allocations, and a frame it holds is never written to again.
+    pub fn render_shared(
+        &mut self,
+        frame: &Frame,
+        cols: u16,
+        rows: u16,
+        charset: &CharSet,
+    ) -> Arc<AsciiFrame> {
+        self.render_into(frame, cols, rows, charset);
+        std::mem::swap(&mut self.frame, &mut self.front);
+        Arc::clone(&self.front)
+    }
+
+    /// Fill the grayscale buffer from a frame, running the enabled preprocessing stages.
+    fn grayscale(&mut self, frame: &Frame) {
+        to_grayscale_into(frame, &mut self.gray);
+        if self.auto_levels {
+            auto_levels(&mut self.gray, DEFAULT_CLIP_PERCENT);
+        }
+        unsharp_into(
+            &mut self.gray,
+            frame.width,
+            frame.height,
+            self.sharpen,
+            &mut self.blurred,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    fn gradient_frame(width: u32, height: u32) -> Frame {
+        let data = (0..width * height)
+            .flat_map(|i| [(i % 256) as u8, 128, (255 - i % 256) as u8])
+            .collect();
+        Frame {
+            data,
+            width,
+            height,
+            format: FrameFormat::Rgb,
+            timestamp: Instant::now(),
+        }
+    }
+
+    /// A YUYV frame of one color (Y, U, V for every pixel).
+    fn yuyv_frame(width: u32, height: u32, (y, u, v): (u8, u8, u8)) -> Frame {
+        Frame {
+            data: [y, u, y, v].repeat((width * height / 2) as usize),
+            width,
+            height,
+            format: FrameFormat::Yuyv,
+            timestamp: Instant::now(),
+        }
+    }
+
+    fn capacities(pipeline: &AsciiPipeline) -> [usize; 9] {
+        [
+            pipeline.rgb.capacity(),
+            pipeline.gray.capacity(),
+            pipeline.blurred.capacity(),
+            pipeline.supersampled.capacity(),
+            pipeline.brightness.capacity(),
+            pipeline.dither_error.capacity(),
+            pipeline.colors.capacity(),
+            pipeline.frame.chars.capacity(),
+            pipeline.frame.colors.as_ref().map_or(0, Vec::capacity),
+        ]
+    }
+
+    #[test]
+    fn test_render_into_fills_grid() {
+        let mut pipeline = AsciiPipeline::new();
+        let frame = gradient_frame(64, 48);
+
+        let ascii = pipeline.render_into(&frame, 16, 8, &CharSet::Standard);
+
+        assert_eq!((ascii.width, ascii.height), (16, 8));
+        assert_eq!(ascii.chars.len(), 128);
+        assert_eq!(ascii.colors.as_ref().map(Vec::len), Some(128));
+    }
+
+    #[test]
+    fn test_repeated_renders_do_not_grow_buffers() {
+        for frame in [gradient_frame(64, 48), yuyv_frame(64, 48, (120, 90, 200))] {
+            for charset in [CharSet::Standard, CharSet::Braille] {
+                let mut pipeline = AsciiPipeline::new();
+                pipeline.supersample = 2;
+                pipeline.dither = DitherMode::FloydSteinberg;
+                pipeline.sharpen = 1.0;
+                pipeline.render_into(&frame, 16, 8, &charset);
+                let first = capacities(&pipeline);
+
+                pipeline.render_into(&frame, 16, 8, &charset);
+                assert_eq!(capacities(&pipeline), first, "{:?}", charset);
+            }
+        }
+    }
+
+    #[test]
+    fn test_yuyv_colors_are_sampled_as_rgb() {
+        let frame = yuyv_frame(8, 4, (81, 90, 240));
+        let expected = frame.decode_yuv().unwrap();
+        let (r, g, b) = (expected.data[0], expected.data[1], expected.data[2]);
+        assert!(r > 200 && g < 50 && b < 50, "{:?}", (r, g, b));
+
+        let mut pipeline = AsciiPipeline::new();
+        let ascii = pipeline.render_into(&frame, 4, 2, &CharSet::Standard);
+        let color = ascii.colors.as_ref().unwrap()[0];
+        assert_eq!((color.r, color.g, color.b), (r, g, b));
+
+        // The free function decodes on its own
+        let mut colors = Vec::new();
+        downsample_colors_into(&frame, 4, 2, &mut colors);
+        assert_eq!((colors[0].r, colors[0].g, colors[0].b), (r, g, b));
+    }
+
+    #[test]
+    fn test_perf_mode_matches_grid_size() {
+        let mut pipeline = AsciiPipeline::new();
+        pipeline.perf = true;
+        let frame = gradient_frame(64, 48);
+
+        let ascii = pipeline.render_into(&frame, 10, 5, &CharSet::Blocks);
+
+        assert_eq!(ascii.chars.len(), 50);
+    }
+
+    #[test]
+    fn test_render_shared_alternates_two_buffers() {
+        let mut pipeline = AsciiPipeline::new();
+        let frame = gradient_frame(64, 48);
+
+        // Like the modal: each new frame replaces the one held before
+        let mut shown = pipeline.render_shared(&frame, 16, 8, &CharSet::Standard);
+        let first = Arc::as_ptr(&shown);
+        shown = pipeline.render_shared(&frame, 16, 8, &CharSet::Standard);
+        assert_ne!(Arc::as_ptr(&shown), first);
+        let capacity = shown.chars.capacity();
+        shown = pipeline.render_shared(&frame, 16, 8, &CharSet::Standard);
+        assert_eq!(Arc::as_ptr(&shown), first);
+        assert_eq!(shown.chars.capacity(), capacity);
+    }
+
+    #[test]
+    fn test_held_frames_are_not_written_while_rendering() {
+        let (tx, rx) = std::sync::mpsc::channel::<Arc<AsciiFrame>>();
+        let reader = std::thread::spawn(move || {
+            // Keep a few frames around while newer ones are rendered
+            let mut held = std::collections::VecDeque::new();
+            for frame in rx {
+                held.push_back(frame);
+                if held.len() > 3 {
+                    held.pop_front();
+                }
+                for frame in &held {
+                    assert!(frame.is_complete());
+                    assert!(frame.chars.iter().all(|&c| c == frame.chars[0]));
+                }
+            }
+        });
+
+        let mut pipeline = AsciiPipeline::new();
+        for i in 0..500u32 {
+            let value = (i * 37 % 256) as u8;
+            let frame = Frame {
+                data: vec![value; 40 * 30 * 3],
+                width: 40,
+                height: 30,
+                format: FrameFormat::Rgb,
+                timestamp: Instant::now(),
+            };
+            let (cols, rows) = (4 + (i % 13) as u16, 2 + (i % 7) as u16);
+            tx.send(pipeline.render_shared(&frame, cols, rows, &CharSet::Standard))
+                .unwrap();
+        }
+        drop(tx);
+        reader.join().unwrap();
+    }
+}
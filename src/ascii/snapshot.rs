@@ -0,0 +1,129 @@
+//! One-shot camera frame to plain-text ASCII capture.
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::charset::CharSet;
+use super::pipeline::AsciiPipeline;
+use crate::camera::{CameraCapture, CameraError, CameraSettings, Frame, list_devices};
+
+/// How long to wait for the camera's first frame before giving up.
+const FIRST_FRAME_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Frames to skip before the snapshot so auto-exposure can settle.
+const WARMUP_FRAMES: u32 = 5;
+
+/// Settings for a single ASCII snapshot.
+#[derive(Debug, Clone)]
+pub struct SnapshotSettings {
+    /// Camera to capture from
+    pub camera: CameraSettings,
+    /// Grid width in characters
+    pub cols: u16,
+    /// Grid height in characters
+    pub rows: u16,
+    /// Character set to render with
+    pub charset: CharSet,
+    /// Invert brightness (for light backgrounds)
+    pub invert: bool,
+}
+
+impl Default for SnapshotSettings {
+    fn default() -> Self {
+        Self {
+            camera: CameraSettings::default(),
+            cols: 80,
+            rows: 40,
+            charset: CharSet::default(),
+            invert: false,
+        }
+    }
+}
+
+/// Grab one webcam frame and render it as newline-joined ASCII text.
+///
+/// # Errors
+/// * `CameraError::NoDevices` - If no camera is connected
+/// * `CameraError::StreamFailed` - If no frame arrives within a few seconds
+/// * Any error from opening or starting the camera
+pub fn capture_ascii_snapshot(settings: &SnapshotSettings) -> Result<String, CameraError> {
+    if list_devices()?.is_empty() {
+        return Err(CameraError::NoDevices);
+    }
+
+    let mut capture = CameraCapture::open(settings.camera.clone())?;
+    capture.start()?;
+    let frame = wait_for_frame(&capture)?;
+    capture.stop();
+
+    Ok(render_snapshot(&frame, settings))
+}
+
+/// Render an already-captured frame the way `capture_ascii_snapshot` does.
+pub fn render_snapshot(frame: &Frame, settings: &SnapshotSettings) -> String {
+    let mut pipeline = AsciiPipeline::new();
+    pipeline.invert = settings.invert;
+    pipeline
+        .render_into(frame, settings.cols, settings.rows, &settings.charset)
+        .to_string_display()
+}
+
+/// Poll the capture buffer until a frame past the warm-up arrives.
+fn wait_for_frame(capture: &CameraCapture) -> Result<Frame, CameraError> {
+    let deadline = Instant::now() + FIRST_FRAME_TIMEOUT;
+    let mut last_timestamp = None;
+    let mut seen = 0;
+    let mut latest = None;
+
+    while Instant::now() < deadline {
+        if let Some(frame) = capture.get_frame() {
+            if last_timestamp != Some(frame.timestamp) {
+                last_timestamp = Some(frame.timestamp);
+                seen += 1;
+                if seen > WARMUP_FRAMES {
+                    return Ok(frame);
+                }
+                latest = Some(frame);
+            }
+        } else if !capture.is_running() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    // A slow camera still yields its most recent frame rather than nothing
+    latest.ok_or_else(|| {
+        CameraError::StreamFailed(format!(
+            "no frame received within {}s",
+            FIRST_FRAME_TIMEOUT.as_secs()
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::camera::FrameFormat;
+
+    #[test]
+    fn test_render_snapshot_is_cols_by_rows_text() {
+        let frame = Frame {
+            data: (0..64 * 48).flat_map(|i| [(i % 256) as u8; 3]).collect(),
+            width: 64,
+            height: 48,
+            format: FrameFormat::Rgb,
+            timestamp: Instant::now(),
+        };
+        let settings = SnapshotSettings {
+            cols: 20,
+            rows: 6,
+            ..SnapshotSettings::default()
+        };
+
+        let text = render_snapshot(&frame, &settings);
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines.len(), 6);
+        assert!(lines.iter().all(|line| line.chars().count() == 20));
+    }
+}
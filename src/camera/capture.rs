@@ -7,7 +7,10 @@ use std::thread::JoinHandle;
 
 use super::capture_loop::{CaptureCommand, run_capture_loop};
 use super::device::list_devices;
-use super::types::{CameraError, CameraSettings, Frame, Resolution};
+use super::fps::FpsMeter;
+use super::frame_queue::FrameQueue;
+use super::frame_utils::orient;
+use super::types::{CameraError, CameraSettings, Frame, Resolution, Rotation};
 
 /// Camera capture handle.
 ///
@@ -18,20 +21,26 @@ use super::types::{CameraError, CameraSettings, Frame, Resolution};
 /// and stores the latest frame in a shared buffer. Call `start()` to begin
 /// capturing and `get_frame()` to retrieve the latest frame.
 pub struct CameraCapture {
-    /// Latest captured frame (shared with capture thread)
-    frame_buffer: Arc<Mutex<Option<Frame>>>,
+    /// Frames waiting to be rendered (shared with capture thread)
+    frames: Arc<FrameQueue>,
+    /// Delivered frame rate, fed by the capture thread
+    fps_meter: Arc<Mutex<FpsMeter>>,
     /// Capture thread handle
     capture_thread: Option<JoinHandle<()>>,
     /// Channel to send commands to capture thread
     command_tx: Option<Sender<CaptureCommand>>,
     /// Signal to stop capture thread
     stop_signal: Arc<AtomicBool>,
+    /// Raised by the capture thread while the device is gone and being reopened
+    disconnected: Arc<AtomicBool>,
     /// Current settings
     settings: CameraSettings,
-    /// Actual resolution (set after camera opens)
-    actual_resolution: Option<Resolution>,
-    /// Actual FPS (set after camera opens)
-    actual_fps: Option<u32>,
+    /// Index `settings.device` resolved to when the camera was opened
+    device_index: u32,
+    /// Resolution negotiated with the device (set after camera opens)
+    negotiated_resolution: Option<Resolution>,
+    /// Frame rate negotiated with the device (set after camera opens)
+    negotiated_fps: Option<u32>,
 }
 
 impl std::fmt::Debug for CameraCapture {
@@ -51,25 +60,27 @@ impl CameraCapture {
     /// inside the background thread to avoid thread-safety issues.
     ///
     /// # Arguments
-    /// * `settings` - Camera configuration including device index and resolution
+    /// * `settings` - Camera configuration including device selector and resolution
     ///
     /// # Errors
     /// * `CameraError::DeviceNotFound` - If the device index doesn't exist
+    /// * `CameraError::DeviceNameNotFound` - If no device name matches
     pub fn open(settings: CameraSettings) -> Result<Self, CameraError> {
         // First check if the device exists
         let devices = list_devices()?;
-        if !devices.iter().any(|d| d.index == settings.device_index) {
-            return Err(CameraError::DeviceNotFound(settings.device_index));
-        }
+        let device_index = settings.device.resolve(&devices)?;
 
         Ok(Self {
-            frame_buffer: Arc::new(Mutex::new(None)),
+            frames: Arc::new(FrameQueue::default()),
+            fps_meter: Arc::new(Mutex::new(FpsMeter::default())),
             capture_thread: None,
             command_tx: None,
             stop_signal: Arc::new(AtomicBool::new(false)),
+            disconnected: Arc::new(AtomicBool::new(false)),
             settings,
-            actual_resolution: None,
-            actual_fps: None,
+            device_index,
+            negotiated_resolution: None,
+            negotiated_fps: None,
         })
     }
 
@@ -78,20 +89,39 @@ impl CameraCapture {
         &self.settings
     }
 
-    /// Get the actual resolution the camera is using.
+    /// Index of the opened device (the selector resolved against `list_devices`).
+    pub fn device_index(&self) -> u32 {
+        self.device_index
+    }
+
+    /// Get the resolution the camera is capturing at.
+    ///
+    /// Returns `None` if the camera hasn't been started yet. When the device
+    /// doesn't support `settings().resolution`, this is the mode picked by
+    /// `settings().resolution_fallback`.
+    pub fn negotiated_resolution(&self) -> Option<Resolution> {
+        self.negotiated_resolution
+    }
+
+    /// Get the frame rate the device agreed to when the stream opened.
     ///
-    /// Returns `None` if the camera hasn't been started yet.
-    /// This may differ from the requested resolution if the camera
-    /// doesn't support it exactly.
-    pub fn actual_resolution(&self) -> Option<Resolution> {
-        self.actual_resolution
+    /// Returns `None` if the camera hasn't been started yet. Compare with
+    /// `settings().fps` (the target) and `actual_fps()` (what arrives).
+    pub fn negotiated_fps(&self) -> Option<u32> {
+        self.negotiated_fps
     }
 
-    /// Get the actual frame rate the camera is using.
+    /// Frames discarded unseen because `get_frame` fell behind the camera.
+    pub fn dropped_frames(&self) -> u64 {
+        self.frames.dropped()
+    }
+
+    /// Frame rate actually being delivered, averaged over the last
+    /// `DEFAULT_FPS_WINDOW` frames.
     ///
-    /// Returns `None` if the camera hasn't been started yet.
-    pub fn actual_fps(&self) -> Option<u32> {
-        self.actual_fps
+    /// Returns `None` until at least two frames have arrived.
+    pub fn actual_fps(&self) -> Option<f32> {
+        self.fps_meter.lock().ok()?.fps()
     }
 
     /// Start capturing frames in a background thread.
@@ -117,16 +147,28 @@ impl CameraCapture {
         self.command_tx = Some(tx);
 
         // Clone values for the capture thread
-        let buffer = Arc::clone(&self.frame_buffer);
+        let frames = Arc::clone(&self.frames);
+        let fps = Arc::clone(&self.fps_meter);
         let stop = Arc::clone(&self.stop_signal);
+        let disconnected = Arc::clone(&self.disconnected);
         let settings = self.settings.clone();
+        let device_index = self.device_index;
 
         // Channel to receive actual resolution/fps from thread
         let (info_tx, info_rx) = mpsc::channel::<Result<(Resolution, u32), CameraError>>();
 
         // Spawn background capture thread
         let handle = std::thread::spawn(move || {
-            run_capture_loop(settings, buffer, stop, rx, info_tx);
+            run_capture_loop(
+                device_index,
+                settings,
+                frames,
+                fps,
+                stop,
+                disconnected,
+                rx,
+                info_tx,
+            );
         });
 
         self.capture_thread = Some(handle);
@@ -134,8 +176,8 @@ impl CameraCapture {
         // Wait for the thread to report success or failure
         match info_rx.recv() {
             Ok(Ok((res, fps))) => {
-                self.actual_resolution = Some(res);
-                self.actual_fps = Some(fps);
+                self.negotiated_resolution = Some(res);
+                self.negotiated_fps = Some(fps);
                 Ok(())
             }
             Ok(Err(e)) => {
@@ -177,13 +219,62 @@ impl CameraCapture {
         }
     }
 
-    /// Get the latest captured frame.
+    /// Get the next captured frame, mirrored and rotated per the settings.
     ///
-    /// Returns `None` if no frame has been captured yet or if capturing
-    /// is not running.
+    /// Older waiting frames are skipped for the newest, so a slow caller
+    /// stays live; if none are waiting the previous frame is returned
+    /// again. Returns `None` if no frame has been captured yet.
     pub fn get_frame(&self) -> Option<Frame> {
-        let buffer = self.frame_buffer.lock().ok()?;
-        buffer.clone()
+        let frame = self.frames.next()?;
+        Some(orient(frame, self.settings.mirror, self.settings.rotation))
+    }
+
+    /// Mirror frames horizontally from the next `get_frame` on.
+    pub fn set_mirror(&mut self, mirror: bool) {
+        self.settings.mirror = mirror;
+    }
+
+    /// Rotate frames clockwise from the next `get_frame` on.
+    pub fn set_rotation(&mut self, rotation: Rotation) {
+        self.settings.rotation = rotation;
+    }
+
+    /// Reopen the camera after a disconnect, without rebuilding the capture.
+    ///
+    /// The capture thread already retries on its own with backoff; call this
+    /// once it has given up (`status()` is `Disconnected` but `is_running()`
+    /// is false). The selector is resolved again, since a replugged camera
+    /// may come back at a different index. The last frame is kept.
+    ///
+    /// # Errors
+    /// Same as `open` and `start`; on failure the capture stays `Disconnected`.
+    pub fn reconnect(&mut self) -> Result<(), CameraError> {
+        self.stop();
+        self.disconnected.store(true, Ordering::SeqCst);
+        if let Ok(mut meter) = self.fps_meter.lock() {
+            meter.reset();
+        }
+
+        let result = list_devices()
+            .and_then(|devices| self.settings.device.resolve(&devices))
+            .and_then(|index| {
+                self.device_index = index;
+                self.start()
+            });
+        if result.is_ok() {
+            self.disconnected.store(false, Ordering::SeqCst);
+        }
+        result
+    }
+
+    /// Health of the capture: `Err(CameraError::Disconnected)` while the
+    /// device is gone (and being reopened), `Ok` otherwise.
+    pub fn status(&self) -> Result<(), CameraError> {
+        if self.disconnected.load(Ordering::SeqCst) {
+            Err(CameraError::Disconnected)
+        } else {
+            Ok(())
+        }
     }
 
     /// Check if the capture thread is currently running.
@@ -203,15 +294,19 @@ impl Drop for CameraCapture {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::camera::{DeviceSelector, ResolutionFallback};
 
     #[test]
     fn test_camera_open_invalid_device() {
         // Use a device index that is very unlikely to exist
         let settings = CameraSettings {
-            device_index: 999,
+            device: DeviceSelector::Index(999),
             resolution: Resolution::default(),
+            resolution_fallback: ResolutionFallback::default(),
             fps: 30,
             mirror: true,
+            rotation: Rotation::Deg0,
+            grayscale: false,
         };
         let result = CameraCapture::open(settings);
         assert!(result.is_err());
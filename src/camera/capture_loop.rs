@@ -1,7 +1,7 @@
 //! Background capture thread implementation.
 
 use nokhwa::Camera;
-use nokhwa::pixel_format::RgbFormat;
+use nokhwa::pixel_format::{LumaFormat, RgbFormat};
 use nokhwa::utils::{
     CameraFormat, CameraIndex, FrameFormat as NokhwaFrameFormat, RequestedFormat,
     RequestedFormatType,
@@ -12,26 +12,118 @@ use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
-use super::frame_utils::{convert_to_rgb, mirror_horizontal};
-use super::types::{CameraError, CameraSettings, Frame, Resolution};
+use super::fps::FpsMeter;
+use super::frame_queue::FrameQueue;
+use super::frame_utils::{convert_to_gray, convert_to_rgb};
+use super::types::{CameraError, CameraSettings, Resolution};
 
 /// Commands sent to the capture thread.
 pub enum CaptureCommand {
     Stop,
 }
 
+/// Consecutive failed reads before the device is treated as unplugged.
+const MAX_READ_FAILURES: u32 = 30;
+
+/// Bounded exponential backoff for reopening a camera.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Backoff {
+    /// Reopen attempts before giving up
+    pub attempts: u32,
+    /// Delay after the first failed attempt
+    pub initial: Duration,
+    /// Upper bound on the delay between attempts
+    pub max: Duration,
+}
+
+impl Backoff {
+    /// Backoff used after a disconnect: 8 attempts over roughly 15 seconds.
+    pub(crate) const RECONNECT: Backoff = Backoff {
+        attempts: 8,
+        initial: Duration::from_millis(250),
+        max: Duration::from_secs(4),
+    };
+
+    /// Delay after failed attempt `n` (0-based), doubling up to `max`.
+    fn delay(&self, n: u32) -> Duration {
+        self.initial
+            .saturating_mul(1u32.checked_shl(n).unwrap_or(u32::MAX))
+            .min(self.max)
+    }
+}
+
+/// Reopen a disconnected camera, retrying `reopen` with `backoff`.
+///
+/// `disconnected` is set for the duration so the UI can say so, and cleared
+/// once `reopen` succeeds. Returns `None`, leaving the flag set, when the
+/// attempts run out or `stop` is raised.
+pub(crate) fn reconnect_with<C>(
+    backoff: Backoff,
+    disconnected: &AtomicBool,
+    stop: &AtomicBool,
+    mut sleep: impl FnMut(Duration),
+    mut reopen: impl FnMut() -> Result<C, CameraError>,
+) -> Option<C> {
+    disconnected.store(true, Ordering::SeqCst);
+    for n in 0..backoff.attempts {
+        if stop.load(Ordering::Relaxed) {
+            return None;
+        }
+        if let Ok(camera) = reopen() {
+            disconnected.store(false, Ordering::SeqCst);
+            return Some(camera);
+        }
+        sleep(backoff.delay(n));
+    }
+    None
+}
+
+/// Sleep for `duration` in short slices, returning early once `stop` is raised.
+fn sleep_unless_stopped(stop: &AtomicBool, duration: Duration) {
+    let slice = Duration::from_millis(50);
+    let mut remaining = duration;
+    while !remaining.is_zero() && !stop.load(Ordering::Relaxed) {
+        let step = remaining.min(slice);
+        thread::sleep(step);
+        remaining -= step;
+    }
+}
+
+/// Open a camera and start its stream.
+fn open_streaming(
+    device_index: u32,
+    index: &CameraIndex,
+    settings: &CameraSettings,
+) -> Result<Camera, CameraError> {
+    let mut camera = open_camera_with_fallback(device_index, index, settings)?;
+    camera
+        .open_stream()
+        .map_err(|e| CameraError::StreamFailed(e.to_string()))?;
+    Ok(camera)
+}
+
 /// Run the capture loop in a background thread.
+///
+/// After `MAX_READ_FAILURES` failed reads in a row the camera is reopened
+/// with `Backoff::RECONNECT`, raising `disconnected` meanwhile. The last good
+/// frame stays in `frames` so the overlay keeps drawing. If reopening fails,
+/// the thread exits with `disconnected` still set. Every stored frame's
+/// timestamp goes into `fps`.
+#[allow(clippy::too_many_arguments)]
 pub fn run_capture_loop(
+    device_index: u32,
     settings: CameraSettings,
-    buffer: Arc<Mutex<Option<Frame>>>,
+    frames: Arc<FrameQueue>,
+    fps: Arc<Mutex<FpsMeter>>,
     stop: Arc<AtomicBool>,
+    disconnected: Arc<AtomicBool>,
     rx: Receiver<CaptureCommand>,
     info_tx: Sender<Result<(Resolution, u32), CameraError>>,
 ) {
-    let index = CameraIndex::Index(settings.device_index);
+    let index = CameraIndex::Index(device_index);
 
     // Try multiple format strategies in order of preference
-    let camera = match open_camera_with_fallback(&index, &settings) {
+    let camera = match open_camera_with_fallback(device_index, &index, &settings) {
         Ok(cam) => cam,
         Err(e) => {
             let _ = info_tx.send(Err(e));
@@ -57,6 +149,7 @@ pub fn run_capture_loop(
     let _ = info_tx.send(Ok((actual_res, actual_fps)));
 
     // Capture loop
+    let mut read_failures = 0;
     while !stop.load(Ordering::Relaxed) {
         // Check for commands (non-blocking)
         if let Ok(CaptureCommand::Stop) = rx.try_recv() {
@@ -64,21 +157,56 @@ pub fn run_capture_loop(
         }
 
         // Try to capture a frame
-        if let Ok(raw_frame) = camera.frame() {
-            // Convert to RGB Frame (handles MJPEG, YUYV, and other formats)
-            if let Some(mut frame) = convert_to_rgb(&raw_frame) {
-                // Apply mirroring if enabled
-                if settings.mirror {
-                    mirror_horizontal(&mut frame);
+        let raw_frame = match camera.frame() {
+            Ok(raw_frame) => {
+                read_failures = 0;
+                raw_frame
+            }
+            Err(_) => {
+                read_failures += 1;
+                if read_failures >= MAX_READ_FAILURES {
+                    let _ = camera.stop_stream();
+                    match reconnect_with(
+                        Backoff::RECONNECT,
+                        &disconnected,
+                        &stop,
+                        |delay| sleep_unless_stopped(&stop, delay),
+                        || open_streaming(device_index, &index, &settings),
+                    ) {
+                        Some(reopened) => {
+                            camera = reopened;
+                            read_failures = 0;
+                            // The gap while unplugged isn't the camera's frame rate
+                            if let Ok(mut meter) = fps.lock() {
+                                meter.reset();
+                            }
+                        }
+                        None => return,
+                    }
                 }
+                thread::sleep(Duration::from_millis(1));
+                continue;
+            }
+        };
 
-                // Store in shared buffer
-                if let Ok(mut buf) = buffer.lock() {
-                    *buf = Some(frame);
-                }
+        // Grayscale mode copies the luma plane when the format has one;
+        // otherwise decode to RGB (handles MJPEG, YUYV, and other formats)
+        let converted = if settings.grayscale {
+            convert_to_gray(&raw_frame).or_else(|| convert_to_rgb(&raw_frame))
+        } else {
+            convert_to_rgb(&raw_frame)
+        };
+        // Mirroring and rotation happen in `CameraCapture::get_frame`, so they
+        // can change while capturing
+        if let Some(frame) = converted {
+            if let Ok(mut meter) = fps.lock() {
+                meter.record(frame.timestamp);
             }
-            // If conversion fails, silently skip this frame and try the next one
+
+            // Hand off to the renderer, dropping the oldest frame if it lags
+            frames.push(frame);
         }
+        // If conversion fails, silently skip this frame and try the next one
 
         // Small sleep to allow checking stop signal
         thread::sleep(Duration::from_millis(1));
@@ -90,6 +218,7 @@ pub fn run_capture_loop(
 
 /// Try to open a camera with multiple format fallback strategies.
 fn open_camera_with_fallback(
+    device_index: u32,
     index: &CameraIndex,
     settings: &CameraSettings,
 ) -> Result<Camera, CameraError> {
@@ -97,7 +226,7 @@ fn open_camera_with_fallback(
     // 1. Closest match with NV12 (common on macOS)
     // 2. Closest match with MJPEG (widely supported)
     // 3. Highest resolution available (let camera decide format)
-    let format_attempts: Vec<RequestedFormat> = vec![
+    let mut format_attempts: Vec<RequestedFormat> = vec![
         // Try NV12 first (native macOS format)
         RequestedFormat::new::<RgbFormat>(RequestedFormatType::Closest(CameraFormat::new(
             nokhwa::utils::Resolution::new(settings.resolution.width, settings.resolution.height),
@@ -114,11 +243,31 @@ fn open_camera_with_fallback(
         RequestedFormat::new::<RgbFormat>(RequestedFormatType::AbsoluteHighestResolution),
     ];
 
+    // Grayscale capture prefers a Y-only format where the device offers one
+    if settings.grayscale {
+        format_attempts.insert(
+            0,
+            RequestedFormat::new::<LumaFormat>(RequestedFormatType::Closest(CameraFormat::new(
+                nokhwa::utils::Resolution::new(
+                    settings.resolution.width,
+                    settings.resolution.height,
+                ),
+                NokhwaFrameFormat::GRAY,
+                settings.fps,
+            ))),
+        );
+    }
+
     let mut last_error = None;
+    let mut unsupported = None;
 
     for requested in format_attempts {
         match Camera::new(index.clone(), requested) {
-            Ok(cam) => return Ok(cam),
+            Ok(mut cam) => match negotiate_resolution(&mut cam, settings) {
+                Ok(()) => return Ok(cam),
+                // Another pixel format may still offer the exact resolution
+                Err(e) => unsupported = Some(e),
+            },
             Err(e) => {
                 last_error = Some(e);
                 continue;
@@ -126,15 +275,201 @@ fn open_camera_with_fallback(
         }
     }
 
+    if let Some(e) = unsupported {
+        return Err(e);
+    }
     let e = last_error.unwrap();
-    let msg = e.to_string().to_lowercase();
-    if msg.contains("permission")
-        || msg.contains("denied")
-        || msg.contains("authorization")
-        || msg.contains("access")
+    Err(classify_open_error(device_index, &e.to_string()))
+}
+
+/// Switch an opened camera to the mode `settings.resolution_fallback` picks.
+///
+/// nokhwa's `Closest` request lands near the requested size by its own
+/// metric; this applies ours to the modes the device lists for the pixel
+/// format it settled on. Devices that can't list their modes keep whatever
+/// the backend chose.
+fn negotiate_resolution(camera: &mut Camera, settings: &CameraSettings) -> Result<(), CameraError> {
+    let frame_format = camera.frame_format();
+    let Ok(formats) = camera.compatible_camera_formats() else {
+        return Ok(());
+    };
+    let supported: Vec<Resolution> = formats
+        .iter()
+        .filter(|format| format.format() == frame_format)
+        .map(|format| Resolution {
+            width: format.resolution().width(),
+            height: format.resolution().height(),
+        })
+        .collect();
+
+    match settings
+        .resolution_fallback
+        .select(settings.resolution, &supported)
     {
-        Err(CameraError::PermissionDenied)
+        Some(res) => {
+            let current = camera.resolution();
+            if (current.width(), current.height()) != (res.width, res.height) {
+                // If the backend refuses the switch its own pick stands
+                let _ =
+                    camera.set_resolution(nokhwa::utils::Resolution::new(res.width, res.height));
+            }
+            Ok(())
+        }
+        None if supported.is_empty() => Ok(()),
+        None => Err(CameraError::UnsupportedResolution(settings.resolution)),
+    }
+}
+
+/// Classify a camera open failure from the backend's error message.
+///
+/// macOS reports a TCC-denied camera differently from a missing device, and
+/// the fixes differ (grant access vs. pick another index), so permission and
+/// not-found failures get their own variants. Anything else is `OpenFailed`.
+pub(crate) fn classify_open_error(device_index: u32, msg: &str) -> CameraError {
+    const PERMISSION_HINTS: &[&str] = &[
+        "permission",
+        "denied",
+        "authoriz",
+        "not permitted",
+        "access",
+    ];
+    const NOT_FOUND_HINTS: &[&str] = &[
+        "not found",
+        "no such device",
+        "no device",
+        "out of range",
+        "invalid index",
+    ];
+
+    let lower = msg.to_lowercase();
+    if PERMISSION_HINTS.iter().any(|hint| lower.contains(hint)) {
+        CameraError::PermissionDenied
+    } else if NOT_FOUND_HINTS.iter().any(|hint| lower.contains(hint)) {
+        CameraError::DeviceNotFound(device_index)
     } else {
-        Err(CameraError::OpenFailed(e.to_string()))
+        CameraError::OpenFailed(msg.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FAST: Backoff = Backoff {
+        attempts: 4,
+        initial: Duration::from_millis(10),
+        max: Duration::from_millis(25),
+    };
+
+    #[test]
+    fn test_backoff_delay_doubles_up_to_max() {
+        let delays: Vec<Duration> = (0..4).map(|n| FAST.delay(n)).collect();
+        assert_eq!(delays, [10, 20, 25, 25].map(Duration::from_millis).to_vec());
+        assert_eq!(Backoff::RECONNECT.delay(40), Backoff::RECONNECT.max);
+    }
+
+    #[test]
+    fn test_reconnect_after_read_error_reopens() {
+        // A read error flags the capture as disconnected; the first reopen
+        // fails (device still unplugged) and the second succeeds
+        let disconnected = AtomicBool::new(false);
+        let stop = AtomicBool::new(false);
+        let mut sleeps = Vec::new();
+        let mut attempts = 0;
+
+        let reopened = reconnect_with(
+            FAST,
+            &disconnected,
+            &stop,
+            |delay| {
+                assert!(disconnected.load(Ordering::SeqCst));
+                sleeps.push(delay);
+            },
+            || {
+                attempts += 1;
+                if attempts == 1 {
+                    Err(CameraError::DeviceNotFound(0))
+                } else {
+                    Ok("camera")
+                }
+            },
+        );
+
+        assert_eq!(reopened, Some("camera"));
+        assert_eq!(attempts, 2);
+        assert_eq!(sleeps, vec![FAST.initial]);
+        assert!(!disconnected.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_reconnect_gives_up_after_bounded_attempts() {
+        let disconnected = AtomicBool::new(false);
+        let stop = AtomicBool::new(false);
+        let mut attempts = 0;
+
+        let reopened: Option<()> = reconnect_with(
+            FAST,
+            &disconnected,
+            &stop,
+            |_| {},
+            || {
+                attempts += 1;
+                Err(CameraError::OpenFailed("unplugged".to_string()))
+            },
+        );
+
+        assert!(reopened.is_none());
+        assert_eq!(attempts, FAST.attempts);
+        assert!(disconnected.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_reconnect_stops_when_asked() {
+        let disconnected = AtomicBool::new(false);
+        let stop = AtomicBool::new(true);
+
+        let reopened = reconnect_with(FAST, &disconnected, &stop, |_| {}, || Ok(()));
+
+        assert!(reopened.is_none());
+    }
+
+    #[test]
+    fn test_classify_open_error_permission_denied() {
+        let cases = [
+            "AVFoundation: AVAuthorizationStatusDenied",
+            "Camera access denied by user",
+            "Not authorized to capture video",
+            "Permission to use the camera was not granted",
+        ];
+        for msg in cases {
+            assert!(
+                matches!(classify_open_error(0, msg), CameraError::PermissionDenied),
+                "Expected PermissionDenied for {:?}",
+                msg
+            );
+        }
+    }
+
+    #[test]
+    fn test_classify_open_error_device_not_found() {
+        let cases = [
+            "Could not find device: Device 3 not found",
+            "No such device (os error 19)",
+            "Index out of range",
+        ];
+        for msg in cases {
+            match classify_open_error(3, msg) {
+                CameraError::DeviceNotFound(idx) => assert_eq!(idx, 3),
+                other => panic!("Expected DeviceNotFound for {:?}, got {:?}", msg, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_classify_open_error_other() {
+        match classify_open_error(0, "Failed to set format") {
+            CameraError::OpenFailed(msg) => assert_eq!(msg, "Failed to set format"),
+            other => panic!("Expected OpenFailed, got {:?}", other),
+        }
     }
 }
@@ -1,9 +1,10 @@
 //! Camera device enumeration.
 
-use nokhwa::query;
-use nokhwa::utils::ApiBackend;
+use nokhwa::pixel_format::RgbFormat;
+use nokhwa::utils::{ApiBackend, CameraIndex, RequestedFormat, RequestedFormatType};
+use nokhwa::{Camera, query};
 
-use super::types::{CameraError, CameraInfo};
+use super::types::{CameraError, CameraInfo, CameraMode, DeviceKind, Resolution};
 
 /// List all available camera devices on the system.
 ///
@@ -14,14 +15,66 @@ pub fn list_devices() -> Result<Vec<CameraInfo>, CameraError> {
 
     Ok(devices
         .into_iter()
-        .map(|d| CameraInfo {
-            index: d.index().as_index().unwrap_or(0),
-            name: d.human_name(),
-            description: d.description().to_string(),
+        .map(|d| {
+            let name = d.human_name();
+            let description = d.description().to_string();
+            CameraInfo {
+                index: d.index().as_index().unwrap_or(0),
+                kind: DeviceKind::detect(&name, &description),
+                name,
+                description,
+                modes: Vec::new(),
+            }
         })
         .collect())
 }
 
+/// List all cameras along with the capture modes each one supports.
+///
+/// Unlike `list_devices`, this briefly opens every device to query it, so it
+/// is slower and may light the camera LED. Devices that can't be opened or
+/// don't support enumeration are still listed, with no modes.
+pub fn list_devices_verbose() -> Result<Vec<CameraInfo>, CameraError> {
+    let mut devices = list_devices()?;
+    for device in &mut devices {
+        device.modes = query_modes(device.index);
+    }
+    Ok(devices)
+}
+
+/// Capture modes reported by a device, sorted by resolution then frame rate
+/// (largest first).
+fn query_modes(index: u32) -> Vec<CameraMode> {
+    let requested = RequestedFormat::new::<RgbFormat>(RequestedFormatType::None);
+    let Ok(mut camera) = Camera::new(CameraIndex::Index(index), requested) else {
+        return Vec::new();
+    };
+    let Ok(formats) = camera.compatible_camera_formats() else {
+        return Vec::new();
+    };
+
+    let mut modes: Vec<CameraMode> = formats
+        .into_iter()
+        .map(|f| CameraMode {
+            resolution: Resolution {
+                width: f.resolution().width(),
+                height: f.resolution().height(),
+            },
+            format: f.format().to_string(),
+            fps: f.frame_rate(),
+        })
+        .collect();
+    modes.sort_by_key(|m| {
+        std::cmp::Reverse((
+            m.resolution.width * m.resolution.height,
+            m.resolution.width,
+            m.fps,
+        ))
+    });
+    modes.dedup();
+    modes
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
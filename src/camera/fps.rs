@@ -0,0 +1,106 @@
+//! Rolling frame rate measurement from frame timestamps.
+
+use std::collections::VecDeque;
+use std::time::Instant;
+
+/// Frames averaged by `FpsMeter::default()` (about a second at 30 FPS).
+pub const DEFAULT_FPS_WINDOW: usize = 30;
+
+/// Measures the delivered frame rate over the last N frame timestamps.
+#[derive(Debug, Clone)]
+pub struct FpsMeter {
+    timestamps: VecDeque<Instant>,
+    window: usize,
+}
+
+impl Default for FpsMeter {
+    fn default() -> Self {
+        Self::new(DEFAULT_FPS_WINDOW)
+    }
+}
+
+impl FpsMeter {
+    /// Create a meter averaging over the last `window` frames (at least 2).
+    pub fn new(window: usize) -> Self {
+        let window = window.max(2);
+        Self {
+            timestamps: VecDeque::with_capacity(window),
+            window,
+        }
+    }
+
+    /// Record a frame captured at `timestamp`.
+    pub fn record(&mut self, timestamp: Instant) {
+        if self.timestamps.len() == self.window {
+            self.timestamps.pop_front();
+        }
+        self.timestamps.push_back(timestamp);
+    }
+
+    /// Average frames per second across the window, or `None` until two
+    /// frames with distinct timestamps have been recorded.
+    pub fn fps(&self) -> Option<f32> {
+        let (first, last) = (self.timestamps.front()?, self.timestamps.back()?);
+        let elapsed = last.duration_since(*first).as_secs_f32();
+        if elapsed <= 0.0 {
+            return None;
+        }
+        Some((self.timestamps.len() - 1) as f32 / elapsed)
+    }
+
+    /// Forget all recorded frames (e.g. after the camera reconnects).
+    pub fn reset(&mut self) {
+        self.timestamps.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_fps_from_synthetic_timestamps() {
+        let mut meter = FpsMeter::new(10);
+        let start = Instant::now();
+        // 25 FPS = one frame every 40ms, with more frames than the window holds
+        for i in 0..40 {
+            meter.record(start + Duration::from_millis(40 * i));
+        }
+
+        let fps = meter.fps().unwrap();
+        assert!((fps - 25.0).abs() < 0.1, "expected ~25fps, got {}", fps);
+    }
+
+    #[test]
+    fn test_fps_window_tracks_rate_changes() {
+        let mut meter = FpsMeter::new(5);
+        let start = Instant::now();
+        for i in 0..5 {
+            meter.record(start + Duration::from_millis(100 * i));
+        }
+        assert!((meter.fps().unwrap() - 10.0).abs() < 0.1);
+
+        // Speed up to 50 FPS; once the window has rolled over only that counts
+        let later = start + Duration::from_millis(400);
+        for i in 1..=5 {
+            meter.record(later + Duration::from_millis(20 * i));
+        }
+        assert!((meter.fps().unwrap() - 50.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_fps_needs_two_frames() {
+        let mut meter = FpsMeter::default();
+        assert_eq!(meter.fps(), None);
+
+        let now = Instant::now();
+        meter.record(now);
+        assert_eq!(meter.fps(), None);
+        meter.record(now);
+        assert_eq!(meter.fps(), None);
+
+        meter.reset();
+        assert_eq!(meter.fps(), None);
+    }
+}
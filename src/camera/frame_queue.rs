@@ -0,0 +1,133 @@
+//! Bounded hand-off of frames from the capture thread to the renderer.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::types::Frame;
+
+/// Frames the capture thread may run ahead of the renderer.
+pub const FRAME_QUEUE_CAPACITY: usize = 2;
+
+/// Bounded frame queue that drops the oldest frame when full.
+///
+/// The renderer always takes the newest frame, skipping any older ones still
+/// queued, so a renderer slower than the camera never adds delay. The last
+/// frame handed out is kept, so polling an empty queue keeps returning it
+/// (e.g. while the camera is reconnecting).
+#[derive(Debug)]
+pub struct FrameQueue {
+    state: Mutex<QueueState>,
+    capacity: usize,
+    dropped: AtomicU64,
+}
+
+#[derive(Debug, Default)]
+struct QueueState {
+    frames: VecDeque<Frame>,
+    last: Option<Frame>,
+}
+
+impl Default for FrameQueue {
+    fn default() -> Self {
+        Self::new(FRAME_QUEUE_CAPACITY)
+    }
+}
+
+impl FrameQueue {
+    /// Create a queue holding at most `capacity` frames (at least 1).
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            state: Mutex::new(QueueState::default()),
+            capacity: capacity.max(1),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Queue a frame, discarding the oldest one if the queue is full.
+    pub fn push(&self, frame: Frame) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        if state.frames.len() == self.capacity {
+            state.frames.pop_front();
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+        state.frames.push_back(frame);
+    }
+
+    /// Take the newest queued frame, or the last one taken if none are waiting.
+    ///
+    /// Older queued frames are discarded. Returns `None` until the first
+    /// frame has been pushed.
+    pub fn next(&self) -> Option<Frame> {
+        let mut state = self.state.lock().ok()?;
+        if let Some(frame) = state.frames.pop_back() {
+            let skipped = state.frames.len() as u64;
+            state.frames.clear();
+            self.dropped.fetch_add(skipped, Ordering::Relaxed);
+            state.last = Some(frame);
+        }
+        state.last.clone()
+    }
+
+    /// Frames waiting to be taken.
+    #[cfg(test)]
+    fn depth(&self) -> usize {
+        self.state.lock().map_or(0, |state| state.frames.len())
+    }
+
+    /// Frames discarded because the queue was full or a newer one was taken.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::camera::FrameFormat;
+    use std::time::Instant;
+
+    fn frame(value: u8) -> Frame {
+        Frame {
+            data: vec![value],
+            width: 1,
+            height: 1,
+            format: FrameFormat::Gray,
+            timestamp: Instant::now(),
+        }
+    }
+
+    #[test]
+    fn test_fast_producer_keeps_depth_bounded() {
+        let queue = FrameQueue::new(2);
+
+        // Producer pushes 10 frames for every one the consumer takes
+        for round in 0..3u8 {
+            for i in 0..10u8 {
+                queue.push(frame(round * 10 + i));
+                assert!(queue.depth() <= 2);
+            }
+            // The consumer gets the newest frame, not one from rounds ago
+            assert_eq!(queue.next().unwrap().data, vec![round * 10 + 9]);
+            assert_eq!(queue.depth(), 0);
+        }
+
+        assert_eq!(queue.dropped(), 9 + 9 + 9);
+    }
+
+    #[test]
+    fn test_next_repeats_last_frame_when_empty() {
+        let queue = FrameQueue::default();
+        assert!(queue.next().is_none());
+
+        queue.push(frame(1));
+        assert_eq!(queue.next().unwrap().data, vec![1]);
+        assert_eq!(queue.next().unwrap().data, vec![1]);
+        queue.push(frame(2));
+        assert_eq!(queue.next().unwrap().data, vec![2]);
+        assert_eq!(queue.next().unwrap().data, vec![2]);
+        assert_eq!(queue.dropped(), 0);
+    }
+}
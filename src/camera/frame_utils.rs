@@ -1,23 +1,36 @@
 //! Frame conversion and transformation utilities.
 
 use nokhwa::pixel_format::RgbFormat;
+use nokhwa::utils::FrameFormat as NokhwaFrameFormat;
 use std::time::Instant;
 
-use super::types::{Frame, FrameFormat};
+use super::types::{Frame, FrameFormat, Rotation};
 
 /// Convert a nokhwa buffer to our RGB Frame format.
 ///
-/// Handles various camera formats (MJPEG, YUYV, NV12, etc.) by using
-/// nokhwa's built-in decode_image which automatically converts from
-/// the camera's native format to RGB.
+/// YUYV and NV12 buffers go through `yuyv_to_rgb` / `nv12_to_rgb`; other
+/// camera formats (MJPEG etc.) use nokhwa's built-in decode_image, which
+/// converts from the camera's native format to RGB.
 ///
 /// Returns `None` if the conversion fails (unsupported format or corrupt data).
 pub fn convert_to_rgb(buffer: &nokhwa::Buffer) -> Option<Frame> {
-    let decoded = buffer.decode_image::<RgbFormat>().ok()?;
     let resolution = buffer.resolution();
+    let (width, height) = (resolution.width(), resolution.height());
+    let raw = buffer.buffer();
+
+    let data = match buffer.source_frame_format() {
+        NokhwaFrameFormat::YUYV if raw.len() >= frame_len(FrameFormat::Yuyv, width, height) => {
+            yuyv_to_rgb(raw, width, height)
+        }
+        NokhwaFrameFormat::NV12 if raw.len() >= frame_len(FrameFormat::Nv12, width, height) => {
+            nv12_to_rgb(raw, width, height)
+        }
+        NokhwaFrameFormat::YUYV | NokhwaFrameFormat::NV12 => return None,
+        _ => buffer.decode_image::<RgbFormat>().ok()?.into_raw(),
+    };
 
     Some(Frame {
-        data: decoded.into_raw(),
+        data,
         width: resolution.width(),
         height: resolution.height(),
         format: FrameFormat::Rgb,
@@ -25,6 +38,131 @@ pub fn convert_to_rgb(buffer: &nokhwa::Buffer) -> Option<Frame> {
     })
 }
 
+/// Extract a grayscale Frame straight from a buffer's luma channel.
+///
+/// NV12 stores a full-resolution Y plane before the chroma plane and YUYV
+/// interleaves Y with chroma, so luma can be copied out without decoding to
+/// RGB. Note that camera luma is typically limited range (16-235).
+///
+/// Returns `None` for formats without a luma channel (e.g. MJPEG), in which
+/// case the caller should fall back to `convert_to_rgb`.
+pub fn convert_to_gray(buffer: &nokhwa::Buffer) -> Option<Frame> {
+    let resolution = buffer.resolution();
+    let data = extract_luma(
+        buffer.source_frame_format(),
+        buffer.buffer(),
+        resolution.width(),
+        resolution.height(),
+    )?;
+
+    Some(Frame {
+        data,
+        width: resolution.width(),
+        height: resolution.height(),
+        format: FrameFormat::Gray,
+        timestamp: Instant::now(),
+    })
+}
+
+/// Copy the luma (Y) samples out of a raw camera buffer.
+fn extract_luma(format: NokhwaFrameFormat, raw: &[u8], width: u32, height: u32) -> Option<Vec<u8>> {
+    let pixel_count = (width * height) as usize;
+    match format {
+        NokhwaFrameFormat::GRAY | NokhwaFrameFormat::NV12 => {
+            raw.get(..pixel_count).map(|y| y.to_vec())
+        }
+        NokhwaFrameFormat::YUYV => {
+            if raw.len() < pixel_count * 2 {
+                return None;
+            }
+            Some(yuyv_luma(raw, pixel_count))
+        }
+        _ => None,
+    }
+}
+
+/// The Y samples of a YUYV buffer (every other byte).
+pub(crate) fn yuyv_luma(raw: &[u8], pixel_count: usize) -> Vec<u8> {
+    raw.iter().step_by(2).take(pixel_count).copied().collect()
+}
+
+/// Bytes in a complete `width` x `height` frame of `format`.
+fn frame_len(format: FrameFormat, width: u32, height: u32) -> usize {
+    let (w, h) = (width as usize, height as usize);
+    match format {
+        FrameFormat::Yuyv => w * h * 2,
+        FrameFormat::Nv12 => w * h + w.div_ceil(2) * h.div_ceil(2) * 2,
+        FrameFormat::Rgb => w * h * 3,
+        FrameFormat::Gray => w * h,
+    }
+}
+
+/// BT.601 limited-range (16-235) YUV to RGB for one pixel, in integer math.
+fn yuv_to_rgb(y: u8, u: u8, v: u8) -> [u8; 3] {
+    let c = 298 * (y as i32 - 16);
+    let d = u as i32 - 128;
+    let e = v as i32 - 128;
+    let channel = |x: i32| ((x + 128) >> 8).clamp(0, 255) as u8;
+    [
+        channel(c + 409 * e),
+        channel(c - 100 * d - 208 * e),
+        channel(c + 516 * d),
+    ]
+}
+
+/// Decode packed YUYV (`Y0 U Y1 V` per pixel pair) to RGB triplets.
+///
+/// The output always holds `width * height` pixels; a short buffer leaves
+/// the missing ones black.
+pub fn yuyv_to_rgb(raw: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let mut rgb = Vec::new();
+    yuyv_to_rgb_into(raw, width, height, &mut rgb);
+    rgb
+}
+
+/// `yuyv_to_rgb` into `rgb`, reusing its allocation.
+pub fn yuyv_to_rgb_into(raw: &[u8], width: u32, height: u32, rgb: &mut Vec<u8>) {
+    let pixel_count = (width * height) as usize;
+    rgb.clear();
+    rgb.reserve(pixel_count * 3 + 3);
+    for quad in raw.chunks_exact(4).take(pixel_count.div_ceil(2)) {
+        let (u, v) = (quad[1], quad[3]);
+        rgb.extend_from_slice(&yuv_to_rgb(quad[0], u, v));
+        rgb.extend_from_slice(&yuv_to_rgb(quad[2], u, v));
+    }
+    rgb.resize(pixel_count * 3, 0);
+}
+
+/// Decode NV12 (Y plane, then interleaved UV per 2x2 block) to RGB triplets.
+///
+/// The output always holds `width * height` pixels; a short buffer leaves
+/// missing luma black and missing chroma neutral.
+pub fn nv12_to_rgb(raw: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let mut rgb = Vec::new();
+    nv12_to_rgb_into(raw, width, height, &mut rgb);
+    rgb
+}
+
+/// `nv12_to_rgb` into `rgb`, reusing its allocation.
+pub fn nv12_to_rgb_into(raw: &[u8], width: u32, height: u32, rgb: &mut Vec<u8>) {
+    let (w, h) = (width as usize, height as usize);
+    let (y_plane, uv_plane) = raw.split_at((w * h).min(raw.len()));
+    let uv_stride = w.div_ceil(2) * 2;
+    rgb.clear();
+    rgb.reserve(w * h * 3);
+
+    for row in 0..h {
+        let uv_row = row / 2 * uv_stride;
+        for col in 0..w {
+            let y = y_plane.get(row * w + col).copied().unwrap_or(0);
+            let uv = uv_row + col / 2 * 2;
+            let u = uv_plane.get(uv).copied().unwrap_or(128);
+            let v = uv_plane.get(uv + 1).copied().unwrap_or(128);
+            rgb.extend_from_slice(&yuv_to_rgb(y, u, v));
+        }
+    }
+}
+
 /// Mirror a frame horizontally (flip left-right) for selfie mode.
 pub fn mirror_horizontal(frame: &mut Frame) {
     let width = frame.width as usize;
@@ -46,9 +184,282 @@ pub fn mirror_horizontal(frame: &mut Frame) {
     }
 }
 
+/// Rotate a frame a half turn in place (equivalent to flipping both axes).
+pub fn rotate_180(frame: &mut Frame) {
+    let bpp = frame.bytes_per_pixel();
+    let pixels = frame.data.len() / bpp;
+
+    for i in 0..pixels / 2 {
+        let a = i * bpp;
+        let b = (pixels - 1 - i) * bpp;
+        for c in 0..bpp {
+            frame.data.swap(a + c, b + c);
+        }
+    }
+}
+
+/// Rotate a frame a quarter turn into a new frame, swapping width and height.
+pub fn rotate_quarter(frame: &Frame, clockwise: bool) -> Frame {
+    let (width, height) = (frame.width as usize, frame.height as usize);
+    let bpp = frame.bytes_per_pixel();
+    let mut data = vec![0u8; width * height * bpp];
+
+    // Output is `height` pixels wide and `width` tall
+    for y in 0..width {
+        for x in 0..height {
+            let (src_x, src_y) = if clockwise {
+                (y, height - 1 - x)
+            } else {
+                (width - 1 - y, x)
+            };
+            let src = (src_y * width + src_x) * bpp;
+            let dst = (y * height + x) * bpp;
+            if let Some(pixel) = frame.data.get(src..src + bpp) {
+                data[dst..dst + bpp].copy_from_slice(pixel);
+            }
+        }
+    }
+
+    Frame {
+        data,
+        width: frame.height,
+        height: frame.width,
+        format: frame.format,
+        timestamp: frame.timestamp,
+    }
+}
+
+/// Mirror (if requested) and then rotate a frame clockwise.
+///
+/// YUV frames are decoded to RGB first, since chroma is shared between
+/// neighboring pixels and can't be moved per pixel.
+pub fn orient(mut frame: Frame, mirror: bool, rotation: Rotation) -> Frame {
+    if let Some(rgb) = frame.decode_yuv() {
+        frame = rgb;
+    }
+    if mirror {
+        mirror_horizontal(&mut frame);
+    }
+    match rotation {
+        Rotation::Deg0 => frame,
+        Rotation::Deg90 => rotate_quarter(&frame, true),
+        Rotation::Deg180 => {
+            rotate_180(&mut frame);
+            frame
+        }
+        Rotation::Deg270 => rotate_quarter(&frame, false),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::borrow::Cow;
+
+    /// 3x2 RGB frame with a distinct value in every byte.
+    fn numbered_frame() -> Frame {
+        Frame {
+            data: (0..18).collect(),
+            width: 3,
+            height: 2,
+            format: FrameFormat::Rgb,
+            timestamp: Instant::now(),
+        }
+    }
+
+    #[test]
+    fn test_mirror_twice_is_identity() {
+        let original = numbered_frame();
+        let mut frame = original.clone();
+        mirror_horizontal(&mut frame);
+        assert_ne!(frame.data, original.data);
+        mirror_horizontal(&mut frame);
+        assert_eq!(frame.data, original.data);
+    }
+
+    #[test]
+    fn test_rotate_90_four_times_is_identity() {
+        let original = numbered_frame();
+        let mut frame = original.clone();
+        for _ in 0..4 {
+            frame = orient(frame, false, Rotation::Deg90);
+        }
+        assert_eq!((frame.width, frame.height), (3, 2));
+        assert_eq!(frame.data, original.data);
+    }
+
+    #[test]
+    fn test_rotate_90_moves_bottom_left_to_top_left() {
+        // Gray 2x2: [a b / c d] clockwise -> [c a / d b]
+        let frame = Frame {
+            data: vec![1, 2, 3, 4],
+            width: 2,
+            height: 2,
+            format: FrameFormat::Gray,
+            timestamp: Instant::now(),
+        };
+        assert_eq!(
+            orient(frame.clone(), false, Rotation::Deg90).data,
+            [3, 1, 4, 2]
+        );
+        assert_eq!(
+            orient(frame.clone(), false, Rotation::Deg270).data,
+            [2, 4, 1, 3]
+        );
+        assert_eq!(orient(frame, false, Rotation::Deg180).data, [4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_rotate_quarter_swaps_dimensions() {
+        let rotated = rotate_quarter(&numbered_frame(), true);
+        assert_eq!((rotated.width, rotated.height), (2, 3));
+        // Top-left of the result is the source's bottom-left pixel
+        assert_eq!(&rotated.data[..3], &[9, 10, 11]);
+    }
+
+    #[test]
+    fn test_rotate_180_equals_two_quarter_turns() {
+        let twice = orient(
+            orient(numbered_frame(), false, Rotation::Deg90),
+            false,
+            Rotation::Deg90,
+        );
+        assert_eq!(
+            orient(numbered_frame(), false, Rotation::Deg180).data,
+            twice.data
+        );
+    }
+
+    #[test]
+    fn test_rotation_degrees_round_trip() {
+        for degrees in [0, 90, 180, 270] {
+            let rotation = Rotation::from_degrees(degrees).unwrap();
+            assert_eq!(rotation.degrees(), degrees);
+            assert_eq!(rotation.next().next().next().next(), rotation);
+        }
+        assert_eq!(Rotation::from_degrees(45), None);
+        assert_eq!("180".parse(), Ok(Rotation::Deg180));
+        assert!("91".parse::<Rotation>().is_err());
+    }
+
+    /// 4x2 YUYV macroblock: black, white, and two red pixels, over a gray ramp.
+    fn yuyv_macroblock() -> Frame {
+        Frame {
+            data: vec![
+                16, 128, 235, 128, 81, 90, 81, 240, //
+                41, 128, 110, 128, 180, 128, 210, 128,
+            ],
+            width: 4,
+            height: 2,
+            format: FrameFormat::Yuyv,
+            timestamp: Instant::now(),
+        }
+    }
+
+    #[test]
+    fn test_yuyv_to_luma_reads_y_bytes() {
+        let frame = yuyv_macroblock();
+        assert_eq!(
+            frame.to_luma().as_deref(),
+            Some(&[16, 235, 81, 81, 41, 110, 180, 210][..])
+        );
+        assert_eq!(numbered_frame().to_luma(), None);
+    }
+
+    #[test]
+    fn test_nv12_to_luma_borrows_y_plane() {
+        let frame = Frame {
+            data: vec![10, 20, 30, 40, 128, 128],
+            width: 2,
+            height: 2,
+            format: FrameFormat::Nv12,
+            timestamp: Instant::now(),
+        };
+        assert!(matches!(
+            frame.to_luma(),
+            Some(Cow::Borrowed(&[10, 20, 30, 40]))
+        ));
+    }
+
+    #[test]
+    fn test_yuyv_to_rgb_decodes_macroblock() {
+        let rgb = yuyv_macroblock().decode_yuv().unwrap();
+        assert_eq!(rgb.format, FrameFormat::Rgb);
+        assert_eq!(rgb.data.len(), 4 * 2 * 3);
+
+        // Limited-range black and white map to the full range; both pixels
+        // of the third pair share its red chroma
+        let pixels: Vec<&[u8]> = rgb.data.chunks(3).collect();
+        assert_eq!(pixels[0], &[0, 0, 0][..]);
+        assert_eq!(pixels[1], &[255, 255, 255][..]);
+        assert_eq!(pixels[2], &[255, 0, 0][..]);
+        assert_eq!(pixels[3], &[255, 0, 0][..]);
+        // Neutral chroma (128) decodes to gray with equal channels
+        assert!(pixels[4..].iter().all(|p| p[0] == p[1] && p[1] == p[2]));
+    }
+
+    #[test]
+    fn test_nv12_to_rgb_matches_yuyv() {
+        // 2x2 NV12 with one neutral UV pair vs the same pixels as YUYV
+        let nv12 = nv12_to_rgb(&[16, 126, 126, 235, 128, 128], 2, 2);
+        let yuyv = yuyv_to_rgb(&[16, 128, 126, 128, 126, 128, 235, 128], 2, 2);
+        assert_eq!(nv12, yuyv);
+        assert_eq!(&nv12[..3], &[0, 0, 0]);
+    }
+
+    #[test]
+    fn test_yuv_decode_pads_short_buffers() {
+        assert_eq!(yuyv_to_rgb(&[235, 128], 2, 2), vec![0; 12]);
+        assert_eq!(nv12_to_rgb(&[], 1, 1), vec![0, 0, 0]);
+    }
+
+    #[test]
+    fn test_orient_decodes_yuv_first() {
+        let frame = orient(yuyv_macroblock(), true, Rotation::Deg0);
+        assert_eq!(frame.format, FrameFormat::Rgb);
+        // Mirrored: the black pixel ends the first row
+        assert_eq!(&frame.data[9..12], &[0, 0, 0]);
+    }
+
+    #[test]
+    fn test_extract_luma_nv12_takes_y_plane() {
+        // 2x2 NV12: 4 Y bytes followed by 2 interleaved UV bytes
+        let raw = vec![10, 20, 30, 40, 128, 128];
+        assert_eq!(
+            extract_luma(NokhwaFrameFormat::NV12, &raw, 2, 2),
+            Some(vec![10, 20, 30, 40])
+        );
+    }
+
+    #[test]
+    fn test_extract_luma_yuyv_skips_chroma() {
+        // 2x1 YUYV: Y0 U Y1 V
+        let raw = vec![50, 128, 60, 128];
+        assert_eq!(
+            extract_luma(NokhwaFrameFormat::YUYV, &raw, 2, 1),
+            Some(vec![50, 60])
+        );
+    }
+
+    #[test]
+    fn test_extract_luma_unsupported_or_short_buffer() {
+        assert_eq!(extract_luma(NokhwaFrameFormat::MJPEG, &[0; 16], 2, 2), None);
+        assert_eq!(extract_luma(NokhwaFrameFormat::NV12, &[0; 3], 2, 2), None);
+        assert_eq!(extract_luma(NokhwaFrameFormat::YUYV, &[0; 4], 2, 2), None);
+    }
+
+    #[test]
+    fn test_mirror_horizontal_gray() {
+        let mut frame = Frame {
+            data: vec![1, 2, 3],
+            width: 3,
+            height: 1,
+            format: FrameFormat::Gray,
+            timestamp: Instant::now(),
+        };
+        mirror_horizontal(&mut frame);
+        assert_eq!(frame.data, vec![3, 2, 1]);
+    }
 
     #[test]
     fn test_mirror_horizontal_2x1() {
@@ -1,16 +1,24 @@
 //! Camera capture module for webcam access and frame capture.
 //!
 //! This module provides a high-level API for camera capture operations:
-//! - Device enumeration via [`list_devices`]
+//! - Device enumeration via [`list_devices`] (with capture modes via
+//!   [`list_devices_verbose`])
 //! - Camera capture via [`CameraCapture`]
 //! - Configuration via [`CameraSettings`] and [`Resolution`]
 
 mod capture;
 mod capture_loop;
 mod device;
+mod fps;
+mod frame_queue;
 mod frame_utils;
 mod types;
 
 pub use capture::CameraCapture;
-pub use device::list_devices;
-pub use types::{CameraError, CameraInfo, CameraSettings, Frame, FrameFormat, Resolution};
+pub use device::{list_devices, list_devices_verbose};
+pub use fps::{DEFAULT_FPS_WINDOW, FpsMeter};
+pub use frame_queue::FRAME_QUEUE_CAPACITY;
+pub use types::{
+    CameraError, CameraInfo, CameraMode, CameraSettings, DeviceKind, DeviceSelector, Frame,
+    FrameFormat, Resolution, ResolutionFallback, Rotation,
+};
@@ -1,8 +1,11 @@
 //! Camera types and data structures.
 
+use std::borrow::Cow;
 use std::fmt;
 use std::time::Instant;
 
+use super::frame_utils::{nv12_to_rgb_into, yuyv_luma, yuyv_to_rgb_into};
+
 /// Information about an available camera device.
 #[derive(Debug, Clone)]
 pub struct CameraInfo {
@@ -12,16 +15,102 @@ pub struct CameraInfo {
     pub name: String,
     /// Device description
     pub description: String,
+    /// Whether this is real hardware or a software (virtual) camera
+    pub kind: DeviceKind,
+    /// Capture modes the device reports; empty unless probed with
+    /// `list_devices_verbose`, or if the device refused enumeration
+    pub modes: Vec<CameraMode>,
+}
+
+impl CameraInfo {
+    /// Distinct supported resolutions, largest first.
+    pub fn resolutions(&self) -> Vec<Resolution> {
+        let mut resolutions: Vec<Resolution> = self.modes.iter().map(|m| m.resolution).collect();
+        resolutions.sort_by_key(|r| std::cmp::Reverse((r.width * r.height, r.width)));
+        resolutions.dedup();
+        resolutions
+    }
+
+    /// Distinct source pixel formats (e.g. "MJPEG", "YUYV"), in reported order.
+    pub fn formats(&self) -> Vec<&str> {
+        let mut formats: Vec<&str> = Vec::new();
+        for mode in &self.modes {
+            if !formats.contains(&mode.format.as_str()) {
+                formats.push(&mode.format);
+            }
+        }
+        formats
+    }
+}
+
+/// One capture mode supported by a camera.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CameraMode {
+    /// Frame size
+    pub resolution: Resolution,
+    /// Source pixel format as named by the backend (e.g. "MJPEG", "NV12")
+    pub format: String,
+    /// Frame rate in frames per second
+    pub fps: u32,
 }
 
 impl fmt::Display for CameraInfo {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "[{}] {} ({})", self.index, self.name, self.description)
+        write!(f, "[{}] {} ({})", self.index, self.name, self.description)?;
+        if self.kind == DeviceKind::Virtual {
+            write!(f, " [virtual]")?;
+        }
+        Ok(())
+    }
+}
+
+/// Name fragments of well-known virtual camera drivers (matched case-insensitively).
+const VIRTUAL_CAMERA_HINTS: &[&str] = &[
+    "obs",
+    "camtwist",
+    "virtual",
+    "snap camera",
+    "mmhmm",
+    "manycam",
+    "xsplit",
+    "camo",
+    "v4l2loopback",
+];
+
+/// Kind of camera device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DeviceKind {
+    /// Hardware camera (built-in or USB)
+    #[default]
+    Physical,
+    /// Software camera such as OBS Virtual Camera or CamTwist
+    Virtual,
+}
+
+impl DeviceKind {
+    /// Guess the device kind from its name and description.
+    ///
+    /// Virtual cameras don't identify themselves to AVFoundation, so this
+    /// relies on the names of common virtual camera drivers.
+    pub fn detect(name: &str, description: &str) -> Self {
+        let haystack = format!("{} {}", name, description).to_lowercase();
+        let is_virtual = haystack
+            .split(|c: char| !c.is_alphanumeric())
+            .any(|word| VIRTUAL_CAMERA_HINTS.contains(&word))
+            || VIRTUAL_CAMERA_HINTS
+                .iter()
+                .any(|hint| hint.contains(' ') && haystack.contains(hint));
+
+        if is_virtual {
+            DeviceKind::Virtual
+        } else {
+            DeviceKind::Physical
+        }
     }
 }
 
 /// Camera resolution settings.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Resolution {
     pub width: u32,
     pub height: u32,
@@ -47,23 +136,79 @@ impl Resolution {
     };
 }
 
+impl Resolution {
+    /// Pixel count.
+    pub fn area(&self) -> u64 {
+        self.width as u64 * self.height as u64
+    }
+
+    /// Whether both dimensions are at least `other`'s.
+    pub fn covers(&self, other: Resolution) -> bool {
+        self.width >= other.width && self.height >= other.height
+    }
+
+    /// Whether the two resolutions share an aspect ratio (e.g. 4:3 vs 16:9).
+    pub fn same_aspect(&self, other: Resolution) -> bool {
+        self.width as u64 * other.height as u64 == other.width as u64 * self.height as u64
+    }
+}
+
 impl Default for Resolution {
     fn default() -> Self {
         Self::MEDIUM
     }
 }
 
+impl fmt::Display for Resolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&format!("{}x{}", self.width, self.height))
+    }
+}
+
+impl std::str::FromStr for Resolution {
+    type Err = String;
+
+    /// Parse `WIDTHxHEIGHT`, e.g. `1280x720`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parsed = s
+            .trim()
+            .split_once(['x', 'X'])
+            .and_then(|(w, h)| Some((w.parse().ok()?, h.parse().ok()?)));
+        match parsed {
+            Some((width, height)) if width > 0 && height > 0 => Ok(Resolution { width, height }),
+            _ => Err(format!(
+                "expected WIDTHxHEIGHT (e.g. 1280x720), got {:?}",
+                s
+            )),
+        }
+    }
+}
+
 /// Pixel format of a captured frame.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FrameFormat {
     /// RGB format (3 bytes per pixel)
     Rgb,
+    /// Grayscale/luma only (1 byte per pixel)
+    Gray,
+    /// Packed 4:2:2 YUV: each pixel pair is `Y0 U Y1 V` (2 bytes per pixel)
+    Yuyv,
+    /// Planar 4:2:0 YUV: a full-size Y plane, then interleaved UV at half
+    /// resolution in each direction
+    Nv12,
+}
+
+impl FrameFormat {
+    /// Whether this is a camera-native YUV format rather than RGB or gray.
+    pub fn is_yuv(&self) -> bool {
+        matches!(self, FrameFormat::Yuyv | FrameFormat::Nv12)
+    }
 }
 
 /// A captured camera frame.
 #[derive(Debug, Clone)]
 pub struct Frame {
-    /// Raw pixel data in RGB format
+    /// Raw pixel data, laid out as described by `format`
     pub data: Vec<u8>,
     /// Frame width in pixels
     pub width: u32,
@@ -76,10 +221,250 @@ pub struct Frame {
 }
 
 impl Frame {
-    /// Get the number of bytes per pixel (3 for RGB).
+    /// Get the number of bytes per pixel (3 for RGB, 2 for YUYV, 1 for
+    /// grayscale and for NV12's Y plane).
     pub fn bytes_per_pixel(&self) -> usize {
         match self.format {
             FrameFormat::Rgb => 3,
+            FrameFormat::Yuyv => 2,
+            FrameFormat::Gray | FrameFormat::Nv12 => 1,
+        }
+    }
+
+    /// Luma bytes, one per pixel, read straight from the frame.
+    ///
+    /// Gray frames and NV12's Y plane are borrowed; YUYV skips the chroma
+    /// bytes. Returns `None` for RGB, which needs a weighted conversion
+    /// (see `ascii::to_grayscale`).
+    pub fn to_luma(&self) -> Option<Cow<'_, [u8]>> {
+        let pixel_count = (self.width * self.height) as usize;
+        match self.format {
+            FrameFormat::Rgb => None,
+            FrameFormat::Gray => Some(Cow::Borrowed(&self.data)),
+            FrameFormat::Nv12 => Some(Cow::Borrowed(
+                &self.data[..pixel_count.min(self.data.len())],
+            )),
+            FrameFormat::Yuyv => Some(Cow::Owned(yuyv_luma(&self.data, pixel_count))),
+        }
+    }
+
+    /// An RGB copy of a YUYV or NV12 frame, or `None` if it isn't YUV.
+    pub fn decode_yuv(&self) -> Option<Frame> {
+        let mut rgb = Frame {
+            data: Vec::new(),
+            width: 0,
+            height: 0,
+            format: FrameFormat::Rgb,
+            timestamp: self.timestamp,
+        };
+        self.decode_yuv_into(&mut rgb).then_some(rgb)
+    }
+
+    /// Decode a YUYV or NV12 frame into `out` as RGB, reusing `out`'s buffer.
+    ///
+    /// Returns false, leaving `out` untouched, if the frame isn't YUV.
+    pub fn decode_yuv_into(&self, out: &mut Frame) -> bool {
+        match self.format {
+            FrameFormat::Yuyv => {
+                yuyv_to_rgb_into(&self.data, self.width, self.height, &mut out.data)
+            }
+            FrameFormat::Nv12 => {
+                nv12_to_rgb_into(&self.data, self.width, self.height, &mut out.data)
+            }
+            FrameFormat::Rgb | FrameFormat::Gray => return false,
+        }
+        out.width = self.width;
+        out.height = self.height;
+        out.format = FrameFormat::Rgb;
+        out.timestamp = self.timestamp;
+        true
+    }
+}
+
+/// Which camera to open: by index (as shown by `list-cameras`) or by name.
+///
+/// Names match case-insensitively, exactly first and then as a substring;
+/// when two cameras share a name the lowest index wins, so use an index to
+/// pick the other one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeviceSelector {
+    /// Device index from `list_devices`
+    Index(u32),
+    /// Device name, or a unique part of it
+    Name(String),
+}
+
+impl Default for DeviceSelector {
+    fn default() -> Self {
+        DeviceSelector::Index(0)
+    }
+}
+
+impl fmt::Display for DeviceSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeviceSelector::Index(index) => write!(f, "{}", index),
+            DeviceSelector::Name(name) => write!(f, "{:?}", name),
+        }
+    }
+}
+
+impl std::str::FromStr for DeviceSelector {
+    type Err = String;
+
+    /// A bare integer selects by index; anything else by name.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err("expected a camera index or name".to_string());
+        }
+        Ok(s.parse()
+            .map(DeviceSelector::Index)
+            .unwrap_or_else(|_| DeviceSelector::Name(s.to_string())))
+    }
+}
+
+impl DeviceSelector {
+    /// Resolve to the index of one of `devices`.
+    ///
+    /// # Errors
+    /// * `CameraError::DeviceNotFound` - If no device has the index
+    /// * `CameraError::DeviceNameNotFound` - If no device name matches
+    pub fn resolve(&self, devices: &[CameraInfo]) -> Result<u32, CameraError> {
+        match self {
+            DeviceSelector::Index(index) => devices
+                .iter()
+                .any(|d| d.index == *index)
+                .then_some(*index)
+                .ok_or(CameraError::DeviceNotFound(*index)),
+            DeviceSelector::Name(name) => {
+                let wanted = name.to_lowercase();
+                let mut by_index: Vec<&CameraInfo> = devices.iter().collect();
+                by_index.sort_by_key(|d| d.index);
+                by_index
+                    .iter()
+                    .find(|d| d.name.to_lowercase() == wanted)
+                    .or_else(|| {
+                        by_index
+                            .iter()
+                            .find(|d| d.name.to_lowercase().contains(&wanted))
+                    })
+                    .map(|d| d.index)
+                    .ok_or_else(|| CameraError::DeviceNameNotFound(name.clone()))
+            }
+        }
+    }
+}
+
+/// Clockwise rotation applied to captured frames (e.g. 180 for a camera
+/// mounted upside-down).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rotation {
+    /// Frames as the camera delivers them
+    #[default]
+    Deg0,
+    /// Quarter turn clockwise; swaps width and height
+    Deg90,
+    /// Half turn
+    Deg180,
+    /// Quarter turn counter-clockwise; swaps width and height
+    Deg270,
+}
+
+impl Rotation {
+    /// Rotation for a clockwise angle in degrees, if it's a multiple of 90 below 360.
+    pub fn from_degrees(degrees: u16) -> Option<Self> {
+        match degrees {
+            0 => Some(Rotation::Deg0),
+            90 => Some(Rotation::Deg90),
+            180 => Some(Rotation::Deg180),
+            270 => Some(Rotation::Deg270),
+            _ => None,
+        }
+    }
+
+    /// Clockwise angle in degrees.
+    pub fn degrees(&self) -> u16 {
+        match self {
+            Rotation::Deg0 => 0,
+            Rotation::Deg90 => 90,
+            Rotation::Deg180 => 180,
+            Rotation::Deg270 => 270,
+        }
+    }
+
+    /// The next quarter turn clockwise (270 wraps to 0).
+    pub fn next(&self) -> Self {
+        match self {
+            Rotation::Deg0 => Rotation::Deg90,
+            Rotation::Deg90 => Rotation::Deg180,
+            Rotation::Deg180 => Rotation::Deg270,
+            Rotation::Deg270 => Rotation::Deg0,
+        }
+    }
+}
+
+impl std::str::FromStr for Rotation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.trim()
+            .parse()
+            .ok()
+            .and_then(Rotation::from_degrees)
+            .ok_or_else(|| format!("expected 0, 90, 180, or 270, got {:?}", s))
+    }
+}
+
+/// What to do when the device doesn't support the requested resolution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResolutionFallback {
+    /// Smallest supported mode covering the request, else the largest below it
+    #[default]
+    NearestLarger,
+    /// Supported mode closest in pixel count, larger or smaller
+    Nearest,
+    /// Fail with `CameraError::UnsupportedResolution` instead of substituting
+    Exact,
+}
+
+impl ResolutionFallback {
+    /// Pick the mode to capture at from the device's `supported` resolutions.
+    ///
+    /// An exact match always wins. Otherwise modes with the requested aspect
+    /// ratio are preferred, so 640x480 falls back to 800x600 over 854x480.
+    /// Returns `None` if `supported` is empty or the policy is `Exact` and
+    /// nothing matches.
+    pub fn select(self, requested: Resolution, supported: &[Resolution]) -> Option<Resolution> {
+        if supported.contains(&requested) {
+            return Some(requested);
+        }
+        let nearest = |candidates: &mut dyn Iterator<Item = Resolution>| {
+            candidates.min_by_key(|res| {
+                (
+                    !res.same_aspect(requested),
+                    res.area().abs_diff(requested.area()),
+                )
+            })
+        };
+
+        match self {
+            ResolutionFallback::Exact => None,
+            ResolutionFallback::Nearest => nearest(&mut supported.iter().copied()),
+            ResolutionFallback::NearestLarger => nearest(
+                &mut supported
+                    .iter()
+                    .copied()
+                    .filter(|res| res.covers(requested)),
+            )
+            .or_else(|| {
+                nearest(
+                    &mut supported
+                        .iter()
+                        .copied()
+                        .filter(|res| !res.covers(requested)),
+                )
+            }),
         }
     }
 }
@@ -87,23 +472,32 @@ impl Frame {
 /// Settings for camera capture.
 #[derive(Debug, Clone)]
 pub struct CameraSettings {
-    /// Camera device index
-    pub device_index: u32,
+    /// Camera to open, by index or name
+    pub device: DeviceSelector,
     /// Capture resolution
     pub resolution: Resolution,
+    /// Mode to use when the device doesn't support `resolution`
+    pub resolution_fallback: ResolutionFallback,
     /// Target FPS (actual may vary)
     pub fps: u32,
     /// Mirror horizontally (selfie mode)
     pub mirror: bool,
+    /// Clockwise rotation, applied after mirroring
+    pub rotation: Rotation,
+    /// Capture luma only, skipping RGB decoding (falls back to RGB if unsupported)
+    pub grayscale: bool,
 }
 
 impl Default for CameraSettings {
     fn default() -> Self {
         Self {
-            device_index: 0,
+            device: DeviceSelector::default(),
             resolution: Resolution::default(),
+            resolution_fallback: ResolutionFallback::default(),
             fps: 30,
             mirror: true, // Default to selfie mode
+            rotation: Rotation::Deg0,
+            grayscale: false,
         }
     }
 }
@@ -121,10 +515,16 @@ pub enum CameraError {
     PermissionDenied,
     /// Camera device not found at specified index
     DeviceNotFound(u32),
+    /// No camera name matched the requested name
+    DeviceNameNotFound(String),
     /// Failed to start video stream
     StreamFailed(String),
     /// Capture thread is already running
     AlreadyRunning,
+    /// The camera stopped delivering frames (e.g. unplugged) and is being reopened
+    Disconnected,
+    /// The device has no mode at this resolution (with `ResolutionFallback::Exact`)
+    UnsupportedResolution(Resolution),
 }
 
 impl fmt::Display for CameraError {
@@ -146,8 +546,23 @@ impl fmt::Display for CameraError {
                     index
                 )
             }
+            CameraError::DeviceNameNotFound(name) => {
+                write!(
+                    f,
+                    "No camera named {:?}. Run 'list-cameras' to see available devices",
+                    name
+                )
+            }
             CameraError::StreamFailed(msg) => write!(f, "Failed to start camera stream: {}", msg),
             CameraError::AlreadyRunning => write!(f, "Capture thread is already running"),
+            CameraError::Disconnected => write!(f, "Camera disconnected, reconnecting"),
+            CameraError::UnsupportedResolution(res) => {
+                write!(
+                    f,
+                    "Camera doesn't support {}. Run 'list-cameras --verbose' to see its modes",
+                    res
+                )
+            }
         }
     }
 }
@@ -164,10 +579,158 @@ mod tests {
             index: 0,
             name: "Test Camera".to_string(),
             description: "Built-in".to_string(),
+            kind: DeviceKind::Physical,
+            modes: Vec::new(),
         };
         assert_eq!(format!("{}", info), "[0] Test Camera (Built-in)");
     }
 
+    fn named(index: u32, name: &str) -> CameraInfo {
+        CameraInfo {
+            index,
+            name: name.to_string(),
+            description: String::new(),
+            kind: DeviceKind::Physical,
+            modes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_device_selector_parses_index_or_name() {
+        assert_eq!("2".parse(), Ok(DeviceSelector::Index(2)));
+        assert_eq!(
+            " OBS Virtual Camera ".parse(),
+            Ok(DeviceSelector::Name("OBS Virtual Camera".to_string()))
+        );
+        assert!("".parse::<DeviceSelector>().is_err());
+    }
+
+    #[test]
+    fn test_device_selector_out_of_range_index_is_error() {
+        let devices = [
+            named(0, "FaceTime HD Camera"),
+            named(1, "OBS Virtual Camera"),
+        ];
+
+        assert_eq!(DeviceSelector::Index(1).resolve(&devices).unwrap(), 1);
+        let err = DeviceSelector::Index(7).resolve(&devices).unwrap_err();
+        assert!(matches!(err, CameraError::DeviceNotFound(7)));
+        assert!(err.to_string().contains("list-cameras"));
+        assert!(matches!(
+            DeviceSelector::Index(0).resolve(&[]),
+            Err(CameraError::DeviceNotFound(0))
+        ));
+    }
+
+    #[test]
+    fn test_device_selector_resolves_names() {
+        let devices = [
+            named(2, "USB Camera"),
+            named(0, "FaceTime HD Camera"),
+            named(1, "USB Camera"),
+        ];
+        let by_name = |name: &str| DeviceSelector::Name(name.to_string()).resolve(&devices);
+
+        // Exact and case-insensitive, preferring the lowest index on duplicates
+        assert_eq!(by_name("usb camera").unwrap(), 1);
+        // Substring fallback
+        assert_eq!(by_name("facetime").unwrap(), 0);
+        assert!(matches!(
+            by_name("Iriun"),
+            Err(CameraError::DeviceNameNotFound(name)) if name == "Iriun"
+        ));
+    }
+
+    #[test]
+    fn test_camera_info_resolutions_and_formats_dedup() {
+        let mode = |width, height, format: &str, fps| CameraMode {
+            resolution: Resolution { width, height },
+            format: format.to_string(),
+            fps,
+        };
+        let info = CameraInfo {
+            index: 0,
+            name: "Test Camera".to_string(),
+            description: "Built-in".to_string(),
+            kind: DeviceKind::Physical,
+            modes: vec![
+                mode(640, 480, "YUYV", 30),
+                mode(1280, 720, "MJPEG", 30),
+                mode(640, 480, "MJPEG", 15),
+                mode(1280, 720, "YUYV", 10),
+            ],
+        };
+
+        assert_eq!(
+            info.resolutions(),
+            vec![Resolution::HIGH, Resolution::MEDIUM]
+        );
+        assert_eq!(info.formats(), vec!["YUYV", "MJPEG"]);
+        assert_eq!(Resolution::HIGH.to_string(), "1280x720");
+    }
+
+    #[test]
+    fn test_camera_info_display_virtual() {
+        let info = CameraInfo {
+            index: 1,
+            name: "OBS Virtual Camera".to_string(),
+            description: "OBS".to_string(),
+            kind: DeviceKind::Virtual,
+            modes: Vec::new(),
+        };
+        assert_eq!(
+            format!("{}", info),
+            "[1] OBS Virtual Camera (OBS) [virtual]"
+        );
+    }
+
+    #[test]
+    fn test_device_kind_detects_virtual_cameras() {
+        for name in [
+            "OBS Virtual Camera",
+            "CamTwist",
+            "Snap Camera",
+            "mmhmm",
+            "ManyCam Virtual Webcam",
+            "Camo",
+            "Dummy video device (0x0000) v4l2loopback",
+        ] {
+            assert_eq!(
+                DeviceKind::detect(name, ""),
+                DeviceKind::Virtual,
+                "{} should be virtual",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_device_kind_detects_physical_cameras() {
+        for name in [
+            "FaceTime HD Camera",
+            "FaceTime HD Camera (Built-in)",
+            "Logitech BRIO",
+            "USB Camera",
+            "Camera Module",
+            "Razer Kiyo",
+        ] {
+            assert_eq!(
+                DeviceKind::detect(name, ""),
+                DeviceKind::Physical,
+                "{} should be physical",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_device_kind_checks_description() {
+        assert_eq!(
+            DeviceKind::detect("Camera", "Virtual Video Device"),
+            DeviceKind::Virtual
+        );
+    }
+
     #[test]
     fn test_resolution_constants() {
         assert_eq!(Resolution::LOW.width, 320);
@@ -178,6 +741,15 @@ mod tests {
         assert_eq!(Resolution::HIGH.height, 720);
     }
 
+    #[test]
+    fn test_resolution_from_str() {
+        assert_eq!("1280x720".parse(), Ok(Resolution::HIGH));
+        assert_eq!(" 320X240 ".parse(), Ok(Resolution::LOW));
+        for bad in ["", "640", "640x", "0x480", "-1x480", "640x480x2"] {
+            assert!(bad.parse::<Resolution>().is_err(), "{:?}", bad);
+        }
+    }
+
     #[test]
     fn test_resolution_default() {
         let res = Resolution::default();
@@ -188,11 +760,77 @@ mod tests {
     #[test]
     fn test_camera_settings_default() {
         let settings = CameraSettings::default();
-        assert_eq!(settings.device_index, 0);
+        assert_eq!(settings.device, DeviceSelector::Index(0));
         assert_eq!(settings.resolution.width, 640);
         assert_eq!(settings.resolution.height, 480);
         assert_eq!(settings.fps, 30);
         assert!(settings.mirror); // Default to selfie mode
+        assert!(!settings.grayscale);
+        assert_eq!(
+            settings.resolution_fallback,
+            ResolutionFallback::NearestLarger
+        );
+    }
+
+    fn res(width: u32, height: u32) -> Resolution {
+        Resolution { width, height }
+    }
+
+    #[test]
+    fn test_resolution_fallback_nearest_larger_picks_expected_mode() {
+        // A typical webcam's capability list without 640x480
+        let supported = [
+            res(320, 240),
+            res(1920, 1080),
+            res(854, 480),
+            res(1024, 768),
+            res(800, 600),
+            res(1280, 720),
+        ];
+        let policy = ResolutionFallback::NearestLarger;
+
+        // Same-or-larger with the same 4:3 aspect beats a closer 16:9 mode
+        assert_eq!(
+            policy.select(res(640, 480), &supported),
+            Some(res(800, 600))
+        );
+        // Only 1920x1080 covers 1280x800, even though 1280x720 is closer
+        assert_eq!(
+            policy.select(res(1280, 800), &supported),
+            Some(res(1920, 1080))
+        );
+        // An exact match is kept
+        assert_eq!(
+            policy.select(res(1280, 720), &supported),
+            Some(res(1280, 720))
+        );
+        // Larger than every mode: fall back to the nearest smaller one
+        assert_eq!(
+            policy.select(res(3840, 2160), &supported),
+            Some(res(1920, 1080))
+        );
+    }
+
+    #[test]
+    fn test_resolution_fallback_other_policies() {
+        let supported = [res(320, 240), res(1280, 960)];
+
+        assert_eq!(
+            ResolutionFallback::Nearest.select(res(640, 480), &supported),
+            Some(res(320, 240))
+        );
+        assert_eq!(
+            ResolutionFallback::NearestLarger.select(res(640, 480), &supported),
+            Some(res(1280, 960))
+        );
+        assert_eq!(
+            ResolutionFallback::Exact.select(res(640, 480), &supported),
+            None
+        );
+        assert_eq!(
+            ResolutionFallback::NearestLarger.select(res(640, 480), &[]),
+            None
+        );
     }
 
     #[test]
@@ -232,5 +870,14 @@ mod tests {
             timestamp: Instant::now(),
         };
         assert_eq!(frame.bytes_per_pixel(), 3);
+
+        let gray = Frame {
+            data: vec![0; 2], // 2 gray pixels
+            width: 2,
+            height: 1,
+            format: FrameFormat::Gray,
+            timestamp: Instant::now(),
+        };
+        assert_eq!(gray.bytes_per_pixel(), 1);
     }
 }
@@ -1,14 +1,63 @@
 //! CLI argument parsing with clap.
 
-use clap::{Parser, Subcommand};
+use clap::parser::ValueSource;
+use clap::{ArgMatches, Parser, Subcommand, ValueEnum};
+use std::ops::RangeInclusive;
 use std::path::PathBuf;
+use std::str::FromStr;
+
+use super::enums::{CharacterSet, ColorDepth, Position, Size};
+use crate::ascii::CELL_ASPECT_RANGE;
+use crate::camera::{DeviceSelector, Resolution, Rotation};
+use crate::config::{Config, ConfigError, default_path};
+
+const EXAMPLES: &str = "\
+Examples:
+  space-recorder                                  Start with defaults
+  space-recorder --position top-right --size large
+  space-recorder --charset braille --invert       Braille art for light terminals
+  space-recorder --no-camera                      Start with the camera hidden (Alt+C to show)
+  space-recorder list-cameras                     Find the index or name for --camera";
+
+/// Overlay redraw rates accepted by `--modal-refresh-rate` and `modal.refresh_rate`.
+const MODAL_REFRESH_RATES: RangeInclusive<i64> = 1..=60;
+
+/// Steps accepted by `--transparency-step` and `modal.transparency_step`.
+pub const TRANSPARENCY_STEPS: RangeInclusive<i64> = 1..=50;
+
+/// Intervals accepted by `--pty-refresh-ms` and `pty.refresh_ms`.
+const PTY_REFRESH_MS: RangeInclusive<i64> = 1..=1000;
+
+/// Parse a `--char-aspect` cell width/height ratio, rejecting values outside CELL_ASPECT_RANGE.
+fn parse_char_aspect(s: &str) -> Result<f32, String> {
+    let value: f32 = s.parse().map_err(|_| format!("'{}' is not a number", s))?;
+    if CELL_ASPECT_RANGE.contains(&value) {
+        Ok(value)
+    } else {
+        Err(format!(
+            "{} is not in {}..={}",
+            value,
+            CELL_ASPECT_RANGE.start(),
+            CELL_ASPECT_RANGE.end()
+        ))
+    }
+}
 
-use super::enums::{CharacterSet, Position, Size};
+/// Parse a `--sharpen` amount in 0.0..=5.0.
+fn parse_sharpen(s: &str) -> Result<f32, String> {
+    let value: f32 = s.parse().map_err(|_| format!("'{}' is not a number", s))?;
+    if (0.0..=5.0).contains(&value) {
+        Ok(value)
+    } else {
+        Err(format!("{} is not in 0..=5", value))
+    }
+}
 
 /// TUI app that renders webcam as ASCII art overlay while hosting a shell
 #[derive(Parser, Debug)]
 #[command(name = "space-recorder")]
 #[command(version, about = "ASCII camera overlay for terminal streaming", long_about = None)]
+#[command(after_help = EXAMPLES)]
 pub struct Args {
     #[command(subcommand)]
     pub command: Option<Command>,
@@ -17,9 +66,22 @@ pub struct Args {
     #[arg(short, long)]
     pub shell: Option<String>,
 
-    /// Camera device index (from list-cameras)
-    #[arg(long, default_value = "0")]
-    pub camera: u32,
+    /// Extra argument for the shell; repeat for more (e.g. --shell-arg=-i)
+    #[arg(long = "shell-arg", value_name = "ARG", allow_hyphen_values = true)]
+    pub shell_args: Vec<String>,
+
+    /// Start the shell as a login shell (passes -l)
+    #[arg(long)]
+    pub login: bool,
+
+    /// Run CMD through the shell's -c instead of an interactive shell;
+    /// the session ends when it exits
+    #[arg(long, value_name = "CMD")]
+    pub exec: Option<String>,
+
+    /// Camera index or name (from list-cameras)
+    #[arg(long, default_value = "0", value_name = "INDEX|NAME")]
+    pub camera: DeviceSelector,
 
     /// Disable camera on start
     #[arg(long)]
@@ -33,31 +95,262 @@ pub struct Args {
     #[arg(long, default_value = "small")]
     pub size: Size,
 
-    /// ASCII character set
+    /// ASCII character set, or custom:<ramp> with characters ordered dark to light
     #[arg(long, default_value = "blocks")]
     pub charset: CharacterSet,
 
-    /// Mirror camera horizontally
+    /// Brightness (0-255) at which braille dots light up; lower = more dots
+    #[arg(long, default_value = "80")]
+    pub braille_threshold: u8,
+
+    /// Supersampling factor for the ASCII downsample (1 = off, 2 = 2x2 per cell)
+    #[arg(long, default_value = "1", value_parser = clap::value_parser!(u8).range(1..=4))]
+    pub render_scale: u8,
+
+    /// Overlay redraw rate in FPS, independent of the camera capture rate
+    #[arg(long, default_value = "15", value_parser = clap::value_parser!(u32).range(MODAL_REFRESH_RATES))]
+    pub modal_refresh_rate: u32,
+
+    /// Milliseconds between writes of shell output; a burst of output within
+    /// one interval is drawn once
+    #[arg(long, value_name = "MS", default_value = "16", value_parser = clap::value_parser!(u32).range(PTY_REFRESH_MS))]
+    pub pty_refresh_ms: u32,
+
+    /// Drag the camera overlay with the mouse (and resize it from a corner);
+    /// the terminal's own mouse selection is unavailable while this is on
+    #[arg(long)]
+    pub mouse: bool,
+
+    /// Record the shell session to an asciicast v2 file (play with `asciinema play`)
+    #[arg(long, value_name = "PATH")]
+    pub record_session: Option<PathBuf>,
+
+    /// Transparency points per Alt+T press (Alt+Shift+T steps 5x)
+    #[arg(long, value_name = "PERCENT", default_value = "10", value_parser = clap::value_parser!(u8).range(TRANSPARENCY_STEPS))]
+    pub transparency_step: u8,
+
+    /// Terminal cell width/height (e.g. 0.5 for typical fonts, 1.0 for square ones);
+    /// fits the ASCII grid to the camera's aspect ratio instead of filling the modal
+    #[arg(long, value_parser = parse_char_aspect)]
+    pub char_aspect: Option<f32>,
+
+    /// Mirror camera horizontally (hotkeys.toggle_mirror toggles)
     #[arg(long)]
     pub mirror: bool,
 
+    /// Camera capture resolution (the nearest supported mode is used)
+    #[arg(long, default_value = "640x480", value_name = "WxH")]
+    pub resolution: Resolution,
+
+    /// Rotate the camera clockwise: 0, 90, 180, or 270 (180 for upside-down mounts)
+    #[arg(long, default_value = "0", value_name = "DEGREES")]
+    pub rotate: Rotation,
+
+    /// Capture luma only, skipping RGB decoding (cheaper on low-power machines)
+    #[arg(long)]
+    pub grayscale: bool,
+
     /// Invert brightness (for light terminals)
     #[arg(long)]
     pub invert: bool,
 
+    /// Overlay colors: truecolor, 256, or mono [default: from $COLORTERM/$TERM]
+    #[arg(long, value_name = "DEPTH")]
+    pub color_depth: Option<ColorDepth>,
+
+    /// Stretch contrast to the full range (helps in dim rooms)
+    #[arg(long)]
+    pub auto_levels: bool,
+
+    /// Sharpen before downsampling with this unsharp-mask amount (0-5, e.g. 1.0)
+    #[arg(long, value_name = "AMOUNT", value_parser = parse_sharpen)]
+    pub sharpen: Option<f32>,
+
+    /// Performance mode: cheaper, blockier frames for high FPS on slow machines
+    #[arg(long)]
+    pub perf: bool,
+
     /// Hide status bar
     #[arg(long)]
     pub no_status: bool,
 
+    /// Enable Alt+Q to quit (ends the shell session)
+    #[arg(long)]
+    pub quit_hotkey: bool,
+
     /// Config file path
     #[arg(long, short)]
     pub config: Option<PathBuf>,
+
+    /// Apply the config's [profiles.NAME] table on top of its base settings
+    #[arg(long, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// Reject config keys space-recorder doesn't know instead of ignoring them
+    #[arg(long)]
+    pub strict_config: bool,
+}
+
+impl Args {
+    /// Arguments for the shell: `-l` with `--login`, then each `--shell-arg`,
+    /// then `-c CMD` with `--exec`.
+    pub fn shell_argv(&self) -> Vec<String> {
+        let login = self.login.then(|| "-l".to_string());
+        let exec = self
+            .exec
+            .iter()
+            .flat_map(|cmd| ["-c".to_string(), cmd.clone()]);
+        login
+            .into_iter()
+            .chain(self.shell_args.iter().cloned())
+            .chain(exec)
+            .collect()
+    }
+
+    /// Load the config file (`--config`, else the default path) with
+    /// `--profile` and then `SPACE_RECORDER_*` environment overrides applied.
+    ///
+    /// A missing default config is fine; a missing `--config` file is not.
+    /// With `--strict-config`, unknown keys in the file are an error.
+    pub fn load_config(&self) -> Result<Config, ConfigError> {
+        let default_path = default_path();
+        let mut config = match &self.config {
+            Some(path) if self.strict_config => Config::load_strict(path)?,
+            Some(path) => Config::load_from_explicit(path)?,
+            None if self.strict_config && default_path.exists() => {
+                Config::load_strict(&default_path)?
+            }
+            None => Config::load(None)?,
+        };
+        if let Some(name) = &self.profile {
+            config = config.with_profile(name)?;
+        }
+        config.apply_env()?;
+        Ok(config)
+    }
+
+    /// Take every setting not given on the command line from `config`.
+    ///
+    /// Precedence is CLI > environment > profile > config file > built-in
+    /// defaults (all but the CLI are already folded into `config`), with
+    /// `matches` telling which values the user actually passed.
+    pub fn apply_config(
+        &mut self,
+        config: &Config,
+        matches: &ArgMatches,
+    ) -> Result<(), ConfigError> {
+        let from_cli = |id: &str| matches.value_source(id) == Some(ValueSource::CommandLine);
+        let invalid = |field: &str, message: String| ConfigError::Invalid {
+            field: field.to_string(),
+            message,
+        };
+
+        if !from_cli("shell") {
+            self.shell = config.shell.command.clone();
+        }
+        if !from_cli("shell_args") {
+            self.shell_args = config.shell.args.clone();
+        }
+        if !from_cli("camera") {
+            self.camera = DeviceSelector::Index(config.camera.device);
+        }
+        if !from_cli("mirror") {
+            self.mirror = config.camera.mirror;
+        }
+        if !from_cli("resolution") {
+            self.resolution = Resolution::from_str(&config.camera.resolution)
+                .map_err(|e| invalid("camera.resolution", e))?;
+        }
+        if !from_cli("no_camera") {
+            self.no_camera = !config.modal.visible;
+        }
+        if !from_cli("position") {
+            self.position = Position::from_str(&config.modal.position, true)
+                .map_err(|e| invalid("modal.position", e))?;
+        }
+        if !from_cli("size") {
+            self.size =
+                Size::from_str(&config.modal.size, true).map_err(|e| invalid("modal.size", e))?;
+        }
+        let in_range = |field: &str, value: i64, range: RangeInclusive<i64>| {
+            if range.contains(&value) {
+                Ok(())
+            } else {
+                let message = format!("{} is not in {}..={}", value, range.start(), range.end());
+                Err(invalid(field, message))
+            }
+        };
+        if !from_cli("modal_refresh_rate") {
+            let rate = config.modal.refresh_rate;
+            in_range("modal.refresh_rate", rate.into(), MODAL_REFRESH_RATES)?;
+            self.modal_refresh_rate = rate;
+        }
+        if !from_cli("pty_refresh_ms") {
+            let interval = config.pty.refresh_ms;
+            in_range("pty.refresh_ms", interval.into(), PTY_REFRESH_MS)?;
+            self.pty_refresh_ms = interval;
+        }
+        if !from_cli("transparency_step") {
+            let step = config.modal.transparency_step;
+            in_range("modal.transparency_step", step.into(), TRANSPARENCY_STEPS)?;
+            self.transparency_step = step;
+        }
+        if !from_cli("charset") {
+            self.charset = CharacterSet::from_str(&config.ascii.charset)
+                .map_err(|e| invalid("ascii.charset", e))?;
+        }
+        if !from_cli("invert") {
+            self.invert = config.ascii.invert;
+        }
+        if !from_cli("no_status") {
+            self.no_status = !config.ui.status_bar;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Subcommand, Debug)]
 pub enum Command {
     /// List available cameras
-    ListCameras,
+    ListCameras {
+        /// Also list each camera's supported resolutions, formats, and frame rates
+        #[arg(long, short)]
+        verbose: bool,
+    },
+    /// List charsets, positions, and sizes
+    ListPresets,
+    /// Print a shell completion script to stdout
+    Completions {
+        /// Shell to generate completions for
+        shell: clap_complete::Shell,
+    },
+    /// Print diagnostics (platform, terminal, cameras, config) for a bug report
+    ReportBug {
+        /// Write to this file instead of stdout
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Print the version and build info
+    Version {
+        /// Print build info and capabilities as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Capture one camera frame and print it as ASCII text
+    AsciiSnapshot {
+        /// Grid width in characters
+        #[arg(long, default_value_t = 80, value_parser = clap::value_parser!(u16).range(1..))]
+        cols: u16,
+        /// Grid height in characters
+        #[arg(long, default_value_t = 40, value_parser = clap::value_parser!(u16).range(1..))]
+        rows: u16,
+    },
+    /// Render a roff man page
+    Man {
+        /// Write to this file instead of stdout
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
     /// Configuration management
     Config {
         #[command(subcommand)]
@@ -69,8 +362,21 @@ pub enum Command {
 pub enum ConfigAction {
     /// Show current configuration
     Show,
-    /// Create default config file
-    Init,
+    /// Create a commented default config file
+    Init {
+        /// Write here instead of the default config location
+        #[arg(long, value_name = "PATH")]
+        path: Option<PathBuf>,
+        /// Overwrite an existing file
+        #[arg(long)]
+        force: bool,
+    },
+    /// Check a config file for invalid values
+    Validate {
+        /// Config file to check instead of the default location
+        #[arg(long, value_name = "PATH")]
+        path: Option<PathBuf>,
+    },
 }
 
 #[cfg(test)]
@@ -81,18 +387,236 @@ mod tests {
     fn test_args_defaults() {
         let args = Args::parse_from(["space-recorder"]);
         assert!(args.shell.is_none());
-        assert_eq!(args.camera, 0);
+        assert_eq!(args.camera, DeviceSelector::Index(0));
         assert!(!args.no_camera);
         assert_eq!(args.position, Position::BottomRight);
         assert_eq!(args.size, Size::Small);
         assert_eq!(args.charset, CharacterSet::Blocks);
+        assert_eq!(args.braille_threshold, 80);
+        assert_eq!(args.render_scale, 1);
+        assert_eq!(args.modal_refresh_rate, 15);
+        assert!(args.char_aspect.is_none());
         assert!(!args.mirror);
         assert!(!args.invert);
+        assert!(!args.auto_levels);
+        assert!(args.sharpen.is_none());
+        assert!(!args.grayscale);
+        assert!(!args.perf);
         assert!(!args.no_status);
+        assert!(!args.quit_hotkey);
         assert!(args.config.is_none());
+        assert!(args.profile.is_none());
+        assert_eq!(args.resolution, Resolution::MEDIUM);
         assert!(args.command.is_none());
     }
 
+    /// Parse `argv` and merge `config_toml` (with `--profile`, if given) into it.
+    fn merged(argv: &[&str], config_toml: &str) -> Result<Args, ConfigError> {
+        use clap::{CommandFactory, FromArgMatches};
+
+        let matches = Args::command().get_matches_from(argv);
+        let mut args = Args::from_arg_matches(&matches).unwrap();
+        let mut config: Config = toml::from_str(config_toml).unwrap();
+        if let Some(name) = &args.profile {
+            config = config.with_profile(name)?;
+        }
+        args.apply_config(&config, &matches)?;
+        Ok(args)
+    }
+
+    const CONFIG: &str = r#"
+[camera]
+mirror = true
+resolution = "1280x720"
+
+[modal]
+position = "top-left"
+size = "large"
+
+[profiles.demo]
+modal = { position = "center" }
+"#;
+
+    #[test]
+    fn test_config_fills_unset_options() {
+        let args = merged(&["space-recorder"], CONFIG).unwrap();
+        assert_eq!(args.position, Position::TopLeft);
+        assert_eq!(args.size, Size::Large);
+        assert!(args.mirror);
+        assert_eq!(args.resolution, Resolution::HIGH);
+        // Not in the file: built-in defaults
+        assert_eq!(args.charset, CharacterSet::Blocks);
+        assert_eq!(args.modal_refresh_rate, 15);
+    }
+
+    #[test]
+    fn test_strict_config_rejects_unknown_keys() {
+        let path = std::env::temp_dir().join(format!(
+            "space-recorder-args-strict-{}.toml",
+            std::process::id()
+        ));
+        std::fs::write(&path, "[ascii]\ninvrt = true\n").unwrap();
+        let config = path.to_str().unwrap();
+        let lenient = Args::parse_from(["space-recorder", "-c", config]).load_config();
+        let strict =
+            Args::parse_from(["space-recorder", "-c", config, "--strict-config"]).load_config();
+        let _ = std::fs::remove_file(&path);
+
+        assert!(lenient.is_ok());
+        assert!(
+            strict
+                .unwrap_err()
+                .to_string()
+                .contains("\"invrt\" in [ascii]")
+        );
+    }
+
+    #[test]
+    fn test_cli_beats_profile_beats_config() {
+        let args = merged(&["space-recorder", "--profile", "demo"], CONFIG).unwrap();
+        assert_eq!(args.position, Position::Center);
+        assert_eq!(args.size, Size::Large);
+
+        let args = merged(
+            &[
+                "space-recorder",
+                "--profile",
+                "demo",
+                "--position",
+                "top-right",
+                "--size",
+                "small",
+            ],
+            CONFIG,
+        )
+        .unwrap();
+        assert_eq!(args.position, Position::TopRight);
+        assert_eq!(args.size, Size::Small);
+    }
+
+    #[test]
+    fn test_env_values_are_range_checked_like_flags() {
+        use clap::{CommandFactory, FromArgMatches};
+
+        let matches = Args::command().get_matches_from(["space-recorder"]);
+        let mut args = Args::from_arg_matches(&matches).unwrap();
+        let mut config = Config::default();
+        config
+            .apply_env_from(|name| (name == "SPACE_RECORDER_REFRESH_RATE").then(|| "0".to_string()))
+            .unwrap();
+
+        let err = args.apply_config(&config, &matches).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid modal.refresh_rate: 0 is not in 1..=60"
+        );
+    }
+
+    #[test]
+    fn test_merge_errors_name_the_field() {
+        let err = merged(&["space-recorder", "--profile", "solo"], CONFIG).unwrap_err();
+        assert!(err.to_string().contains("\"solo\""), "{}", err);
+
+        let err = merged(&["space-recorder"], "[modal]\nsize = \"giant\"\n").unwrap_err();
+        assert!(
+            err.to_string().starts_with("Invalid modal.size:"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_args_braille_threshold() {
+        let args = Args::parse_from(["space-recorder", "--braille-threshold", "40"]);
+        assert_eq!(args.braille_threshold, 40);
+
+        assert!(Args::try_parse_from(["space-recorder", "--braille-threshold", "256"]).is_err());
+    }
+
+    #[test]
+    fn test_args_render_scale() {
+        let args = Args::parse_from(["space-recorder", "--render-scale", "2"]);
+        assert_eq!(args.render_scale, 2);
+
+        assert!(Args::try_parse_from(["space-recorder", "--render-scale", "0"]).is_err());
+        assert!(Args::try_parse_from(["space-recorder", "--render-scale", "5"]).is_err());
+    }
+
+    #[test]
+    fn test_args_modal_refresh_rate() {
+        let args = Args::parse_from(["space-recorder", "--modal-refresh-rate", "5"]);
+        assert_eq!(args.modal_refresh_rate, 5);
+
+        assert!(Args::try_parse_from(["space-recorder", "--modal-refresh-rate", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_shell_argv() {
+        assert!(Args::parse_from(["space-recorder"]).shell_argv().is_empty());
+
+        let args = Args::parse_from([
+            "space-recorder",
+            "--login",
+            "--shell-arg",
+            "-i",
+            "--shell-arg=--norc",
+            "--exec",
+            "make test",
+        ]);
+        assert_eq!(args.shell_argv(), ["-l", "-i", "--norc", "-c", "make test"]);
+    }
+
+    #[test]
+    fn test_args_transparency_step() {
+        assert_eq!(Args::parse_from(["space-recorder"]).transparency_step, 10);
+        let args = Args::parse_from(["space-recorder", "--transparency-step", "2"]);
+        assert_eq!(args.transparency_step, 2);
+
+        assert!(Args::try_parse_from(["space-recorder", "--transparency-step", "0"]).is_err());
+        assert!(Args::try_parse_from(["space-recorder", "--transparency-step", "51"]).is_err());
+    }
+
+    #[test]
+    fn test_args_color_depth() {
+        assert_eq!(Args::parse_from(["space-recorder"]).color_depth, None);
+        let args = Args::parse_from(["space-recorder", "--color-depth", "256"]);
+        assert_eq!(args.color_depth, Some(ColorDepth::Indexed256));
+        let args = Args::parse_from(["space-recorder", "--color-depth", "mono"]);
+        assert_eq!(args.color_depth, Some(ColorDepth::Monochrome));
+
+        assert!(Args::try_parse_from(["space-recorder", "--color-depth", "16"]).is_err());
+    }
+
+    #[test]
+    fn test_args_pty_refresh_ms() {
+        assert_eq!(Args::parse_from(["space-recorder"]).pty_refresh_ms, 16);
+        let args = Args::parse_from(["space-recorder", "--pty-refresh-ms", "33"]);
+        assert_eq!(args.pty_refresh_ms, 33);
+
+        assert!(Args::try_parse_from(["space-recorder", "--pty-refresh-ms", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_args_char_aspect() {
+        let args = Args::parse_from(["space-recorder", "--char-aspect", "0.9"]);
+        assert_eq!(args.char_aspect, Some(0.9));
+
+        assert!(Args::try_parse_from(["space-recorder", "--char-aspect", "0.1"]).is_err());
+        assert!(Args::try_parse_from(["space-recorder", "--char-aspect", "4"]).is_err());
+        assert!(Args::try_parse_from(["space-recorder", "--char-aspect", "wide"]).is_err());
+    }
+
+    #[test]
+    fn test_args_rotate() {
+        let args = Args::parse_from(["space-recorder"]);
+        assert_eq!(args.rotate, Rotation::Deg0);
+
+        let args = Args::parse_from(["space-recorder", "--rotate", "180"]);
+        assert_eq!(args.rotate, Rotation::Deg180);
+
+        assert!(Args::try_parse_from(["space-recorder", "--rotate", "45"]).is_err());
+    }
+
     #[test]
     fn test_args_mirror_flag() {
         let args = Args::parse_from(["space-recorder", "--mirror"]);
@@ -105,12 +629,45 @@ mod tests {
         assert!(args.invert);
     }
 
+    #[test]
+    fn test_args_auto_levels_flag() {
+        let args = Args::parse_from(["space-recorder", "--auto-levels"]);
+        assert!(args.auto_levels);
+    }
+
+    #[test]
+    fn test_args_sharpen_amount() {
+        let args = Args::parse_from(["space-recorder", "--sharpen", "1.5"]);
+        assert_eq!(args.sharpen, Some(1.5));
+
+        assert!(Args::try_parse_from(["space-recorder", "--sharpen", "-1"]).is_err());
+        assert!(Args::try_parse_from(["space-recorder", "--sharpen", "6"]).is_err());
+    }
+
+    #[test]
+    fn test_args_grayscale_flag() {
+        let args = Args::parse_from(["space-recorder", "--grayscale"]);
+        assert!(args.grayscale);
+    }
+
+    #[test]
+    fn test_args_perf_flag() {
+        let args = Args::parse_from(["space-recorder", "--perf"]);
+        assert!(args.perf);
+    }
+
     #[test]
     fn test_args_no_status_flag() {
         let args = Args::parse_from(["space-recorder", "--no-status"]);
         assert!(args.no_status);
     }
 
+    #[test]
+    fn test_args_quit_hotkey_flag() {
+        let args = Args::parse_from(["space-recorder", "--quit-hotkey"]);
+        assert!(args.quit_hotkey);
+    }
+
     #[test]
     fn test_args_no_camera_flag() {
         let args = Args::parse_from(["space-recorder", "--no-camera"]);
@@ -120,7 +677,18 @@ mod tests {
     #[test]
     fn test_args_camera_index() {
         let args = Args::parse_from(["space-recorder", "--camera", "2"]);
-        assert_eq!(args.camera, 2);
+        assert_eq!(args.camera, DeviceSelector::Index(2));
+    }
+
+    #[test]
+    fn test_args_camera_name() {
+        let args = Args::parse_from(["space-recorder", "--camera", "FaceTime HD Camera"]);
+        assert_eq!(
+            args.camera,
+            DeviceSelector::Name("FaceTime HD Camera".to_string())
+        );
+
+        assert!(Args::try_parse_from(["space-recorder", "--camera", ""]).is_err());
     }
 
     #[test]
@@ -166,6 +734,21 @@ mod tests {
 
         let args = Args::parse_from(["space-recorder", "--charset", "braille"]);
         assert_eq!(args.charset, CharacterSet::Braille);
+
+        let args = Args::parse_from(["space-recorder", "--charset", "extended"]);
+        assert_eq!(args.charset, CharacterSet::Extended);
+    }
+
+    #[test]
+    fn test_args_charset_custom_ramp() {
+        let args = Args::parse_from(["space-recorder", "--charset", "custom: .oO@"]);
+        assert_eq!(
+            args.charset,
+            CharacterSet::Custom(vec![' ', '.', 'o', 'O', '@'])
+        );
+
+        let err = Args::try_parse_from(["space-recorder", "--charset", "custom:@"]).unwrap_err();
+        assert!(err.to_string().contains("at least 2 characters"), "{}", err);
     }
 
     #[test]
@@ -189,7 +772,101 @@ mod tests {
     #[test]
     fn test_args_list_cameras_subcommand() {
         let args = Args::parse_from(["space-recorder", "list-cameras"]);
-        assert!(matches!(args.command, Some(Command::ListCameras)));
+        assert!(matches!(
+            args.command,
+            Some(Command::ListCameras { verbose: false })
+        ));
+
+        let args = Args::parse_from(["space-recorder", "list-cameras", "--verbose"]);
+        assert!(matches!(
+            args.command,
+            Some(Command::ListCameras { verbose: true })
+        ));
+    }
+
+    #[test]
+    fn test_args_list_presets_subcommand() {
+        let args = Args::parse_from(["space-recorder", "list-presets"]);
+        assert!(matches!(args.command, Some(Command::ListPresets)));
+    }
+
+    #[test]
+    fn test_args_completions_subcommand() {
+        let args = Args::parse_from(["space-recorder", "completions", "zsh"]);
+        assert!(matches!(
+            args.command,
+            Some(Command::Completions {
+                shell: clap_complete::Shell::Zsh
+            })
+        ));
+    }
+
+    #[test]
+    fn test_args_report_bug_subcommand() {
+        let args = Args::parse_from(["space-recorder", "report-bug"]);
+        assert!(matches!(
+            args.command,
+            Some(Command::ReportBug { output: None })
+        ));
+    }
+
+    #[test]
+    fn test_args_version_subcommand() {
+        let args = Args::parse_from(["space-recorder", "version"]);
+        assert!(matches!(
+            args.command,
+            Some(Command::Version { json: false })
+        ));
+
+        let args = Args::parse_from(["space-recorder", "version", "--json"]);
+        assert!(matches!(
+            args.command,
+            Some(Command::Version { json: true })
+        ));
+    }
+
+    #[test]
+    fn test_args_ascii_snapshot_subcommand() {
+        let args = Args::parse_from(["space-recorder", "ascii-snapshot"]);
+        assert!(matches!(
+            args.command,
+            Some(Command::AsciiSnapshot { cols: 80, rows: 40 })
+        ));
+
+        let args = Args::parse_from([
+            "space-recorder",
+            "--charset",
+            "braille",
+            "ascii-snapshot",
+            "--cols",
+            "120",
+            "--rows",
+            "30",
+        ]);
+        assert!(matches!(
+            args.command,
+            Some(Command::AsciiSnapshot {
+                cols: 120,
+                rows: 30
+            })
+        ));
+        assert!(matches!(args.charset, CharacterSet::Braille));
+
+        assert!(Args::try_parse_from(["space-recorder", "ascii-snapshot", "--cols", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_args_man_subcommand() {
+        let args = Args::parse_from(["space-recorder", "man"]);
+        assert!(matches!(args.command, Some(Command::Man { output: None })));
+
+        let args = Args::parse_from(["space-recorder", "man", "-o", "space-recorder.1"]);
+        match args.command {
+            Some(Command::Man { output: Some(path) }) => {
+                assert_eq!(path, PathBuf::from("space-recorder.1"))
+            }
+            other => panic!("expected man with output, got {:?}", other),
+        }
     }
 
     #[test]
@@ -208,10 +885,40 @@ mod tests {
         let args = Args::parse_from(["space-recorder", "config", "init"]);
         match args.command {
             Some(Command::Config {
-                action: ConfigAction::Init,
+                action:
+                    ConfigAction::Init {
+                        path: None,
+                        force: false,
+                    },
             }) => (),
             _ => panic!("Expected Config Init subcommand"),
         }
+
+        let args = Args::parse_from([
+            "space-recorder",
+            "config",
+            "init",
+            "--path",
+            "/tmp/sr.toml",
+            "--force",
+        ]);
+        match args.command {
+            Some(Command::Config {
+                action: ConfigAction::Init { path, force: true },
+            }) => assert_eq!(path, Some(PathBuf::from("/tmp/sr.toml"))),
+            _ => panic!("Expected Config Init --path --force"),
+        }
+    }
+
+    #[test]
+    fn test_args_config_validate_subcommand() {
+        let args = Args::parse_from(["space-recorder", "config", "validate", "--path", "c.toml"]);
+        match args.command {
+            Some(Command::Config {
+                action: ConfigAction::Validate { path },
+            }) => assert_eq!(path, Some(PathBuf::from("c.toml"))),
+            _ => panic!("Expected Config Validate subcommand"),
+        }
     }
 
     #[test]
@@ -233,7 +940,7 @@ mod tests {
             "--no-status",
         ]);
         assert_eq!(args.shell, Some("/bin/zsh".to_string()));
-        assert_eq!(args.camera, 1);
+        assert_eq!(args.camera, DeviceSelector::Index(1));
         assert_eq!(args.position, Position::TopLeft);
         assert_eq!(args.size, Size::Large);
         assert_eq!(args.charset, CharacterSet::Braille);
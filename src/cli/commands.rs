@@ -1,12 +1,29 @@
-//! Subcommand handlers for list-cameras and config actions.
+//! Subcommand handlers for list-cameras, list-presets, completions, man, report-bug,
+//! version, ascii-snapshot, and config actions.
 
-use super::args::ConfigAction;
+use clap::{CommandFactory, Parser};
+use std::error::Error;
+use std::io::Write;
+use std::path::Path;
+
+use super::args::{Args, ConfigAction};
+use super::exit_code::ExitCode;
+use crate::ascii::{self, CharSet, SnapshotSettings};
 use crate::camera;
-use crate::config::default_path as get_config_path;
+use crate::config::{Config, DEFAULT_CONFIG, default_path as get_config_path};
+use crate::input::KeyBinding;
+use crate::terminal::{ModalPosition, ModalSize};
 
 /// List available cameras and print them to stdout.
-pub fn list_cameras() {
-    match camera::list_devices() {
+///
+/// With `verbose`, each camera is opened to query its capture modes.
+pub fn list_cameras(verbose: bool) {
+    let devices = if verbose {
+        camera::list_devices_verbose()
+    } else {
+        camera::list_devices()
+    };
+    match devices {
         Ok(devices) => {
             if devices.is_empty() {
                 println!("No cameras found.");
@@ -19,6 +36,9 @@ pub fn list_cameras() {
                 println!("Available cameras:");
                 for device in devices {
                     println!("  {}", device);
+                    if verbose {
+                        print!("{}", format_camera_modes(&device));
+                    }
                 }
                 println!();
                 println!("Use --camera <index> to select a camera.");
@@ -26,11 +46,302 @@ pub fn list_cameras() {
         }
         Err(e) => {
             eprintln!("Error: {}", e);
-            std::process::exit(1);
+            ExitCode::from(&e).exit();
+        }
+    }
+}
+
+/// Format a camera's capture modes as indented lines, one per resolution.
+fn format_camera_modes(device: &camera::CameraInfo) -> String {
+    if device.modes.is_empty() {
+        return "      (capabilities unavailable)\n".to_string();
+    }
+
+    let mut out = format!("      formats: {}\n", device.formats().join(", "));
+    for resolution in device.resolutions() {
+        let mut rates: Vec<u32> = device
+            .modes
+            .iter()
+            .filter(|m| m.resolution == resolution)
+            .map(|m| m.fps)
+            .collect();
+        rates.sort_unstable_by(|a, b| b.cmp(a));
+        rates.dedup();
+        let rates: Vec<String> = rates.iter().map(u32::to_string).collect();
+        out.push_str(&format!(
+            "      {:<10} {} fps\n",
+            resolution,
+            rates.join("/")
+        ));
+    }
+    out
+}
+
+/// Print all presets (charsets, positions, sizes) grouped by category.
+pub fn list_presets() {
+    println!("Character sets (--charset, Alt+A to cycle):");
+    for charset in CharSet::all() {
+        println!("  {:<14}{}", charset.name(), charset.description());
+    }
+    println!(
+        "  {:<14}Your own density ramp, dark to light",
+        "custom:<ramp>"
+    );
+    println!();
+
+    println!("Positions (--position, Alt+P to cycle):");
+    for position in ModalPosition::all() {
+        println!("  {}", position.name());
+    }
+    println!();
+
+    println!("Sizes (--size, Alt+S to cycle):");
+    for size in ModalSize::all() {
+        let (width, height) = size.inner_dimensions();
+        println!("  {:<14}{}x{} characters", size.name(), width, height);
+    }
+}
+
+/// Write a completion script for `shell` to `out`.
+///
+/// Users can source the output, e.g. `space-recorder completions zsh > _space-recorder`.
+pub fn write_completions(shell: clap_complete::Shell, out: &mut dyn Write) {
+    let mut command = Args::command();
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name, out);
+}
+
+/// Render the man page for the CLI as roff to `out`.
+///
+/// Packagers can install the output as `space-recorder.1`.
+pub fn write_man_page(out: &mut dyn Write) -> std::io::Result<()> {
+    clap_mangen::Man::new(Args::command()).render(out)
+}
+
+/// Write the man page to `output`, or stdout when no path is given.
+pub fn export_man_page(output: Option<&Path>) {
+    let result = match output {
+        Some(path) => std::fs::File::create(path).and_then(|mut file| write_man_page(&mut file)),
+        None => write_man_page(&mut std::io::stdout()),
+    };
+
+    match result {
+        Ok(()) => {
+            if let Some(path) = output {
+                println!("Wrote man page: {}", path.display());
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to write man page: {}", e);
+            ExitCode::Failure.exit();
         }
     }
 }
 
+/// Git commit the binary was built from, if it was built from a checkout.
+pub const GIT_HASH: Option<&str> = option_env!("SPACE_RECORDER_GIT_HASH");
+
+/// Crate version with the git hash appended when known, e.g. "0.1.0 (1a2b3c4)".
+pub fn version_string() -> String {
+    match GIT_HASH {
+        Some(hash) => format!("{} ({})", env!("CARGO_PKG_VERSION"), hash),
+        None => env!("CARGO_PKG_VERSION").to_string(),
+    }
+}
+
+/// Quote and escape a string as a JSON string literal.
+fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Build info and capabilities as a JSON object.
+///
+/// Cheap to produce: it doesn't open or enumerate cameras (use report-bug for that).
+pub fn version_json() -> String {
+    let git_hash = GIT_HASH.map_or("null".to_string(), json_string);
+    // Cargo.toml only enables nokhwa's AVFoundation input
+    let camera_backend = if cfg!(target_os = "macos") {
+        json_string("avfoundation")
+    } else {
+        "null".to_string()
+    };
+    let charsets: Vec<String> = CharSet::all()
+        .iter()
+        .map(|c| json_string(c.name()))
+        .chain(std::iter::once(json_string("custom")))
+        .collect();
+
+    format!(
+        r#"{{
+  "name": {},
+  "version": {},
+  "git_hash": {},
+  "platform": {{
+    "os": {},
+    "arch": {}
+  }},
+  "capabilities": {{
+    "truecolor": {},
+    "camera_backend": {},
+    "charsets": [{}]
+  }}
+}}"#,
+        json_string(env!("CARGO_PKG_NAME")),
+        json_string(env!("CARGO_PKG_VERSION")),
+        git_hash,
+        json_string(std::env::consts::OS),
+        json_string(std::env::consts::ARCH),
+        crate::terminal::supports_truecolor(),
+        camera_backend,
+        charsets.join(", "),
+    )
+}
+
+/// Print the version, as plain text or as a JSON report.
+pub fn print_version(json: bool) {
+    if json {
+        println!("{}", version_json());
+    } else {
+        println!("{} {}", env!("CARGO_PKG_NAME"), version_string());
+    }
+}
+
+/// Capture one camera frame and print it to stdout as ASCII text.
+pub fn ascii_snapshot(settings: &SnapshotSettings) {
+    match ascii::capture_ascii_snapshot(settings) {
+        Ok(text) => println!("{}", text),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            if matches!(e, camera::CameraError::NoDevices) {
+                eprintln!("ascii-snapshot needs a connected camera; run 'list-cameras' to check.");
+            }
+            ExitCode::from(&e).exit();
+        }
+    }
+}
+
+/// Config keys whose values are replaced in bug reports.
+const SECRET_KEY_HINTS: &[&str] = &["key", "token", "secret", "password"];
+
+/// Build a plain-text diagnostics dump for attaching to bug reports.
+///
+/// Covers the version, platform, terminal capabilities, detected cameras,
+/// and the config file (with secret-looking values redacted).
+pub fn bug_report(config_path: Option<&Path>) -> String {
+    let mut report = String::new();
+    let mut line = |text: String| {
+        report.push_str(&text);
+        report.push('\n');
+    };
+
+    line("space-recorder bug report".to_string());
+    line(String::new());
+    line(format!("Version: {}", version_string()));
+    line(format!(
+        "Platform: {} ({})",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    ));
+    line(format!("Shell: {}", crate::pty::select_shell(None)));
+    line(format!("TERM: {}", env_or_unset("TERM")));
+    line(format!(
+        "COLORTERM: {} (truecolor: {})",
+        env_or_unset("COLORTERM"),
+        if crate::terminal::supports_truecolor() {
+            "yes"
+        } else {
+            "no"
+        }
+    ));
+    line(String::new());
+
+    match camera::list_devices() {
+        Ok(devices) if devices.is_empty() => line("Cameras: none found".to_string()),
+        Ok(devices) => {
+            line("Cameras:".to_string());
+            for device in devices {
+                line(format!("  {}", device));
+            }
+        }
+        Err(e) => line(format!(
+            "Cameras: error: {} (exit code {})",
+            e,
+            ExitCode::from(&e).code()
+        )),
+    }
+    line(String::new());
+
+    let config_path = config_path
+        .map(Path::to_path_buf)
+        .unwrap_or_else(get_config_path);
+    match std::fs::read_to_string(&config_path) {
+        Ok(contents) => {
+            line(format!("Config file: {} (exists)", config_path.display()));
+            line(redact_secrets(&contents));
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => line(format!(
+            "Config file: {} (not found)",
+            config_path.display()
+        )),
+        Err(e) => line(format!(
+            "Config file: {} (unreadable: {})",
+            config_path.display(),
+            e
+        )),
+    }
+
+    report
+}
+
+fn env_or_unset(name: &str) -> String {
+    std::env::var(name).unwrap_or_else(|_| "(unset)".to_string())
+}
+
+/// Replace the value of any `key = value` line whose key looks like a secret.
+fn redact_secrets(toml: &str) -> String {
+    toml.lines()
+        .map(|line| match line.split_once('=') {
+            Some((key, _))
+                if !key.trim_start().starts_with('#')
+                    && SECRET_KEY_HINTS
+                        .iter()
+                        .any(|hint| key.to_lowercase().contains(hint)) =>
+            {
+                format!("{}= \"<redacted>\"", key)
+            }
+            _ => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Write the bug report to `output`, or stdout when no path is given.
+pub fn report_bug(output: Option<&Path>, config_path: Option<&Path>) {
+    let report = bug_report(config_path);
+    match output {
+        Some(path) => {
+            if let Err(e) = std::fs::write(path, report) {
+                eprintln!("Failed to write bug report: {}", e);
+                ExitCode::Failure.exit();
+            }
+            println!("Wrote bug report: {}", path.display());
+        }
+        None => print!("{}", report),
+    }
+}
+
 /// Handle config subcommand actions.
 pub fn handle_config_action(action: ConfigAction) {
     match action {
@@ -55,74 +366,403 @@ pub fn handle_config_action(action: ConfigAction) {
                 println!("Config file: {} (not found)", config_path.display());
             }
         }
-        ConfigAction::Init => {
-            let config_path = get_config_path();
+        ConfigAction::Init { path, force } => {
+            let config_path = path.unwrap_or_else(get_config_path);
 
-            if config_path.exists() {
-                eprintln!("Config file already exists: {}", config_path.display());
-                eprintln!("Use 'space-recorder config show' to view current settings.");
-                std::process::exit(1);
+            match write_default_config(&config_path, force) {
+                Ok(()) => println!("Created config file: {}", config_path.display()),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    eprintln!("Config file already exists: {}", config_path.display());
+                    eprintln!(
+                        "Use 'space-recorder config show' to view current settings, or --force to overwrite."
+                    );
+                    ExitCode::ConfigError.exit();
+                }
+                Err(e) => {
+                    eprintln!("Error writing config file: {}", e);
+                    ExitCode::ConfigError.exit();
+                }
             }
+        }
+        ConfigAction::Validate { path } => {
+            let config_path = path.unwrap_or_else(get_config_path);
 
-            // Create parent directories if needed
-            if let Some(parent) = config_path.parent()
-                && let Err(e) = std::fs::create_dir_all(parent)
-            {
-                eprintln!("Error creating config directory: {}", e);
-                std::process::exit(1);
+            match validate_config_file(&config_path) {
+                Ok(()) => println!("{}: OK", config_path.display()),
+                Err(problems) => {
+                    eprintln!("{}: {} problem(s)", config_path.display(), problems.len());
+                    for problem in problems {
+                        eprintln!("  {}", problem);
+                    }
+                    ExitCode::ConfigError.exit();
+                }
             }
+        }
+    }
+}
 
-            // Write default config
-            let default_config = r#"# space-recorder configuration
-
-[shell]
-# Shell to spawn (default: $SHELL)
-# command = "/bin/zsh"
-
-[camera]
-# Camera device index
-device = 0
-# Mirror horizontally (selfie mode)
-mirror = true
-# Capture resolution (lower = faster)
-resolution = "640x480"
-
-[modal]
-# Start with camera visible
-visible = true
-# Position: top-left, top-right, bottom-left, bottom-right, center
-position = "bottom-right"
-# Size: small, medium, large
-size = "small"
-# Show border around modal
-border = true
-
-[ascii]
-# Character set: standard, blocks, minimal, braille
-charset = "standard"
-# Invert brightness (for light themes)
-invert = false
-# Enable edge detection for sharper features
-edge_detection = false
-
-[ui]
-# Show status bar
-status_bar = true
-
-[hotkeys]
-# Key bindings (Alt + key)
-toggle_camera = "c"
-cycle_position = "p"
-cycle_size = "s"
-cycle_charset = "a"
-"#;
-
-            if let Err(e) = std::fs::write(&config_path, default_config) {
-                eprintln!("Error writing config file: {}", e);
-                std::process::exit(1);
+/// Largest capture resolution a config may request (8K UHD).
+const MAX_RESOLUTION: camera::Resolution = camera::Resolution {
+    width: 7680,
+    height: 4320,
+};
+
+/// Load and check a config file, returning one message per problem.
+///
+/// A file that doesn't exist or doesn't parse is a single problem.
+pub fn validate_config_file(path: &Path) -> Result<(), Vec<String>> {
+    let config = Config::load_from_explicit(path).map_err(|e| vec![e.to_string()])?;
+    let problems = validate_config(&config);
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(problems)
+    }
+}
+
+/// Check a config's values, returning `field = value: reason` per problem.
+///
+/// Settings that have a CLI flag go through that flag's parser, so a value
+/// is accepted here exactly when it would be on the command line.
+pub fn validate_config(config: &Config) -> Vec<String> {
+    let mut problems = Vec::new();
+    // `value` is shown as written in the TOML file
+    let mut check = |field: &str, value: String, result: Result<(), String>| {
+        if let Err(reason) = result {
+            problems.push(format!("{} = {}: {}", field, value, reason));
+        }
+    };
+
+    let resolution = &config.camera.resolution;
+    let result =
+        resolution
+            .parse::<camera::Resolution>()
+            .and_then(|res| match MAX_RESOLUTION.covers(res) {
+                true => Ok(()),
+                false => Err(format!("exceeds the {} maximum", MAX_RESOLUTION)),
+            });
+    check("camera.resolution", format!("{:?}", resolution), result);
+
+    let modal = &config.modal;
+    for (field, flag, value) in [
+        ("modal.position", "position", &modal.position),
+        ("modal.size", "size", &modal.size),
+        ("ascii.charset", "charset", &config.ascii.charset),
+    ] {
+        check(field, format!("{:?}", value), check_flag_value(flag, value));
+    }
+    for (field, flag, value) in [
+        (
+            "modal.refresh_rate",
+            "modal-refresh-rate",
+            modal.refresh_rate,
+        ),
+        (
+            "modal.transparency_step",
+            "transparency-step",
+            modal.transparency_step.into(),
+        ),
+        ("pty.refresh_ms", "pty-refresh-ms", config.pty.refresh_ms),
+    ] {
+        let value = value.to_string();
+        let result = check_flag_value(flag, &value);
+        check(field, value, result);
+    }
+
+    let hotkeys = &config.hotkeys;
+    let mut bound: Vec<(&str, &String, KeyBinding)> = Vec::new();
+    for (field, value) in [
+        ("hotkeys.toggle_camera", Some(&hotkeys.toggle_camera)),
+        ("hotkeys.cycle_position", Some(&hotkeys.cycle_position)),
+        ("hotkeys.cycle_size", Some(&hotkeys.cycle_size)),
+        ("hotkeys.cycle_charset", Some(&hotkeys.cycle_charset)),
+        (
+            "hotkeys.cycle_transparency",
+            Some(&hotkeys.cycle_transparency),
+        ),
+        ("hotkeys.toggle_color", Some(&hotkeys.toggle_color)),
+        ("hotkeys.toggle_invert", Some(&hotkeys.toggle_invert)),
+        ("hotkeys.toggle_mirror", hotkeys.toggle_mirror.as_ref()),
+        ("hotkeys.cycle_gamma", hotkeys.cycle_gamma.as_ref()),
+        ("hotkeys.toggle_perf", hotkeys.toggle_perf.as_ref()),
+        ("hotkeys.cycle_dither", hotkeys.cycle_dither.as_ref()),
+        (
+            "hotkeys.toggle_auto_levels",
+            hotkeys.toggle_auto_levels.as_ref(),
+        ),
+        ("hotkeys.toggle_sharpen", hotkeys.toggle_sharpen.as_ref()),
+        ("hotkeys.quit", Some(&hotkeys.quit)),
+    ] {
+        let Some(value) = value else {
+            continue;
+        };
+        let result = value
+            .parse::<KeyBinding>()
+            .map(|binding| bound.push((field, value, binding)));
+        check(field, format!("{:?}", value), result);
+    }
+    // Of two actions on one key only the first runs; the quit key counts
+    // although it needs --quit-hotkey
+    for (i, (first, _, binding)) in bound.iter().enumerate() {
+        for (second, value, other) in &bound[i + 1..] {
+            if binding.overlaps(other) {
+                check(
+                    second,
+                    format!("{:?}", value),
+                    Err(format!("same key as {}", first)),
+                );
+            }
+        }
+    }
+
+    if let Some(shell) = &config.shell.command
+        && shell.trim().is_empty()
+    {
+        check(
+            "shell.command",
+            format!("{:?}", shell),
+            Err("is empty".to_string()),
+        );
+    }
+
+    problems
+}
+
+/// Parse `--flag=value` as the command line would.
+fn check_flag_value(flag: &str, value: &str) -> Result<(), String> {
+    Args::try_parse_from([
+        "space-recorder".to_string(),
+        format!("--{}={}", flag, value),
+    ])
+    .map(drop)
+    .map_err(|e| match e.source() {
+        // Range and custom parsers carry the reason as the source
+        Some(source) => source.to_string(),
+        // Enum flags: clap's first line, plus the accepted values
+        None => {
+            let message = e.to_string();
+            let mut lines = message.lines().map(str::trim);
+            let first = lines.next().unwrap_or_default();
+            let mut reason = first.trim_start_matches("error: ").to_string();
+            if let Some(values) = lines.find(|line| line.starts_with("[possible values")) {
+                reason = format!("{} {}", reason, values);
             }
+            reason
+        }
+    })
+}
 
-            println!("Created config file: {}", config_path.display());
+/// Write the commented default config to `path`, creating parent directories.
+///
+/// Fails with `ErrorKind::AlreadyExists` if the file exists and `force` is off.
+fn write_default_config(path: &Path, force: bool) -> std::io::Result<()> {
+    if path.exists() && !force {
+        return Err(std::io::ErrorKind::AlreadyExists.into());
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, DEFAULT_CONFIG)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap_complete::Shell;
+
+    #[test]
+    fn test_write_completions_for_each_shell() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+            let mut out = Vec::new();
+            write_completions(shell, &mut out);
+            let script = String::from_utf8(out).expect("completion script should be UTF-8");
+            assert!(
+                script.contains("space-recorder") && script.contains("list-cameras"),
+                "{} completions should mention the binary and its subcommands",
+                shell
+            );
         }
     }
+
+    #[test]
+    fn test_json_string_escapes() {
+        assert_eq!(json_string("plain"), "\"plain\"");
+        assert_eq!(json_string("a\"b\\c\n"), "\"a\\\"b\\\\c\\n\"");
+        assert_eq!(json_string("\u{1}"), "\"\\u0001\"");
+    }
+
+    #[test]
+    fn test_format_camera_modes_groups_rates_by_resolution() {
+        use crate::camera::{CameraInfo, CameraMode, DeviceKind, Resolution};
+
+        let mode = |resolution, format: &str, fps| CameraMode {
+            resolution,
+            format: format.to_string(),
+            fps,
+        };
+        let mut device = CameraInfo {
+            index: 0,
+            name: "FaceTime HD Camera".to_string(),
+            description: "Built-in".to_string(),
+            kind: DeviceKind::Physical,
+            modes: vec![
+                mode(Resolution::HIGH, "NV12", 30),
+                mode(Resolution::HIGH, "NV12", 15),
+                mode(Resolution::MEDIUM, "YUYV", 30),
+            ],
+        };
+
+        assert_eq!(
+            format_camera_modes(&device),
+            "      formats: NV12, YUYV\n      1280x720   30/15 fps\n      640x480    30 fps\n"
+        );
+
+        // Devices that refused enumeration still get a line
+        device.modes.clear();
+        assert_eq!(
+            format_camera_modes(&device),
+            "      (capabilities unavailable)\n"
+        );
+    }
+
+    #[test]
+    fn test_version_json_fields() {
+        let json = version_json();
+        assert!(json.starts_with('{') && json.ends_with('}'));
+        assert!(json.contains(&format!("\"version\": \"{}\"", env!("CARGO_PKG_VERSION"))));
+        assert!(json.contains(&format!("\"os\": \"{}\"", std::env::consts::OS)));
+        assert!(json.contains("\"git_hash\": "));
+        assert!(json.contains("\"truecolor\": "));
+        assert!(json.contains("\"braille\""));
+        assert_eq!(json.matches('{').count(), json.matches('}').count());
+    }
+
+    #[test]
+    fn test_version_string_starts_with_crate_version() {
+        assert!(version_string().starts_with(env!("CARGO_PKG_VERSION")));
+    }
+
+    #[test]
+    fn test_bug_report_sections() {
+        let missing = std::env::temp_dir().join("space-recorder-missing-config.toml");
+        let report = bug_report(Some(&missing));
+        assert!(report.contains(&format!("Version: {}", version_string())));
+        assert!(report.contains(&format!("Platform: {}", std::env::consts::OS)));
+        assert!(report.contains("Cameras"));
+        assert!(report.contains("(not found)"));
+    }
+
+    #[test]
+    fn test_bug_report_includes_redacted_config() {
+        let path =
+            std::env::temp_dir().join(format!("space-recorder-report-{}.toml", std::process::id()));
+        std::fs::write(&path, "[modal]\nsize = \"large\"\napi_key = \"sk-12345\"\n").unwrap();
+
+        let report = bug_report(Some(&path));
+        std::fs::remove_file(&path).ok();
+
+        assert!(report.contains("(exists)"));
+        assert!(report.contains("size = \"large\""));
+        assert!(report.contains("api_key = \"<redacted>\""));
+        assert!(!report.contains("sk-12345"));
+    }
+
+    #[test]
+    fn test_redact_secrets_keeps_comments_and_other_keys() {
+        let redacted = redact_secrets("# token = example\ncharset = \"blocks\"\nauth_token=abc");
+        assert_eq!(
+            redacted,
+            "# token = example\ncharset = \"blocks\"\nauth_token= \"<redacted>\""
+        );
+    }
+
+    #[test]
+    fn test_config_init_round_trips_and_respects_force() {
+        let path = std::env::temp_dir()
+            .join(format!("space-recorder-init-{}", std::process::id()))
+            .join("config.toml");
+        let _ = std::fs::remove_file(&path);
+
+        write_default_config(&path, false).expect("should create the file and its directory");
+        let loaded =
+            crate::config::Config::load_from_explicit(&path).expect("generated config should load");
+        assert_eq!(loaded, crate::config::Config::default());
+
+        let err = write_default_config(&path, false).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+        std::fs::write(&path, "# edited").unwrap();
+        write_default_config(&path, true).expect("--force should overwrite");
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), DEFAULT_CONFIG);
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    /// Write `contents` to a per-test temp file and validate it.
+    fn validate_temp(name: &str, contents: &str) -> Result<(), Vec<String>> {
+        let path = std::env::temp_dir().join(format!(
+            "space-recorder-validate-{}-{}.toml",
+            name,
+            std::process::id()
+        ));
+        std::fs::write(&path, contents).unwrap();
+        let result = validate_config_file(&path);
+        let _ = std::fs::remove_file(&path);
+        result
+    }
+
+    #[test]
+    fn test_validate_accepts_default_config() {
+        assert_eq!(validate_temp("default", DEFAULT_CONFIG), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_reports_field_and_value() {
+        let problems = validate_temp(
+            "bad",
+            "[camera]\nresolution = \"8000x4320\"\n\n[modal]\nposition = \"middle\"\nrefresh_rate = 0\n",
+        )
+        .unwrap_err();
+
+        assert_eq!(problems.len(), 3, "{:?}", problems);
+        assert!(problems[0].starts_with("camera.resolution = \"8000x4320\": exceeds"));
+        assert!(problems[1].starts_with("modal.position = \"middle\": "));
+        assert!(problems[2].contains("modal.refresh_rate = 0: 0 is not in 1..=60"));
+    }
+
+    #[test]
+    fn test_validate_reports_hotkey_conflicts() {
+        let problems = validate_temp(
+            "hotkeys",
+            "[hotkeys]\ncycle_size = \"o\"\ncycle_gamma = \"alt+c\"\n",
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            problems,
+            vec![
+                "hotkeys.cycle_gamma = \"alt+c\": same key as hotkeys.toggle_camera",
+                "hotkeys.toggle_color = \"o\": same key as hotkeys.cycle_size",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_reports_unparseable_file() {
+        let problems = validate_temp("syntax", "[modal\n").unwrap_err();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].starts_with("Invalid config file"));
+    }
+
+    #[test]
+    fn test_write_man_page() {
+        let mut out = Vec::new();
+        write_man_page(&mut out).expect("man page should render");
+        let page = String::from_utf8(out).expect("man page should be UTF-8");
+        assert!(page.contains(".TH"), "should have a roff title header");
+        assert!(page.contains("list\\-cameras"), "should list subcommands");
+        assert!(
+            page.contains("Examples:"),
+            "should include after_help examples"
+        );
+    }
 }
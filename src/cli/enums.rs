@@ -1,9 +1,13 @@
-//! CLI enum types for position, size, and character set options.
+//! CLI enum types for position, size, color depth, and character set options.
 
 use clap::ValueEnum;
+use clap::builder::{PossibleValue, TypedValueParser, ValueParserFactory};
+use clap::error::ErrorKind;
+use std::ffi::OsStr;
+use std::str::FromStr;
 
 use crate::ascii;
-use crate::terminal::{ModalPosition, ModalSize};
+use crate::terminal::{self, ModalPosition, ModalSize};
 
 /// Camera modal position on screen.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
@@ -51,14 +55,123 @@ impl From<Size> for ModalSize {
     }
 }
 
+/// Overlay color depth, overriding what the terminal advertises.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ColorDepth {
+    /// 24-bit color
+    #[value(name = "truecolor")]
+    TrueColor,
+    /// The xterm 256-color palette
+    #[value(name = "256")]
+    Indexed256,
+    /// Plain glyphs, no color
+    #[value(name = "mono")]
+    Monochrome,
+}
+
+impl From<ColorDepth> for terminal::ColorDepth {
+    fn from(depth: ColorDepth) -> Self {
+        match depth {
+            ColorDepth::TrueColor => terminal::ColorDepth::TrueColor,
+            ColorDepth::Indexed256 => terminal::ColorDepth::Indexed256,
+            ColorDepth::Monochrome => terminal::ColorDepth::Monochrome,
+        }
+    }
+}
+
 /// ASCII character set for rendering.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+///
+/// Parsed from one of the preset names, or `custom:<ramp>` for a user
+/// density ramp ordered dark to light (e.g. `"custom: .:-=+*#%@"`).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum CharacterSet {
     #[default]
     Standard,
     Blocks,
     Minimal,
     Braille,
+    Extended,
+    Custom(Vec<char>),
+}
+
+/// Prefix that selects a custom ramp in `--charset custom:<ramp>`.
+const CUSTOM_PREFIX: &str = "custom:";
+
+impl FromStr for CharacterSet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(ramp) = s.strip_prefix(CUSTOM_PREFIX) {
+            return ascii::parse_ramp(ramp)
+                .map(CharacterSet::Custom)
+                .map_err(|e| e.to_string());
+        }
+
+        match s {
+            "standard" => Ok(CharacterSet::Standard),
+            "blocks" => Ok(CharacterSet::Blocks),
+            "minimal" => Ok(CharacterSet::Minimal),
+            "braille" => Ok(CharacterSet::Braille),
+            "extended" => Ok(CharacterSet::Extended),
+            _ => Err(format!(
+                "expected standard, blocks, minimal, braille, extended, or {}<ramp>",
+                CUSTOM_PREFIX
+            )),
+        }
+    }
+}
+
+/// clap parser for `CharacterSet`, since presets plus `custom:<ramp>` can't be a `ValueEnum`.
+#[derive(Debug, Clone, Copy)]
+pub struct CharacterSetParser;
+
+impl TypedValueParser for CharacterSetParser {
+    type Value = CharacterSet;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        arg: Option<&clap::Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let value = value
+            .to_str()
+            .ok_or_else(|| clap::Error::new(ErrorKind::InvalidUtf8).with_cmd(cmd))?;
+        let arg = arg
+            .map(|a| a.to_string())
+            .unwrap_or_else(|| "...".to_string());
+
+        value.parse().map_err(|reason| {
+            clap::Error::raw(
+                ErrorKind::InvalidValue,
+                format!("invalid value '{}' for '{}': {}\n", value, arg, reason),
+            )
+            .with_cmd(cmd)
+        })
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(
+            [
+                "standard",
+                "blocks",
+                "minimal",
+                "braille",
+                "extended",
+                "custom:<RAMP>",
+            ]
+            .into_iter()
+            .map(PossibleValue::new),
+        ))
+    }
+}
+
+impl ValueParserFactory for CharacterSet {
+    type Parser = CharacterSetParser;
+
+    fn value_parser() -> Self::Parser {
+        CharacterSetParser
+    }
 }
 
 impl From<CharacterSet> for ascii::CharSet {
@@ -68,6 +181,9 @@ impl From<CharacterSet> for ascii::CharSet {
             CharacterSet::Blocks => ascii::CharSet::Blocks,
             CharacterSet::Minimal => ascii::CharSet::Minimal,
             CharacterSet::Braille => ascii::CharSet::Braille,
+            CharacterSet::Extended => ascii::CharSet::Extended,
+            // Already validated when parsed, so this can't fail
+            CharacterSet::Custom(chars) => ascii::CharSet::Custom(chars.into()),
         }
     }
 }
@@ -122,5 +238,30 @@ mod tests {
             ascii::CharSet::from(CharacterSet::Braille),
             ascii::CharSet::Braille
         );
+        assert_eq!(
+            ascii::CharSet::from(CharacterSet::Extended),
+            ascii::CharSet::Extended
+        );
+        assert_eq!(
+            ascii::CharSet::from(CharacterSet::Custom(vec![' ', 'o', 'O'])).chars(),
+            &[' ', 'o', 'O']
+        );
+    }
+
+    #[test]
+    fn test_charset_from_str() {
+        assert_eq!("blocks".parse(), Ok(CharacterSet::Blocks));
+        assert_eq!(
+            "custom: .:#".parse(),
+            Ok(CharacterSet::Custom(vec![' ', '.', ':', '#']))
+        );
+        assert!("sparkles".parse::<CharacterSet>().is_err());
+    }
+
+    #[test]
+    fn test_charset_custom_rejects_short_ramp() {
+        let err = "custom:#".parse::<CharacterSet>().unwrap_err();
+        assert!(err.contains("at least 2 characters"), "{}", err);
+        assert!("custom:".parse::<CharacterSet>().is_err());
     }
 }
@@ -0,0 +1,104 @@
+//! Process exit codes for distinct failure modes.
+//!
+//! Wrapper scripts can use these to react appropriately, e.g. retry on
+//! device-not-found or prompt for permissions on permission-denied.
+
+use crate::camera::CameraError;
+use crate::pty::PtyError;
+
+/// Exit code reported when the process terminates with an error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    /// Unclassified failure
+    Failure = 1,
+    /// Camera permission missing (grant it in System Settings)
+    PermissionDenied = 2,
+    /// Camera backend unavailable (device query failed)
+    DependencyMissing = 3,
+    /// Requested camera not found, or no cameras at all
+    DeviceNotFound = 4,
+    /// Shell, PTY, or camera stream failed at runtime
+    PipelineFailure = 5,
+    /// Config file could not be read, parsed, or written
+    ConfigError = 6,
+}
+
+impl ExitCode {
+    /// Get the numeric process exit code.
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+
+    /// Terminate the process with this exit code.
+    pub fn exit(self) -> ! {
+        std::process::exit(self.code())
+    }
+}
+
+impl From<&CameraError> for ExitCode {
+    fn from(err: &CameraError) -> Self {
+        match err {
+            CameraError::PermissionDenied => ExitCode::PermissionDenied,
+            CameraError::QueryFailed(_) => ExitCode::DependencyMissing,
+            CameraError::NoDevices
+            | CameraError::DeviceNotFound(_)
+            | CameraError::DeviceNameNotFound(_) => ExitCode::DeviceNotFound,
+            CameraError::OpenFailed(_)
+            | CameraError::UnsupportedResolution(_)
+            | CameraError::StreamFailed(_)
+            | CameraError::AlreadyRunning
+            | CameraError::Disconnected => ExitCode::PipelineFailure,
+        }
+    }
+}
+
+impl From<&PtyError> for ExitCode {
+    fn from(_: &PtyError) -> Self {
+        ExitCode::PipelineFailure
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_code_values() {
+        assert_eq!(ExitCode::Failure.code(), 1);
+        assert_eq!(ExitCode::PermissionDenied.code(), 2);
+        assert_eq!(ExitCode::DependencyMissing.code(), 3);
+        assert_eq!(ExitCode::DeviceNotFound.code(), 4);
+        assert_eq!(ExitCode::PipelineFailure.code(), 5);
+        assert_eq!(ExitCode::ConfigError.code(), 6);
+    }
+
+    #[test]
+    fn test_exit_code_from_camera_error() {
+        assert_eq!(
+            ExitCode::from(&CameraError::PermissionDenied),
+            ExitCode::PermissionDenied
+        );
+        assert_eq!(
+            ExitCode::from(&CameraError::QueryFailed("test".to_string())),
+            ExitCode::DependencyMissing
+        );
+        assert_eq!(
+            ExitCode::from(&CameraError::DeviceNotFound(3)),
+            ExitCode::DeviceNotFound
+        );
+        assert_eq!(
+            ExitCode::from(&CameraError::NoDevices),
+            ExitCode::DeviceNotFound
+        );
+        assert_eq!(
+            ExitCode::from(&CameraError::StreamFailed("test".to_string())),
+            ExitCode::PipelineFailure
+        );
+    }
+
+    #[test]
+    fn test_exit_code_from_pty_error() {
+        let err = PtyError::IoError(std::io::Error::other("test"));
+        assert_eq!(ExitCode::from(&err), ExitCode::PipelineFailure);
+    }
+}
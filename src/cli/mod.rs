@@ -5,7 +5,12 @@
 mod args;
 mod commands;
 mod enums;
+mod exit_code;
 
-pub use args::{Args, Command, ConfigAction};
-pub use commands::{handle_config_action, list_cameras};
-pub use enums::{CharacterSet, Position, Size};
+pub use args::{Args, Command, ConfigAction, TRANSPARENCY_STEPS};
+pub use commands::{
+    ascii_snapshot, export_man_page, handle_config_action, list_cameras, list_presets,
+    print_version, report_bug, write_completions,
+};
+pub use enums::{CharacterSet, ColorDepth, Position, Size};
+pub use exit_code::ExitCode;
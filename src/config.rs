@@ -1,6 +1,173 @@
-//! Configuration path utilities for space-recorder.
+//! Configuration file loading and path utilities for space-recorder.
 
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Duration;
+
+/// How often `Config::watch` checks the file for changes.
+pub const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Keys that only take effect on the next start (the camera, shell, and
+/// screen layout are set up once).
+pub const RESTART_KEYS: &[&str] = &[
+    "shell.command",
+    "shell.args",
+    "pty.env",
+    "pty.refresh_ms",
+    "camera.device",
+    "camera.resolution",
+    "modal.border",
+    "modal.refresh_rate",
+    "ui.status_bar",
+    "hotkeys",
+];
+
+/// Fully commented config file written by `config init`.
+///
+/// Every value matches `Config::default()`, so loading this file unchanged
+/// gives the same settings as having no config file at all.
+pub const DEFAULT_CONFIG: &str = r#"# space-recorder configuration
+
+[shell]
+# Shell to spawn (default: $SHELL)
+# command = "/bin/zsh"
+# Extra arguments, e.g. ["-l"] for a login shell
+args = []
+
+[pty]
+# Shell output is batched and drawn at most this often (milliseconds)
+refresh_ms = 16
+
+[pty.env]
+# Extra environment variables for the shell, on top of the inherited ones
+# (SPACE_RECORDER=1 is always set)
+# TERM = "xterm-256color"
+# PROMPT_HINT = "live"
+
+[camera]
+# Camera device index
+device = 0
+# Mirror horizontally (selfie mode)
+mirror = false
+# Capture resolution (lower = faster)
+resolution = "640x480"
+
+[modal]
+# Start with camera visible
+visible = true
+# Position: top-left, top-right, bottom-left, bottom-right, center
+position = "bottom-right"
+# Size: small, medium, large, xlarge, huge
+size = "small"
+# Show border around modal
+border = false
+# Overlay redraw rate in FPS (independent of camera capture rate)
+refresh_rate = 15
+# Transparency points per Alt+T press, 1-50 (Alt+Shift+T steps 5x)
+transparency_step = 10
+
+[ascii]
+# Character set: standard, blocks, minimal, braille, extended
+charset = "blocks"
+# Invert brightness (for light themes)
+invert = false
+
+[ui]
+# Show status bar
+status_bar = true
+
+[hotkeys]
+# Key bindings: a bare key means Alt+key, or spell out the combo ("ctrl+shift+o")
+toggle_camera = "c"
+cycle_position = "p"
+cycle_size = "s"
+cycle_charset = "a"
+cycle_transparency = "t"
+toggle_color = "o"
+toggle_invert = "i"
+# Unbound by default, as shells use these keys (e.g. Alt+M, Alt+G in zsh)
+# toggle_mirror = "m"
+# cycle_gamma = "g"
+# toggle_perf = "f"
+# cycle_dither = "d"
+# toggle_auto_levels = "l"
+# toggle_sharpen = "h"
+# Only active with --quit-hotkey
+quit = "q"
+"#;
+
+/// Stores an environment variable's value in its config key.
+type EnvSetter = fn(&mut Config, &str) -> Result<(), String>;
+
+/// Environment variable overrides: variable, config key it sets, and setter.
+///
+/// Values are parsed to the key's type here; ranges and names are checked
+/// later with the file's values, by the same code as the CLI flags.
+const ENV_OVERRIDES: &[(&str, &str, EnvSetter)] = &[
+    ("SPACE_RECORDER_SHELL", "shell.command", |c, v| {
+        c.shell.command = Some(v.to_string());
+        Ok(())
+    }),
+    ("SPACE_RECORDER_CAMERA", "camera.device", |c, v| {
+        c.camera.device = parse_number(v)?;
+        Ok(())
+    }),
+    ("SPACE_RECORDER_MIRROR", "camera.mirror", |c, v| {
+        c.camera.mirror = parse_bool(v)?;
+        Ok(())
+    }),
+    ("SPACE_RECORDER_RESOLUTION", "camera.resolution", |c, v| {
+        c.camera.resolution = v.to_string();
+        Ok(())
+    }),
+    ("SPACE_RECORDER_POSITION", "modal.position", |c, v| {
+        c.modal.position = v.to_string();
+        Ok(())
+    }),
+    ("SPACE_RECORDER_SIZE", "modal.size", |c, v| {
+        c.modal.size = v.to_string();
+        Ok(())
+    }),
+    (
+        "SPACE_RECORDER_REFRESH_RATE",
+        "modal.refresh_rate",
+        |c, v| {
+            c.modal.refresh_rate = parse_number(v)?;
+            Ok(())
+        },
+    ),
+    ("SPACE_RECORDER_CHARSET", "ascii.charset", |c, v| {
+        c.ascii.charset = v.to_string();
+        Ok(())
+    }),
+    ("SPACE_RECORDER_INVERT", "ascii.invert", |c, v| {
+        c.ascii.invert = parse_bool(v)?;
+        Ok(())
+    }),
+    ("SPACE_RECORDER_STATUS_BAR", "ui.status_bar", |c, v| {
+        c.ui.status_bar = parse_bool(v)?;
+        Ok(())
+    }),
+];
+
+fn parse_number(value: &str) -> Result<u32, String> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("'{}' is not a whole number", value))
+}
+
+fn parse_bool(value: &str) -> Result<bool, String> {
+    match value.trim().to_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Ok(true),
+        "0" | "false" | "no" | "off" => Ok(false),
+        _ => Err(format!("'{}' is not true/false", value)),
+    }
+}
 
 /// Get the default config file path.
 ///
@@ -16,3 +183,677 @@ pub fn default_path() -> PathBuf {
             PathBuf::from(home).join(".config/space-recorder/config.toml")
         })
 }
+
+/// Settings read from `config.toml`. Missing sections and keys keep their defaults.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Config {
+    pub shell: ShellConfig,
+    pub pty: PtyConfig,
+    pub camera: CameraConfig,
+    pub modal: ModalConfig,
+    pub ascii: AsciiConfig,
+    pub ui: UiConfig,
+    pub hotkeys: HotkeyConfig,
+    /// `[profiles.NAME]` tables, each overriding any of the sections above
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, toml::Table>,
+}
+
+/// `[shell]` section.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ShellConfig {
+    /// Shell to spawn instead of `$SHELL`
+    pub command: Option<String>,
+    /// Arguments passed to the shell
+    pub args: Vec<String>,
+}
+
+/// `[pty]` section.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PtyConfig {
+    /// Milliseconds between writes of batched shell output
+    pub refresh_ms: u32,
+    /// Variables added to (or overriding) the shell's inherited environment
+    pub env: HashMap<String, String>,
+}
+
+impl Default for PtyConfig {
+    fn default() -> Self {
+        Self {
+            refresh_ms: 16,
+            env: HashMap::new(),
+        }
+    }
+}
+
+/// `[camera]` section.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CameraConfig {
+    /// Device index from `list-cameras`
+    pub device: u32,
+    /// Mirror horizontally (selfie mode)
+    pub mirror: bool,
+    /// Capture resolution as `WIDTHxHEIGHT`
+    pub resolution: String,
+}
+
+impl Default for CameraConfig {
+    fn default() -> Self {
+        Self {
+            device: 0,
+            mirror: false,
+            resolution: "640x480".to_string(),
+        }
+    }
+}
+
+/// `[modal]` section.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ModalConfig {
+    /// Start with the camera visible
+    pub visible: bool,
+    /// Position preset name (e.g. `bottom-right`)
+    pub position: String,
+    /// Size preset name (e.g. `small`)
+    pub size: String,
+    /// Draw a border around the modal
+    pub border: bool,
+    /// Overlay redraw rate in FPS
+    pub refresh_rate: u32,
+    /// Transparency points per hotkey press
+    pub transparency_step: u8,
+}
+
+impl Default for ModalConfig {
+    fn default() -> Self {
+        Self {
+            visible: true,
+            position: "bottom-right".to_string(),
+            size: "small".to_string(),
+            border: false,
+            refresh_rate: 15,
+            transparency_step: 10,
+        }
+    }
+}
+
+/// `[ascii]` section.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AsciiConfig {
+    /// Character set name, or `custom:<ramp>`
+    pub charset: String,
+    /// Invert brightness (for light themes)
+    pub invert: bool,
+}
+
+impl Default for AsciiConfig {
+    fn default() -> Self {
+        Self {
+            charset: "blocks".to_string(),
+            invert: false,
+        }
+    }
+}
+
+/// `[ui]` section.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct UiConfig {
+    /// Show the status bar
+    pub status_bar: bool,
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self { status_bar: true }
+    }
+}
+
+/// `[hotkeys]` section: the key combo for each action, parsed by `input::KeyBinding`.
+///
+/// Actions without a default binding stay unbound (their keys go to the
+/// shell) unless set here.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct HotkeyConfig {
+    pub toggle_camera: String,
+    pub cycle_position: String,
+    pub cycle_size: String,
+    pub cycle_charset: String,
+    pub cycle_transparency: String,
+    pub toggle_color: String,
+    pub toggle_invert: String,
+    pub toggle_mirror: Option<String>,
+    pub cycle_gamma: Option<String>,
+    pub toggle_perf: Option<String>,
+    pub cycle_dither: Option<String>,
+    pub toggle_auto_levels: Option<String>,
+    pub toggle_sharpen: Option<String>,
+    pub quit: String,
+}
+
+impl Default for HotkeyConfig {
+    fn default() -> Self {
+        Self {
+            toggle_camera: "c".to_string(),
+            cycle_position: "p".to_string(),
+            cycle_size: "s".to_string(),
+            cycle_charset: "a".to_string(),
+            cycle_transparency: "t".to_string(),
+            toggle_color: "o".to_string(),
+            toggle_invert: "i".to_string(),
+            toggle_mirror: None,
+            cycle_gamma: None,
+            toggle_perf: None,
+            cycle_dither: None,
+            toggle_auto_levels: None,
+            toggle_sharpen: None,
+            quit: "q".to_string(),
+        }
+    }
+}
+
+/// Errors from reading a config file.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The file couldn't be read
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// The file isn't valid TOML or has a value of the wrong type
+    Parse { path: PathBuf, message: String },
+    /// `--profile` named a profile the config doesn't define
+    UnknownProfile {
+        name: String,
+        available: Vec<String>,
+    },
+    /// A value parsed but isn't usable (e.g. an unknown position name)
+    Invalid { field: String, message: String },
+    /// Strict loading found a key the app doesn't know (usually a typo)
+    UnknownKey {
+        path: PathBuf,
+        /// Dotted table name, or `None` at the top level
+        section: Option<String>,
+        key: String,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io { path, source } => {
+                write!(
+                    f,
+                    "Failed to read config file {}: {}",
+                    path.display(),
+                    source
+                )
+            }
+            ConfigError::Parse { path, message } => {
+                write!(f, "Invalid config file {}: {}", path.display(), message)
+            }
+            ConfigError::UnknownProfile { name, available } if available.is_empty() => {
+                write!(
+                    f,
+                    "No profile {:?}: the config defines no [profiles.*] tables",
+                    name
+                )
+            }
+            ConfigError::UnknownProfile { name, available } => {
+                write!(
+                    f,
+                    "No profile {:?} (available: {})",
+                    name,
+                    available.join(", ")
+                )
+            }
+            ConfigError::Invalid { field, message } => write!(f, "Invalid {}: {}", field, message),
+            ConfigError::UnknownKey { path, section, key } => {
+                write!(f, "Unknown key {:?} ", key)?;
+                if let Some(section) = section {
+                    write!(f, "in [{}] ", section)?;
+                }
+                write!(f, "of config file {}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    /// Load `path`, or the default path when `None`.
+    ///
+    /// A missing file is not an error and yields `Config::default()`.
+    pub fn load(path: Option<&Path>) -> Result<Self, ConfigError> {
+        let path = path.map(Path::to_path_buf).unwrap_or_else(default_path);
+        if path.exists() {
+            Self::load_from_explicit(&path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Load a config file that must exist (e.g. one named with `--config`).
+    pub fn load_from_explicit(path: &Path) -> Result<Self, ConfigError> {
+        let contents = read(path)?;
+        toml::from_str(&contents).map_err(|e| parse_error(path, e))
+    }
+
+    /// Like `load_from_explicit`, but a key the app doesn't know is an error
+    /// instead of being ignored. Profiles are checked against the same keys.
+    pub fn load_strict(path: &Path) -> Result<Self, ConfigError> {
+        let contents = read(path)?;
+        let table: toml::Table = toml::from_str(&contents).map_err(|e| parse_error(path, e))?;
+        if let Some((section, key)) = find_unknown_key(&table) {
+            return Err(ConfigError::UnknownKey {
+                path: path.to_path_buf(),
+                section,
+                key,
+            });
+        }
+        toml::from_str(&contents).map_err(|e| parse_error(path, e))
+    }
+
+    /// Watch `path`, sending a freshly parsed config every time its contents change.
+    ///
+    /// A polling thread compares the file every `WATCH_INTERVAL`. Edits that
+    /// don't parse are skipped, so a half-saved file never reaches the app;
+    /// the next valid save does. Unknown keys are ignored as by `Config::load`,
+    /// even under `--strict-config`. Once the receiver is dropped, the thread
+    /// exits at the next change it sees.
+    pub fn watch(path: PathBuf) -> Receiver<Config> {
+        Self::watch_every(path, WATCH_INTERVAL)
+    }
+
+    /// `watch` polling every `interval`.
+    pub fn watch_every(path: PathBuf, interval: Duration) -> Receiver<Config> {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let mut last = std::fs::read_to_string(&path).ok();
+            loop {
+                thread::sleep(interval);
+                let contents = std::fs::read_to_string(&path).ok();
+                if contents == last {
+                    continue;
+                }
+                last = contents;
+                let Some(contents) = &last else { continue };
+                if let Ok(config) = toml::from_str::<Config>(contents)
+                    && tx.send(config).is_err()
+                {
+                    return;
+                }
+            }
+        });
+        rx
+    }
+
+    /// The `RESTART_KEYS` whose values differ between `self` and `other`.
+    pub fn restart_required(&self, other: &Config) -> Vec<&'static str> {
+        let changed = [
+            self.shell.command != other.shell.command,
+            self.shell.args != other.shell.args,
+            self.pty.env != other.pty.env,
+            self.pty.refresh_ms != other.pty.refresh_ms,
+            self.camera.device != other.camera.device,
+            self.camera.resolution != other.camera.resolution,
+            self.modal.border != other.modal.border,
+            self.modal.refresh_rate != other.modal.refresh_rate,
+            self.ui.status_bar != other.ui.status_bar,
+            self.hotkeys != other.hotkeys,
+        ];
+        RESTART_KEYS
+            .iter()
+            .zip(changed)
+            .filter_map(|(key, changed)| changed.then_some(*key))
+            .collect()
+    }
+
+    /// Override keys from `SPACE_RECORDER_*` environment variables.
+    pub fn apply_env(&mut self) -> Result<(), ConfigError> {
+        self.apply_env_from(|name| std::env::var(name).ok())
+    }
+
+    /// `apply_env` reading variables through `lookup`.
+    ///
+    /// A value that doesn't parse is an error naming the variable, rather
+    /// than being ignored.
+    pub fn apply_env_from(
+        &mut self,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<(), ConfigError> {
+        for (var, key, set) in ENV_OVERRIDES {
+            if let Some(value) = lookup(var) {
+                set(self, &value).map_err(|message| ConfigError::Invalid {
+                    field: format!("{} (for {})", var, key),
+                    message,
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// This config with the `[profiles.NAME]` table laid over it.
+    ///
+    /// Keys the profile sets replace the base values; everything else is
+    /// kept, so a profile only needs the settings that differ.
+    pub fn with_profile(&self, name: &str) -> Result<Self, ConfigError> {
+        let overrides = self
+            .profiles
+            .get(name)
+            .ok_or_else(|| ConfigError::UnknownProfile {
+                name: name.to_string(),
+                available: self.profiles.keys().cloned().collect(),
+            })?;
+        let invalid = |e: &dyn fmt::Display| ConfigError::Invalid {
+            field: format!("profiles.{}", name),
+            message: e.to_string().trim_end().to_string(),
+        };
+
+        let mut table = toml::Table::try_from(self).map_err(|e| invalid(&e))?;
+        merge_tables(&mut table, overrides);
+        table.try_into().map_err(|e| invalid(&e))
+    }
+}
+
+/// Recursively copy `overrides` into `base`, merging nested tables.
+fn merge_tables(base: &mut toml::Table, overrides: &toml::Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(overrides)) => {
+                merge_tables(base, overrides)
+            }
+            _ => {
+                base.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+fn read(path: &Path) -> Result<String, ConfigError> {
+    std::fs::read_to_string(path).map_err(|source| ConfigError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn parse_error(path: &Path, e: toml::de::Error) -> ConfigError {
+    ConfigError::Parse {
+        path: path.to_path_buf(),
+        message: e.to_string().trim_end().to_string(),
+    }
+}
+
+/// The first key in `table` that `Config` doesn't define, with its section.
+fn find_unknown_key(table: &toml::Table) -> Option<(Option<String>, String)> {
+    // Serializing a full default yields every key; optional ones need a value
+    let mut template = Config::default();
+    template.shell.command = Some(String::new());
+    template.hotkeys.toggle_mirror = Some(String::new());
+    template.hotkeys.cycle_gamma = Some(String::new());
+    template.hotkeys.toggle_perf = Some(String::new());
+    template.hotkeys.cycle_dither = Some(String::new());
+    template.hotkeys.toggle_auto_levels = Some(String::new());
+    template.hotkeys.toggle_sharpen = Some(String::new());
+    let mut known = toml::Table::try_from(template).ok()?;
+    known.remove("profiles");
+
+    let mut profiles = None;
+    for (key, value) in table {
+        if key == "profiles" {
+            profiles = value.as_table();
+            continue;
+        }
+        if let Some(unknown) = unknown_in(key, value, &known, None) {
+            return Some(unknown);
+        }
+    }
+    for (name, profile) in profiles.into_iter().flatten() {
+        let section = format!("profiles.{}", name);
+        let Some(profile) = profile.as_table() else {
+            continue;
+        };
+        for (key, value) in profile {
+            if let Some(unknown) = unknown_in(key, value, &known, Some(&section)) {
+                return Some(unknown);
+            }
+        }
+    }
+    None
+}
+
+fn unknown_in(
+    key: &str,
+    value: &toml::Value,
+    known: &toml::Table,
+    section: Option<&str>,
+) -> Option<(Option<String>, String)> {
+    let Some(expected) = known.get(key) else {
+        return Some((section.map(str::to_string), key.to_string()));
+    };
+    let (toml::Value::Table(table), toml::Value::Table(expected)) = (value, expected) else {
+        return None;
+    };
+    // Map fields like `pty.env` serialize empty and take any key
+    if expected.is_empty() {
+        return None;
+    }
+    let nested = match section {
+        Some(section) => format!("{}.{}", section, key),
+        None => key.to_string(),
+    };
+    table
+        .iter()
+        .find_map(|(key, value)| unknown_in(key, value, expected, Some(&nested)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_text_matches_defaults() {
+        let parsed: Config = toml::from_str(DEFAULT_CONFIG).unwrap();
+        assert_eq!(parsed, Config::default());
+    }
+
+    #[test]
+    fn test_partial_file_keeps_other_defaults() {
+        let parsed: Config = toml::from_str("[modal]\nsize = \"large\"\n").unwrap();
+        assert_eq!(parsed.modal.size, "large");
+        assert_eq!(parsed.modal.position, "bottom-right");
+        assert_eq!(parsed.camera, CameraConfig::default());
+    }
+
+    const PROFILES: &str = r#"
+[modal]
+position = "top-left"
+size = "large"
+
+[profiles.pair]
+modal = { size = "small" }
+ascii = { invert = true }
+
+[profiles.demo]
+modal = { position = "center" }
+"#;
+
+    #[test]
+    fn test_profile_overrides_only_its_keys() {
+        let base: Config = toml::from_str(PROFILES).unwrap();
+        let pair = base.with_profile("pair").unwrap();
+
+        assert_eq!(pair.modal.size, "small");
+        assert!(pair.ascii.invert);
+        // Unset in the profile: base value, then built-in default
+        assert_eq!(pair.modal.position, "top-left");
+        assert_eq!(pair.modal.refresh_rate, 15);
+        assert_eq!(pair.ascii.charset, "blocks");
+    }
+
+    #[test]
+    fn test_unknown_profile_lists_available() {
+        let base: Config = toml::from_str(PROFILES).unwrap();
+        let err = base.with_profile("solo").unwrap_err();
+        assert!(matches!(err, ConfigError::UnknownProfile { .. }));
+        assert_eq!(
+            err.to_string(),
+            "No profile \"solo\" (available: demo, pair)"
+        );
+
+        let err = Config::default().with_profile("solo").unwrap_err();
+        assert!(err.to_string().contains("defines no [profiles.*]"));
+    }
+
+    #[test]
+    fn test_profile_with_wrong_type_is_invalid() {
+        let base: Config =
+            toml::from_str("[profiles.bad]\nmodal = { refresh_rate = \"fast\" }\n").unwrap();
+        let err = base.with_profile("bad").unwrap_err();
+        assert!(
+            err.to_string().starts_with("Invalid profiles.bad:"),
+            "{}",
+            err
+        );
+    }
+
+    fn env(vars: &[(&'static str, &'static str)]) -> impl Fn(&str) -> Option<String> {
+        let vars = vars.to_vec();
+        move |name| {
+            vars.iter()
+                .find(|(var, _)| *var == name)
+                .map(|(_, value)| value.to_string())
+        }
+    }
+
+    #[test]
+    fn test_env_overrides_file_values() {
+        let mut config: Config =
+            toml::from_str("[modal]\nrefresh_rate = 30\nsize = \"large\"\n").unwrap();
+        config
+            .apply_env_from(env(&[
+                ("SPACE_RECORDER_REFRESH_RATE", "10"),
+                ("SPACE_RECORDER_MIRROR", "yes"),
+            ]))
+            .unwrap();
+
+        assert_eq!(config.modal.refresh_rate, 10);
+        assert!(config.camera.mirror);
+        // No variable set: the file value stays
+        assert_eq!(config.modal.size, "large");
+    }
+
+    #[test]
+    fn test_env_unparseable_value_errors() {
+        let mut config = Config::default();
+        let err = config
+            .apply_env_from(env(&[("SPACE_RECORDER_REFRESH_RATE", "fast")]))
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Invalid SPACE_RECORDER_REFRESH_RATE (for modal.refresh_rate): 'fast' is not a whole number"
+        );
+        assert_eq!(config.modal.refresh_rate, 15);
+    }
+
+    #[test]
+    fn test_watch_sends_reparsed_config_on_change() {
+        let path =
+            std::env::temp_dir().join(format!("space-recorder-watch-{}.toml", std::process::id()));
+        std::fs::write(&path, "[modal]\nsize = \"small\"\n").unwrap();
+        let updates = Config::watch_every(path.clone(), Duration::from_millis(10));
+
+        // Let the watcher read the original contents first
+        thread::sleep(Duration::from_millis(50));
+        std::fs::write(&path, "[modal]\nsize = \"large\"\n").unwrap();
+        let config = updates.recv_timeout(Duration::from_secs(2));
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(
+            config.expect("watcher should send the edit").modal.size,
+            "large"
+        );
+    }
+
+    #[test]
+    fn test_restart_required_lists_changed_keys() {
+        let base = Config::default();
+        let mut edited = base.clone();
+        edited.modal.size = "large".to_string();
+        assert!(base.restart_required(&edited).is_empty());
+
+        edited.camera.resolution = "1280x720".to_string();
+        edited.shell.command = Some("/bin/bash".to_string());
+        assert_eq!(
+            base.restart_required(&edited),
+            vec!["shell.command", "camera.resolution"]
+        );
+    }
+
+    #[test]
+    fn test_strict_load_names_misspelled_key() {
+        let path =
+            std::env::temp_dir().join(format!("space-recorder-strict-{}.toml", std::process::id()));
+        std::fs::write(&path, "[modal]\nsize = \"large\"\npositon = \"center\"\n").unwrap();
+        let strict = Config::load_strict(&path);
+        let lenient = Config::load_from_explicit(&path);
+        let _ = std::fs::remove_file(&path);
+
+        let err = strict.unwrap_err();
+        assert!(
+            matches!(&err, ConfigError::UnknownKey { section: Some(s), key, .. }
+                if s == "modal" && key == "positon"),
+            "{:?}",
+            err
+        );
+        assert!(
+            err.to_string()
+                .starts_with("Unknown key \"positon\" in [modal]")
+        );
+        assert_eq!(lenient.unwrap().modal.size, "large");
+    }
+
+    #[test]
+    fn test_unknown_keys_in_profiles_and_top_level() {
+        let table = |text: &str| toml::from_str::<toml::Table>(text).unwrap();
+
+        assert_eq!(find_unknown_key(&table(DEFAULT_CONFIG)), None);
+        assert_eq!(
+            find_unknown_key(&table("[pty.env]\nANY_NAME = \"1\"\n")),
+            None
+        );
+        assert_eq!(find_unknown_key(&table(PROFILES)), None);
+        assert_eq!(
+            find_unknown_key(&table("[shell]\ncommand = \"zsh\"\n[screen]\nfps = 30\n")),
+            Some((None, "screen".to_string()))
+        );
+        assert_eq!(
+            find_unknown_key(&table(
+                "[profiles.demo]\nascii = { charest = \"braille\" }\n"
+            )),
+            Some((
+                Some("profiles.demo.ascii".to_string()),
+                "charest".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        let missing = std::env::temp_dir().join("space-recorder-no-such-config.toml");
+        assert_eq!(Config::load(Some(&missing)).unwrap(), Config::default());
+        assert!(matches!(
+            Config::load_from_explicit(&missing),
+            Err(ConfigError::Io { .. })
+        ));
+    }
+}
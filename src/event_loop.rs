@@ -3,55 +3,317 @@
 //! This module separates the main event loop logic from initialization,
 //! making the code more testable and maintainable.
 
-use crossterm::event::{Event, EventStream};
+use clap::ValueEnum;
+use crossterm::event::{Event, EventStream, KeyCode, KeyEvent, KeyModifiers};
 use futures::StreamExt;
+use portable_pty::ExitStatus;
+use ratatui::layout::Rect;
 use std::io::Write;
+use std::str::FromStr;
+use std::sync::mpsc::Receiver;
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 
-use crate::ascii;
+use crate::ascii::AsciiPipeline;
 use crate::camera::CameraCapture;
-use crate::input::{KeyAction, handle_key_event};
-use crate::pty::{PtyHostSplit, PtySize};
-use crate::renderer::{clear_modal_area, render_camera_overlay};
-use crate::terminal::{AsciiFrame, CameraModal, CellColor, StatusBar};
+use crate::cli::{CharacterSet, Position, Size, TRANSPARENCY_STEPS};
+use crate::config::{Config, ConfigError};
+use crate::input::{
+    Hotkeys, KeyAction, MouseDrag, handle_key_event, handle_mouse_event, handle_scroll_key,
+};
+use crate::pty::{PtyHostSplit, PtySize, SessionRecorder};
+use crate::renderer::{
+    DiffRenderer, clear_modal_area, release_status_row, render_scrollback, render_status_line,
+    reserve_status_row,
+};
+use crate::terminal::{CameraModal, PtyBuffer, StatusBar};
+
+/// Live config file updates for the running session.
+///
+/// Only keys that changed in the file are applied, so a setting given on the
+/// command line holds until the user edits that key.
+pub struct ConfigReload {
+    /// Configs parsed by `Config::watch`.
+    pub updates: Receiver<Config>,
+    /// `--profile` to apply on top of each reloaded file.
+    pub profile: Option<String>,
+    /// The config the session is running with.
+    pub current: Config,
+}
+
+impl ConfigReload {
+    /// Apply the hot-reloadable keys that changed in `file` to `modal`.
+    ///
+    /// Returns the changed keys that only take effect after a restart.
+    pub fn apply(
+        &mut self,
+        file: Config,
+        modal: &mut CameraModal,
+    ) -> Result<Vec<&'static str>, ConfigError> {
+        let mut config = match &self.profile {
+            Some(name) => file.with_profile(name)?,
+            None => file,
+        };
+        config.apply_env()?;
+        let invalid = |field: &str, message: String| ConfigError::Invalid {
+            field: field.to_string(),
+            message,
+        };
+
+        // Parse everything before touching the modal, so a bad edit applies nothing
+        let old = &self.current;
+        let position = (config.modal.position != old.modal.position)
+            .then(|| Position::from_str(&config.modal.position, true))
+            .transpose()
+            .map_err(|e| invalid("modal.position", e))?;
+        let size = (config.modal.size != old.modal.size)
+            .then(|| Size::from_str(&config.modal.size, true))
+            .transpose()
+            .map_err(|e| invalid("modal.size", e))?;
+        let charset = (config.ascii.charset != old.ascii.charset)
+            .then(|| CharacterSet::from_str(&config.ascii.charset))
+            .transpose()
+            .map_err(|e| invalid("ascii.charset", e))?;
+        let step = config.modal.transparency_step;
+        if step != old.modal.transparency_step && !TRANSPARENCY_STEPS.contains(&step.into()) {
+            let (start, end) = (TRANSPARENCY_STEPS.start(), TRANSPARENCY_STEPS.end());
+            let message = format!("{} is not in {}..={}", step, start, end);
+            return Err(invalid("modal.transparency_step", message));
+        }
+
+        if let Some(position) = position {
+            modal.position = position.into();
+        }
+        if let Some(size) = size {
+            modal.size = size.into();
+        }
+        if config.modal.visible != old.modal.visible {
+            modal.visible = config.modal.visible;
+        }
+        if step != old.modal.transparency_step {
+            modal.transparency_step = step;
+        }
+        if let Some(charset) = charset {
+            modal.set_charset(charset.into());
+        }
+        if config.ascii.invert != old.ascii.invert {
+            modal.invert = config.ascii.invert;
+        }
+        if config.camera.mirror != old.camera.mirror {
+            modal.mirror = config.camera.mirror;
+        }
+
+        let restart = old.restart_required(&config);
+        self.current = config;
+        Ok(restart)
+    }
+
+    /// Apply the newest pending update, if any, returning a notice to show.
+    fn poll(&mut self, modal: &mut CameraModal) -> Option<String> {
+        let latest = self.updates.try_iter().last()?;
+        Some(match self.apply(latest, modal) {
+            Ok(restart) if restart.is_empty() => "config reloaded".to_string(),
+            Ok(restart) => format!("restart required: {}", restart.join(", ")),
+            Err(e) => format!("config: {}", e),
+        })
+    }
+}
+
+/// `EventLoopConfig::refresh_interval` by default (about 60 FPS).
+pub const DEFAULT_PTY_REFRESH_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Event loop tuning.
+#[derive(Debug, Clone, Copy)]
+pub struct EventLoopConfig {
+    /// Shell output is batched and written to the terminal at most this
+    /// often, so a chatty build doesn't repaint on every tiny chunk
+    pub refresh_interval: Duration,
+}
+
+impl Default for EventLoopConfig {
+    fn default() -> Self {
+        Self {
+            refresh_interval: DEFAULT_PTY_REFRESH_INTERVAL,
+        }
+    }
+}
+
+/// Output held while scrolled back before the view snaps to the bottom.
+const MAX_HELD_OUTPUT: usize = 1 << 20;
+
+/// Shell output kept for scrolling back with Shift+PageUp.
+///
+/// The scrolled window is drawn on the alternate screen, so returning to the
+/// bottom brings back the shell's screen untouched. Output that arrives in
+/// the meantime goes into the scrollback and is held for the shell's screen.
+struct Scrollback {
+    buffer: PtyBuffer,
+    /// Output to write once the view is back at the bottom
+    held: Vec<u8>,
+    /// Whether the scrolled view is on screen
+    active: bool,
+}
+
+impl Scrollback {
+    fn new() -> Self {
+        Self {
+            buffer: PtyBuffer::new(),
+            held: Vec::new(),
+            active: false,
+        }
+    }
+
+    /// Whether `key` scrolls the view rather than going to the shell.
+    ///
+    /// Only Shift+PageUp starts scrolling back, so the shell keeps its other
+    /// Shift chords; once scrolled, Shift+PageDown/Up/Down/Home/End move too.
+    fn wants_key(&self, key: &KeyEvent) -> bool {
+        if key.modifiers != KeyModifiers::SHIFT {
+            return false;
+        }
+        match key.code {
+            KeyCode::PageUp => true,
+            KeyCode::PageDown | KeyCode::Up | KeyCode::Down | KeyCode::Home | KeyCode::End => {
+                self.active
+            }
+            _ => false,
+        }
+    }
+
+    /// Scroll by `key` (see `wants_key`) with `rows` rows of view.
+    fn handle_key(
+        &mut self,
+        out: &mut impl Write,
+        key: KeyEvent,
+        rows: u16,
+    ) -> std::io::Result<()> {
+        handle_scroll_key(key, &mut self.buffer, rows);
+        self.redraw(out, rows)
+    }
+
+    /// Draw the view at the current scroll offset, or leave it at the bottom.
+    fn redraw(&mut self, out: &mut impl Write, rows: u16) -> std::io::Result<()> {
+        // Never scroll past the oldest full page
+        let oldest = self.buffer.line_count().saturating_sub(rows as usize);
+        if usize::from(self.buffer.scroll()) > oldest {
+            self.buffer
+                .set_scroll(oldest.try_into().unwrap_or(u16::MAX));
+        }
+        if !self.buffer.is_scrolled() {
+            return self.leave(out);
+        }
+        if !self.active {
+            out.write_all(b"\x1b[?1049h")?;
+            self.active = true;
+        }
+        out.write_all(render_scrollback(&self.buffer, rows).as_bytes())?;
+        out.flush()
+    }
+
+    /// Back to the shell's screen, catching up on the output held meanwhile.
+    fn leave(&mut self, out: &mut impl Write) -> std::io::Result<()> {
+        self.buffer.scroll_to_bottom();
+        if !self.active {
+            return Ok(());
+        }
+        self.active = false;
+        out.write_all(b"\x1b[?1049l\x1b[?25h")?;
+        out.write_all(&self.held)?;
+        self.held.clear();
+        out.flush()
+    }
+
+    /// Write a batch of output to the terminal, or hold it while scrolled back.
+    fn write(&mut self, out: &mut impl Write, batch: &[u8]) -> std::io::Result<()> {
+        if !self.active {
+            out.write_all(batch)?;
+            return out.flush();
+        }
+        self.held.extend_from_slice(batch);
+        if self.held.len() > MAX_HELD_OUTPUT {
+            self.leave(out)?;
+        }
+        Ok(())
+    }
+}
+
+/// How a session in the event loop ended.
+#[derive(Debug)]
+pub enum SessionEnd {
+    /// The shell exited on its own with this status
+    ShellExited(ExitStatus),
+    /// The quit hotkey was pressed (the shell was killed)
+    Quit,
+    /// The PTY output closed while the shell kept running, e.g. a read error
+    /// (reported by the reader thread)
+    OutputClosed,
+}
 
 /// Async main event loop using tokio::select! for concurrent handling.
 ///
 /// This loop handles three concurrent concerns:
 /// 1. Terminal events (keyboard input, resize) via crossterm EventStream
-/// 2. PTY output via tokio channel from the reader thread
-/// 3. Camera frame capture and ASCII rendering (~15 FPS)
+/// 2. PTY output via tokio channel from the reader thread, batched in a
+///    `PtyBuffer` and written every `config.refresh_interval`; the buffer
+///    doubles as scrollback (Shift+PageUp)
+/// 3. Camera frame capture and ASCII rendering (at the modal's refresh rate)
 ///
-/// The loop exits when the shell closes (PTY channel disconnects) or on error.
+/// With `reload`, config file edits are applied on the camera tick. With
+/// `recording`, PTY output is also written to the session recording; if that
+/// fails the recording stops but the session carries on.
+///
+/// The loop exits when the shell closes (PTY channel disconnects), when the
+/// quit hotkey is pressed (if enabled), or on error.
+#[allow(clippy::too_many_arguments)]
 pub async fn run(
     mut pty: PtyHostSplit,
     mut pty_rx: mpsc::Receiver<Vec<u8>>,
     camera_modal: &mut CameraModal,
-    _status_bar: &StatusBar,
-    camera: Option<&mut CameraCapture>,
-    invert: bool,
-) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    status_bar: &StatusBar,
+    mut camera: Option<&mut CameraCapture>,
+    hotkeys: &Hotkeys,
+    mut reload: Option<ConfigReload>,
+    mut recording: Option<&mut SessionRecorder>,
+    config: &EventLoopConfig,
+) -> Result<SessionEnd, Box<dyn std::error::Error + Send + Sync>> {
     let mut stdout = std::io::stdout();
     let mut event_stream = EventStream::new();
 
-    // Camera frame interval (~15 FPS for ASCII rendering)
-    let mut camera_interval = tokio::time::interval(Duration::from_millis(67));
+    // Overlay redraw interval, decoupled from the camera capture rate so a
+    // 30 FPS camera can still be drawn at 15 FPS over slow links like SSH
+    let mut camera_interval = tokio::time::interval(camera_modal.refresh_interval);
     camera_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
 
+    // Shell output waits here until the next refresh tick, then stays as scrollback
+    let mut scrollback = Scrollback::new();
+    let mut refresh_interval = tokio::time::interval(config.refresh_interval);
+    refresh_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    // Overlay cells last drawn, so each tick only rewrites what changed
+    let mut overlay = DiffRenderer::new();
+
     // Reusable buffers for ASCII conversion (avoid allocations in hot path)
-    let mut gray_buffer: Vec<u8> = Vec::new();
-    let mut brightness_buffer: Vec<u8> = Vec::new();
-    let mut char_buffer: Vec<char> = Vec::new();
-    let mut color_buffer: Vec<ascii::CellColor> = Vec::new();
+    let mut pipeline = AsciiPipeline::new();
 
-    // Track terminal size for modal positioning
-    let (mut term_cols, mut term_rows) = crossterm::terminal::size().unwrap_or((80, 24));
+    // Track terminal size for modal positioning; `term_rows` excludes the
+    // status bar's row, which the shell (and overlay) never draw into
+    let status_rows = u16::from(status_bar.visible);
+    let (mut term_cols, mut screen_rows) = crossterm::terminal::size().unwrap_or((80, 24));
+    let mut term_rows = screen_rows.saturating_sub(status_rows);
+    if status_bar.visible {
+        // Newline then up: scrolls once if the cursor is on the last row, else a no-op
+        stdout.write_all(b"\n\x1b[A")?;
+        reserve_status_row(&mut stdout, screen_rows)?;
+    }
 
     // Track previous modal state to clear old area when size/position/visibility changes
     let mut prev_modal_size = camera_modal.size;
     let mut prev_modal_position = camera_modal.position;
     let mut prev_modal_visible = camera_modal.visible;
+    let mut prev_notice_shown = false;
+
+    // Modal drag in progress (mouse events only arrive with --mouse)
+    let mut mouse_drag: Option<MouseDrag> = None;
 
     // Track last activity to pause modal rendering while terminal is actively changing
     // This prevents cursor position conflicts and visual artifacts from scrolling
@@ -59,9 +321,26 @@ pub async fn run(
     const ACTIVITY_PAUSE_MS: u128 = 100; // Short pause after any terminal activity
     let mut modal_hidden_for_activity = false;
 
+    // Camera hot-swap: the capture thread retries on its own; once it gives
+    // up, reconnect from here every RECONNECT_INTERVAL
+    const RECONNECT_INTERVAL: Duration = Duration::from_secs(5);
+
+    // How long to wait for the shell to be reaped once its output closes,
+    // or for its output to close once it's reaped
+    const SHELL_EXIT_GRACE: Duration = Duration::from_secs(1);
+    let mut end = SessionEnd::Quit;
+    let mut camera_was_disconnected = false;
+    let mut last_reconnect: Option<Instant> = None;
+
     loop {
         // Check if shell has exited (non-blocking)
-        if let Some(_status) = pty.try_wait()? {
+        if let Some(status) = pty.try_wait()? {
+            end = SessionEnd::ShellExited(status);
+            // The reader thread may still have its last output queued; take
+            // it until the PTY closes, unless a leftover child keeps it open
+            while let Ok(Some(data)) = tokio::time::timeout(SHELL_EXIT_GRACE, pty_rx.recv()).await {
+                scrollback.buffer.queue(&data);
+            }
             break;
         }
 
@@ -72,8 +351,29 @@ pub async fn run(
                     Some(Ok(event)) => {
                         match event {
                             Event::Key(key_event) => {
+                                if scrollback.wants_key(&key_event) {
+                                    if !scrollback.active && camera_modal.visible {
+                                        // Keep the overlay off the screen the view returns to
+                                        clear_modal_area(
+                                            &mut stdout,
+                                            camera_modal.size,
+                                            camera_modal.position,
+                                            term_cols,
+                                            term_rows,
+                                        )?;
+                                    }
+                                    scrollback.handle_key(&mut stdout, key_event, term_rows)?;
+                                    overlay.invalidate();
+                                    continue;
+                                }
+                                if scrollback.active {
+                                    // Any other key goes back to the shell's screen
+                                    scrollback.leave(&mut stdout)?;
+                                    overlay.invalidate();
+                                }
+
                                 // Handle hotkeys first, then forward other keys to PTY
-                                match handle_key_event(key_event, camera_modal) {
+                                match handle_key_event(key_event, camera_modal, hotkeys) {
                                     KeyAction::Handled => {
                                         // Check if camera was toggled off - need to clear the area
                                         if prev_modal_visible && !camera_modal.visible {
@@ -84,6 +384,7 @@ pub async fn run(
                                                 term_cols,
                                                 term_rows,
                                             )?;
+                                            overlay.invalidate();
                                         }
                                         prev_modal_visible = camera_modal.visible;
                                     }
@@ -99,9 +400,25 @@ pub async fn run(
                                                 term_cols,
                                                 term_rows,
                                             )?;
+                                            overlay.invalidate();
                                             modal_hidden_for_activity = true;
                                         }
                                     }
+                                    KeyAction::Quit => {
+                                        if camera_modal.visible {
+                                            clear_modal_area(
+                                                &mut stdout,
+                                                camera_modal.size,
+                                                camera_modal.position,
+                                                term_cols,
+                                                term_rows,
+                                            )?;
+                                            overlay.invalidate();
+                                        }
+                                        // Kill the shell so the reader thread sees EOF and exits
+                                        pty.kill()?;
+                                        break;
+                                    }
                                     KeyAction::None => {
                                         // Key not recognized, ignore
                                     }
@@ -110,17 +427,29 @@ pub async fn run(
                             Event::Resize(cols, rows) => {
                                 // Terminal was resized (SIGWINCH) - resize the PTY to match
                                 term_cols = cols;
-                                term_rows = rows;
-                                let new_size = PtySize {
-                                    rows,
-                                    cols,
-                                    pixel_width: 0,
-                                    pixel_height: 0,
-                                };
-                                pty.resize(new_size)?;
+                                screen_rows = rows;
+                                term_rows = rows.saturating_sub(status_rows);
+                                let size = PtySize::for_terminal(cols, rows, status_rows);
+                                pty.resize(size)?;
+                                overlay.invalidate();
+                                if let Some(Err(e)) = recording.as_deref_mut().map(|r| r.resize(size)) {
+                                    camera_modal.show_notice(format!("recording stopped: {}", e));
+                                    recording = None;
+                                }
+                                if status_bar.visible {
+                                    reserve_status_row(&mut stdout, screen_rows)?;
+                                }
+                                if scrollback.active {
+                                    scrollback.redraw(&mut stdout, term_rows)?;
+                                }
+                            }
+                            Event::Mouse(mouse_event) => {
+                                // The old area is cleared on the next redraw, like after a hotkey
+                                let container = Rect::new(0, 0, term_cols, term_rows);
+                                handle_mouse_event(mouse_event, camera_modal, container, &mut mouse_drag);
                             }
                             _ => {
-                                // Ignore other events (mouse, focus, etc.)
+                                // Ignore other events (focus, paste, etc.)
                             }
                         }
                     }
@@ -137,32 +466,84 @@ pub async fn run(
             // Handle PTY output from the reader thread
             maybe_data = pty_rx.recv() => {
                 match maybe_data {
-                    Some(data) => {
-                        // Write PTY output to stdout - colors and escape sequences pass through
-                        stdout.write_all(&data)?;
-                        stdout.flush()?;
-                        last_activity = Some(Instant::now());
-                        // Hide modal during output to prevent scrolling artifacts
-                        if camera_modal.visible && !modal_hidden_for_activity {
-                            clear_modal_area(
-                                &mut stdout,
-                                camera_modal.size,
-                                camera_modal.position,
-                                term_cols,
-                                term_rows,
-                            )?;
-                            modal_hidden_for_activity = true;
-                        }
-                    }
+                    Some(data) => scrollback.buffer.queue(&data),
                     None => {
                         // Channel closed - reader thread exited (shell closed)
+                        end = match tokio::task::block_in_place(|| pty.wait_timeout(SHELL_EXIT_GRACE))? {
+                            Some(status) => SessionEnd::ShellExited(status),
+                            None => SessionEnd::OutputClosed,
+                        };
                         break;
                     }
                 }
             }
 
+            // Write the output batched since the last tick
+            _ = refresh_interval.tick(), if scrollback.buffer.has_pending() => {
+                let was_scrolled = scrollback.active;
+                if write_output(&mut stdout, &mut scrollback, &mut recording, camera_modal)? {
+                    last_activity = Some(Instant::now());
+                    if was_scrolled && !scrollback.active {
+                        overlay.invalidate();
+                    }
+                    // Hide modal during output to prevent scrolling artifacts
+                    if camera_modal.visible && !modal_hidden_for_activity && !scrollback.active {
+                        clear_modal_area(
+                            &mut stdout,
+                            camera_modal.size,
+                            camera_modal.position,
+                            term_cols,
+                            term_rows,
+                        )?;
+                        overlay.invalidate();
+                        modal_hidden_for_activity = true;
+                    }
+                }
+            }
+
             // Camera frame capture and rendering
             _ = camera_interval.tick() => {
+                if let Some(notice) = reload.as_mut().and_then(|r| r.poll(camera_modal)) {
+                    camera_modal.show_notice(notice);
+                    // Hidden by the edit: erase the last frame drawn
+                    if prev_modal_visible && !camera_modal.visible {
+                        clear_modal_area(
+                            &mut stdout,
+                            prev_modal_size,
+                            prev_modal_position,
+                            term_cols,
+                            term_rows,
+                        )?;
+                        overlay.invalidate();
+                    }
+                    prev_modal_visible = camera_modal.visible;
+                }
+
+                if let Some(cam) = camera.as_deref_mut() {
+                    cam.set_mirror(camera_modal.mirror);
+                    let disconnected = cam.status().is_err();
+                    if disconnected {
+                        camera_modal.show_notice("camera reconnecting…");
+                        let due = last_reconnect.is_none_or(|t| t.elapsed() >= RECONNECT_INTERVAL);
+                        if !cam.is_running() && due {
+                            last_reconnect = Some(Instant::now());
+                            // Opening a camera blocks briefly; keep other tasks moving
+                            let _ = tokio::task::block_in_place(|| cam.reconnect());
+                        }
+                    } else if camera_was_disconnected {
+                        camera_modal.show_notice("camera reconnected");
+                        last_reconnect = None;
+                    }
+                    camera_was_disconnected = disconnected;
+                    camera_modal.camera_fps = if disconnected { None } else { cam.actual_fps() };
+                }
+
+                // Redrawn every tick: a full-screen clear in the shell erases it too
+                if status_bar.visible {
+                    let text = status_bar.format(camera_modal);
+                    render_status_line(&mut stdout, &text, term_cols, screen_rows)?;
+                }
+
                 // Skip rendering if terminal is actively changing (typing or output)
                 let activity_settled = last_activity
                     .map(|t| t.elapsed().as_millis() > ACTIVITY_PAUSE_MS)
@@ -175,67 +556,41 @@ pub async fn run(
 
                 if camera_modal.visible
                     && activity_settled
+                    && !scrollback.active
                     && let Some(ref cam) = camera
                     && let Some(frame) = cam.get_frame()
                 {
                     // Get modal dimensions
-                    let (modal_width, modal_height) = camera_modal.size.inner_dimensions();
+                    let (modal_width, modal_height) =
+                        camera_modal.grid_dimensions(frame.width, frame.height);
 
-                    // Downsample colors for the frame
-                    ascii::downsample_colors_into(
+                    // Convert frame to ASCII
+                    pipeline.invert = camera_modal.invert;
+                    pipeline.supersample = camera_modal.supersample;
+                    pipeline.perf = camera_modal.perf;
+                    pipeline.dither = camera_modal.dither;
+                    pipeline.braille_threshold = camera_modal.braille_threshold;
+                    pipeline.auto_levels = camera_modal.auto_levels;
+                    pipeline.sharpen = camera_modal.effective_sharpen();
+                    pipeline.gamma = camera_modal.gamma;
+                    // Hand over the finished frame; the pipeline fills its other buffer next
+                    camera_modal.set_frame(pipeline.render_shared(
                         &frame,
                         modal_width,
                         modal_height,
-                        &mut color_buffer,
-                    );
-
-                    // Convert colors to terminal CellColor format
-                    let terminal_colors: Vec<CellColor> = color_buffer
-                        .iter()
-                        .map(|c| CellColor { r: c.r, g: c.g, b: c.b })
-                        .collect();
-
-                    // Convert frame to ASCII
-                    let ascii_frame = if camera_modal.charset.is_braille() {
-                        // Braille rendering (2x4 subpixel resolution)
-                        ascii::to_grayscale_into(&frame, &mut gray_buffer);
-                        let chars = ascii::render_braille(
-                            &gray_buffer,
-                            frame.width,
-                            frame.height,
-                            modal_width,
-                            modal_height,
-                            80, // threshold - lower = more dots = more detail
-                            invert,
-                        );
-                        AsciiFrame::from_chars_colored(chars, terminal_colors, modal_width, modal_height)
-                    } else {
-                        // Standard/blocks/minimal charset rendering
-                        ascii::to_grayscale_into(&frame, &mut gray_buffer);
-                        ascii::downsample_into(
-                            &gray_buffer,
-                            frame.width,
-                            frame.height,
-                            modal_width,
-                            modal_height,
-                            &mut brightness_buffer,
-                        );
-                        ascii::map_to_chars_gamma_into(
-                            &brightness_buffer,
-                            camera_modal.charset.chars(),
-                            invert,
-                            &mut char_buffer,
-                        );
-                        AsciiFrame::from_chars_colored(char_buffer.clone(), terminal_colors, modal_width, modal_height)
-                    };
-
-                    camera_modal.set_frame(ascii_frame);
+                        &camera_modal.charset,
+                    ));
 
                     // Check if modal size/position changed - need to clear old area
                     let size_changed = prev_modal_size != camera_modal.size;
                     let position_changed = prev_modal_position != camera_modal.position;
 
-                    if size_changed || position_changed {
+                    // Transparent cells aren't redrawn, so an expired notice must be erased
+                    let notice_shown = camera_modal.current_notice().is_some();
+                    let notice_expired = prev_notice_shown && !notice_shown;
+                    prev_notice_shown = notice_shown;
+
+                    if size_changed || position_changed || notice_expired {
                         // Clear the old modal area
                         clear_modal_area(
                             &mut stdout,
@@ -244,21 +599,195 @@ pub async fn run(
                             term_cols,
                             term_rows,
                         )?;
+                        overlay.invalidate();
                         prev_modal_size = camera_modal.size;
                         prev_modal_position = camera_modal.position;
                     }
 
-                    // Render the overlay
-                    render_camera_overlay(
-                        &mut stdout,
-                        camera_modal,
-                        term_cols,
-                        term_rows,
-                    )?;
+                    // Render the overlay's changed cells
+                    overlay.render_modal(&mut stdout, camera_modal, term_cols, term_rows)?;
                 }
             }
         }
     }
 
-    Ok(())
+    // Don't lose the shell's last words
+    scrollback.leave(&mut stdout)?;
+    write_output(&mut stdout, &mut scrollback, &mut recording, camera_modal)?;
+
+    if status_bar.visible {
+        release_status_row(&mut stdout, screen_rows)?;
+    }
+
+    Ok(end)
+}
+
+/// Pass the PTY output batched in `scrollback` through to the terminal
+/// (colors and escape sequences intact) and to the session recording, if any.
+///
+/// Returns false if nothing was batched.
+fn write_output(
+    stdout: &mut impl Write,
+    scrollback: &mut Scrollback,
+    recording: &mut Option<&mut SessionRecorder>,
+    camera_modal: &mut CameraModal,
+) -> std::io::Result<bool> {
+    let Some(batch) = scrollback.buffer.flush() else {
+        return Ok(false);
+    };
+    scrollback.write(stdout, &batch)?;
+    if let Some(Err(e)) = recording.as_deref_mut().map(|r| r.output(&batch)) {
+        camera_modal.show_notice(format!("recording stopped: {}", e));
+        *recording = None;
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terminal::ModalSize;
+
+    fn reload(current: Config) -> ConfigReload {
+        let (_tx, updates) = std::sync::mpsc::channel();
+        ConfigReload {
+            updates,
+            profile: None,
+            current,
+        }
+    }
+
+    #[test]
+    fn test_reload_applies_only_changed_keys() {
+        let mut reload = reload(Config::default());
+        let mut modal = CameraModal::new();
+        // Set from the command line; the file's value hasn't changed
+        modal.invert = true;
+
+        let mut edited = Config::default();
+        edited.modal.size = "large".to_string();
+        edited.modal.transparency_step = 25;
+        let restart = reload.apply(edited, &mut modal).unwrap();
+
+        assert!(restart.is_empty());
+        assert_eq!(modal.size, ModalSize::Large);
+        assert_eq!(modal.transparency_step, 25);
+        assert!(modal.invert);
+    }
+
+    #[test]
+    fn test_reload_reports_restart_keys() {
+        let mut reload = reload(Config::default());
+        let mut modal = CameraModal::new();
+
+        let mut edited = Config::default();
+        edited.camera.device = 1;
+        edited.modal.border = true;
+        edited.ui.status_bar = false;
+        assert_eq!(
+            reload.apply(edited, &mut modal).unwrap(),
+            vec!["camera.device", "modal.border", "ui.status_bar"]
+        );
+        assert!(!modal.border);
+
+        let mut bad = reload.current.clone();
+        bad.modal.position = "middle".to_string();
+        assert!(reload.apply(bad, &mut modal).is_err());
+        let mut bad = reload.current.clone();
+        bad.modal.transparency_step = 0;
+        assert!(reload.apply(bad, &mut modal).is_err());
+    }
+
+    #[test]
+    fn test_reload_with_bad_value_applies_nothing() {
+        let mut reload = reload(Config::default());
+        let mut modal = CameraModal::new();
+
+        let mut edited = Config::default();
+        edited.modal.size = "large".to_string();
+        edited.ascii.invert = true;
+        edited.ascii.charset = "runes".to_string();
+        assert!(reload.apply(edited, &mut modal).is_err());
+
+        assert_eq!(modal.size, ModalSize::Small);
+        assert!(!modal.invert);
+        // Fixing the typo still applies the rest of the edit
+        let mut fixed = Config::default();
+        fixed.modal.size = "large".to_string();
+        fixed.ascii.invert = true;
+        reload.apply(fixed, &mut modal).unwrap();
+        assert_eq!(modal.size, ModalSize::Large);
+        assert!(modal.invert);
+    }
+
+    #[test]
+    fn test_scroll_keys_route_to_scrollback_view() {
+        let mut scrollback = Scrollback::new();
+        for i in 0..50 {
+            scrollback.buffer.queue(format!("{}\r\n", i).as_bytes());
+        }
+        let mut out = Vec::new();
+        assert!(
+            write_output(
+                &mut out,
+                &mut scrollback,
+                &mut None,
+                &mut CameraModal::new()
+            )
+            .unwrap()
+        );
+        out.clear();
+        let shift = |code| KeyEvent::new(code, KeyModifiers::SHIFT);
+
+        // At the bottom, only Shift+PageUp is taken from the shell
+        assert!(!scrollback.wants_key(&shift(KeyCode::Up)));
+        assert!(!scrollback.wants_key(&KeyEvent::new(KeyCode::PageUp, KeyModifiers::NONE)));
+        assert!(scrollback.wants_key(&shift(KeyCode::PageUp)));
+
+        scrollback
+            .handle_key(&mut out, shift(KeyCode::PageUp), 10)
+            .unwrap();
+        let view = String::from_utf8_lossy(&out).into_owned();
+        assert!(view.starts_with("\x1b[?1049h"));
+        assert!(view.contains("\x1b[1;1H\x1b[2K30\x1b[0m"));
+        assert!(view.contains("\x1b[10;1H\x1b[2K39\x1b[0m"));
+        assert!(scrollback.wants_key(&shift(KeyCode::Up)));
+
+        // Output while scrolled back waits for the shell's screen
+        out.clear();
+        scrollback.buffer.queue(b"new\r\n");
+        write_output(
+            &mut out,
+            &mut scrollback,
+            &mut None,
+            &mut CameraModal::new(),
+        )
+        .unwrap();
+        assert!(out.is_empty());
+
+        scrollback
+            .handle_key(&mut out, shift(KeyCode::End), 10)
+            .unwrap();
+        assert!(!scrollback.active);
+        assert_eq!(out, b"\x1b[?1049l\x1b[?25hnew\r\n");
+    }
+
+    #[test]
+    fn test_scroll_home_stops_at_oldest_page() {
+        let mut scrollback = Scrollback::new();
+        scrollback.buffer.append_str("a\nb\nc\n");
+        let mut out = Vec::new();
+
+        // Less than a page of output: nothing to scroll back to
+        let page_up = KeyEvent::new(KeyCode::PageUp, KeyModifiers::SHIFT);
+        scrollback.handle_key(&mut out, page_up, 10).unwrap();
+        assert!(!scrollback.active);
+        assert!(out.is_empty());
+
+        scrollback.buffer.append_str(&"x\n".repeat(20));
+        scrollback.handle_key(&mut out, page_up, 10).unwrap();
+        scrollback.handle_key(&mut out, page_up, 10).unwrap();
+        assert_eq!(scrollback.buffer.scroll(), 13);
+        assert!(String::from_utf8_lossy(&out).ends_with("\x1b[10;1H\x1b[2Kx\x1b[0m\x1b[?7h"));
+    }
 }
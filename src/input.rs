@@ -3,10 +3,258 @@
 //! This module handles:
 //! - Converting crossterm KeyEvents to bytes for PTY transmission
 //! - Processing hotkeys (Alt+C, Alt+P, etc.) before forwarding to PTY
+//! - Parsing configurable `[hotkeys]` bindings like `"ctrl+shift+o"`
+//! - Dragging and resizing the camera modal with the mouse
+//! - Scrolling back through a `PtyBuffer`
 
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Rect;
+use std::fmt;
+use std::str::FromStr;
 
-use crate::terminal::CameraModal;
+use crate::config::{ConfigError, HotkeyConfig};
+use crate::terminal::{CameraModal, MIN_CUSTOM_SIZE, ModalPosition, PtyBuffer};
+
+/// A key combination from the `[hotkeys]` config section.
+///
+/// Written as `+`-separated modifiers (`ctrl`, `alt`, `shift`) followed by one
+/// key: a character or a name like `space`, `enter`, `left`, or `f5`. A bare
+/// key (`"c"`) means Alt+key, matching the built-in hotkeys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// Whether `event` is this key combination.
+    ///
+    /// Ctrl and Alt must match exactly. Shift must be held if bound, but is
+    /// otherwise ignored: terminals report shifted letters inconsistently, so
+    /// Alt+C and Alt+Shift+C both trigger an `alt+c` binding.
+    pub fn matches(&self, event: &KeyEvent) -> bool {
+        let exact = KeyModifiers::CONTROL | KeyModifiers::ALT;
+        if event.modifiers & exact != self.modifiers & exact
+            || !event
+                .modifiers
+                .contains(self.modifiers & KeyModifiers::SHIFT)
+        {
+            return false;
+        }
+        match (self.code, event.code) {
+            (KeyCode::Char(bound), KeyCode::Char(pressed)) => bound.eq_ignore_ascii_case(&pressed),
+            (bound, pressed) => bound == pressed,
+        }
+    }
+
+    /// Whether some key press would match both bindings.
+    ///
+    /// Shift is ignored unless bound (see `matches`), so `alt+t` and
+    /// `alt+shift+t` overlap.
+    pub fn overlaps(&self, other: &KeyBinding) -> bool {
+        let exact = KeyModifiers::CONTROL | KeyModifiers::ALT;
+        self.code == other.code && self.modifiers & exact == other.modifiers & exact
+    }
+}
+
+impl FromStr for KeyBinding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_ascii_lowercase();
+        let mut parts: Vec<&str> = lower.split('+').collect();
+        // "ctrl++" binds the plus key
+        if lower.ends_with("++") {
+            parts.truncate(parts.len() - 2);
+            parts.push("+");
+        }
+        let key = parts.pop().unwrap_or_default();
+
+        let mut modifiers = KeyModifiers::NONE;
+        for part in &parts {
+            let modifier = match *part {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "option" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                "" => return Err(format!("'{}' has an empty part", s)),
+                other => {
+                    return Err(format!(
+                        "'{}' is not a modifier (use ctrl, alt, shift)",
+                        other
+                    ));
+                }
+            };
+            if modifiers.contains(modifier) {
+                return Err(format!("'{}' repeats the {} modifier", s, part));
+            }
+            modifiers |= modifier;
+        }
+        if parts.is_empty() {
+            modifiers = KeyModifiers::ALT;
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (None, _) => return Err(format!("'{}' has no key", s)),
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key {
+                "space" => KeyCode::Char(' '),
+                "enter" | "return" => KeyCode::Enter,
+                "tab" => KeyCode::Tab,
+                "esc" | "escape" => KeyCode::Esc,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" => KeyCode::Insert,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                _ => match key.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=12) => KeyCode::F(n),
+                    _ => return Err(format!("'{}' is not a key", key)),
+                },
+            },
+        };
+        Ok(Self { code, modifiers })
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "ctrl"),
+            (KeyModifiers::ALT, "alt"),
+            (KeyModifiers::SHIFT, "shift"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{}+", name)?;
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::F(n) => write!(f, "f{}", n),
+            code => f.write_str(&format!("{:?}", code).to_ascii_lowercase()),
+        }
+    }
+}
+
+/// The configurable hotkeys, parsed from `[hotkeys]`.
+///
+/// The `Option` actions are `None` while unbound, so their keys reach the shell.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hotkeys {
+    pub toggle_camera: KeyBinding,
+    pub cycle_position: KeyBinding,
+    pub cycle_size: KeyBinding,
+    pub cycle_charset: KeyBinding,
+    pub cycle_transparency: KeyBinding,
+    pub toggle_color: KeyBinding,
+    pub toggle_invert: KeyBinding,
+    pub toggle_mirror: Option<KeyBinding>,
+    pub cycle_gamma: Option<KeyBinding>,
+    pub toggle_perf: Option<KeyBinding>,
+    pub cycle_dither: Option<KeyBinding>,
+    pub toggle_auto_levels: Option<KeyBinding>,
+    pub toggle_sharpen: Option<KeyBinding>,
+    /// `None` unless the quit hotkey is enabled (`--quit-hotkey`)
+    pub quit: Option<KeyBinding>,
+}
+
+impl Default for Hotkeys {
+    fn default() -> Self {
+        Self::from_config(&HotkeyConfig::default(), false).expect("default hotkeys parse")
+    }
+}
+
+impl Hotkeys {
+    /// Parse every binding in `config`, naming the first one that doesn't
+    /// parse or that shares its key with another action.
+    pub fn from_config(config: &HotkeyConfig, quit_hotkey: bool) -> Result<Self, ConfigError> {
+        let parse = |field: &str, value: &str| {
+            value
+                .parse::<KeyBinding>()
+                .map_err(|message| ConfigError::Invalid {
+                    field: format!("hotkeys.{}", field),
+                    message,
+                })
+        };
+        let parse_optional = |field: &str, value: &Option<String>| {
+            value.as_deref().map(|v| parse(field, v)).transpose()
+        };
+        let quit = parse("quit", &config.quit)?;
+        let hotkeys = Self {
+            toggle_camera: parse("toggle_camera", &config.toggle_camera)?,
+            cycle_position: parse("cycle_position", &config.cycle_position)?,
+            cycle_size: parse("cycle_size", &config.cycle_size)?,
+            cycle_charset: parse("cycle_charset", &config.cycle_charset)?,
+            cycle_transparency: parse("cycle_transparency", &config.cycle_transparency)?,
+            toggle_color: parse("toggle_color", &config.toggle_color)?,
+            toggle_invert: parse("toggle_invert", &config.toggle_invert)?,
+            toggle_mirror: parse_optional("toggle_mirror", &config.toggle_mirror)?,
+            cycle_gamma: parse_optional("cycle_gamma", &config.cycle_gamma)?,
+            toggle_perf: parse_optional("toggle_perf", &config.toggle_perf)?,
+            cycle_dither: parse_optional("cycle_dither", &config.cycle_dither)?,
+            toggle_auto_levels: parse_optional("toggle_auto_levels", &config.toggle_auto_levels)?,
+            toggle_sharpen: parse_optional("toggle_sharpen", &config.toggle_sharpen)?,
+            quit: quit_hotkey.then_some(quit),
+        };
+        match hotkeys.conflicts().first() {
+            Some((first, second)) => Err(ConfigError::Invalid {
+                field: format!("hotkeys.{}", second),
+                message: format!("same key as hotkeys.{}", first),
+            }),
+            None => Ok(hotkeys),
+        }
+    }
+
+    /// Every bound action, by its `[hotkeys]` key.
+    pub fn bindings(&self) -> Vec<(&'static str, KeyBinding)> {
+        let optional = [
+            ("toggle_mirror", self.toggle_mirror),
+            ("cycle_gamma", self.cycle_gamma),
+            ("toggle_perf", self.toggle_perf),
+            ("cycle_dither", self.cycle_dither),
+            ("toggle_auto_levels", self.toggle_auto_levels),
+            ("toggle_sharpen", self.toggle_sharpen),
+            ("quit", self.quit),
+        ];
+        let mut bindings = vec![
+            ("toggle_camera", self.toggle_camera),
+            ("cycle_position", self.cycle_position),
+            ("cycle_size", self.cycle_size),
+            ("cycle_charset", self.cycle_charset),
+            ("cycle_transparency", self.cycle_transparency),
+            ("toggle_color", self.toggle_color),
+            ("toggle_invert", self.toggle_invert),
+        ];
+        bindings.extend(
+            optional
+                .into_iter()
+                .filter_map(|(field, binding)| Some((field, binding?))),
+        );
+        bindings
+    }
+
+    /// Pairs of actions bound to overlapping keys; only the first of each
+    /// pair would ever run.
+    pub fn conflicts(&self) -> Vec<(&'static str, &'static str)> {
+        let bindings = self.bindings();
+        let mut conflicts = Vec::new();
+        for (i, (first, binding)) in bindings.iter().enumerate() {
+            for (second, other) in &bindings[i + 1..] {
+                if binding.overlaps(other) {
+                    conflicts.push((*first, *second));
+                }
+            }
+        }
+        conflicts
+    }
+}
 
 /// Result of handling a key event.
 pub enum KeyAction {
@@ -14,44 +262,141 @@ pub enum KeyAction {
     Handled,
     /// Key should be forwarded to PTY
     Forward(Vec<u8>),
+    /// Quit hotkey pressed (end the session)
+    Quit,
     /// No action needed
     None,
 }
 
 /// Handle a key event, checking for hotkeys first.
 ///
-/// Hotkeys intercepted (not forwarded to PTY):
+/// Hotkeys intercepted (not forwarded to PTY), with their default bindings:
 /// - Alt+C: Toggle camera visibility
 /// - Alt+P: Cycle position
 /// - Alt+S: Cycle size
 /// - Alt+A: Cycle charset
-/// - Alt+T: Cycle transparency
-pub fn handle_key_event(event: KeyEvent, modal: &mut CameraModal) -> KeyAction {
+/// - Alt+T: Cycle transparency (Alt+Shift+T for a 5x step)
+/// - Alt+O: Toggle color
+/// - Alt+I: Toggle inversion
+/// - Alt+Q: Quit (only when `hotkeys.quit` is set, otherwise forwarded)
+///
+/// These follow `hotkeys`, which can also bind mirroring, gamma, performance
+/// mode, dithering, auto-levels, and sharpening (unbound by default).
+/// Alt+Shift+arrows, which nudge the modal, are fixed.
+pub fn handle_key_event(event: KeyEvent, modal: &mut CameraModal, hotkeys: &Hotkeys) -> KeyAction {
     let KeyEvent {
         code, modifiers, ..
     } = event;
 
-    // Check for Alt+key hotkeys first
+    // Configurable hotkeys first
+    if hotkeys.toggle_camera.matches(&event) {
+        modal.toggle();
+        return KeyAction::Handled;
+    }
+    if hotkeys.cycle_position.matches(&event) {
+        modal.cycle_position();
+        return KeyAction::Handled;
+    }
+    if hotkeys.cycle_size.matches(&event) {
+        modal.cycle_size();
+        return KeyAction::Handled;
+    }
+    if hotkeys.cycle_charset.matches(&event) {
+        modal.cycle_charset();
+        return KeyAction::Handled;
+    }
+    if hotkeys.cycle_transparency.matches(&event) {
+        // Shift (or a shifted letter) takes a coarse step, unless Shift is part of the binding
+        let shifted = modifiers.contains(KeyModifiers::SHIFT)
+            || matches!(code, KeyCode::Char(c) if c.is_ascii_uppercase());
+        let coarse = shifted
+            && !hotkeys
+                .cycle_transparency
+                .modifiers
+                .contains(KeyModifiers::SHIFT);
+        modal.cycle_transparency(coarse);
+        modal.show_notice(format!("transparency {}%", modal.transparency));
+        return KeyAction::Handled;
+    }
+    if hotkeys.toggle_color.matches(&event) {
+        modal.toggle_color();
+        modal.show_notice(if modal.color { "color on" } else { "color off" });
+        return KeyAction::Handled;
+    }
+    if hotkeys.toggle_invert.matches(&event) {
+        modal.toggle_invert();
+        modal.show_notice(if modal.invert {
+            "invert on"
+        } else {
+            "invert off"
+        });
+        return KeyAction::Handled;
+    }
+    if hotkeys.toggle_mirror.is_some_and(|key| key.matches(&event)) {
+        modal.toggle_mirror();
+        modal.show_notice(if modal.mirror {
+            "mirror on"
+        } else {
+            "mirror off"
+        });
+        return KeyAction::Handled;
+    }
+    if hotkeys.cycle_gamma.is_some_and(|key| key.matches(&event)) {
+        modal.cycle_gamma();
+        modal.show_notice(format!("gamma {:.1}", modal.gamma));
+        return KeyAction::Handled;
+    }
+    if hotkeys.toggle_perf.is_some_and(|key| key.matches(&event)) {
+        modal.toggle_perf();
+        modal.show_notice(if modal.perf { "perf on" } else { "perf off" });
+        return KeyAction::Handled;
+    }
+    if hotkeys.cycle_dither.is_some_and(|key| key.matches(&event)) {
+        modal.cycle_dither();
+        modal.show_notice(format!("dither {}", modal.dither.name()));
+        return KeyAction::Handled;
+    }
+    if hotkeys
+        .toggle_auto_levels
+        .is_some_and(|key| key.matches(&event))
+    {
+        modal.toggle_auto_levels();
+        modal.show_notice(if modal.auto_levels {
+            "auto-levels on"
+        } else {
+            "auto-levels off"
+        });
+        return KeyAction::Handled;
+    }
+    if hotkeys
+        .toggle_sharpen
+        .is_some_and(|key| key.matches(&event))
+    {
+        modal.toggle_sharpen();
+        modal.show_notice(if modal.sharpen {
+            "sharpen on"
+        } else {
+            "sharpen off"
+        });
+        return KeyAction::Handled;
+    }
+    if hotkeys.quit.is_some_and(|quit| quit.matches(&event)) {
+        return KeyAction::Quit;
+    }
+
+    // Then the fixed Alt+Shift+arrow nudge
     if modifiers.contains(KeyModifiers::ALT) {
         match code {
-            KeyCode::Char('c') | KeyCode::Char('C') => {
-                modal.toggle();
-                return KeyAction::Handled;
-            }
-            KeyCode::Char('p') | KeyCode::Char('P') => {
-                modal.cycle_position();
-                return KeyAction::Handled;
-            }
-            KeyCode::Char('s') | KeyCode::Char('S') => {
-                modal.cycle_size();
-                return KeyAction::Handled;
-            }
-            KeyCode::Char('a') | KeyCode::Char('A') => {
-                modal.cycle_charset();
-                return KeyAction::Handled;
-            }
-            KeyCode::Char('t') | KeyCode::Char('T') => {
-                modal.cycle_transparency();
+            KeyCode::Left | KeyCode::Right | KeyCode::Up | KeyCode::Down
+                if modifiers.contains(KeyModifiers::SHIFT) =>
+            {
+                let (dx, dy) = match code {
+                    KeyCode::Left => (-1, 0),
+                    KeyCode::Right => (1, 0),
+                    KeyCode::Up => (0, -1),
+                    _ => (0, 1),
+                };
+                modal.nudge_position(dx, dy);
                 return KeyAction::Handled;
             }
             _ => {
@@ -67,6 +412,175 @@ pub fn handle_key_event(event: KeyEvent, modal: &mut CameraModal) -> KeyAction {
     }
 }
 
+/// Handle scrollback keys for a `PtyBuffer`, a `page` of lines at a time.
+///
+/// Shift+PageUp/PageDown scroll by a page, Shift+Up/Down by a line, and
+/// Shift+Home/End jump to the oldest or newest output. Returns false for any
+/// other key, which should go on to `handle_key_event`.
+pub fn handle_scroll_key(event: KeyEvent, buffer: &mut PtyBuffer, page: u16) -> bool {
+    if event.modifiers != KeyModifiers::SHIFT {
+        return false;
+    }
+    match event.code {
+        KeyCode::PageUp => buffer.scroll_up(page.max(1)),
+        KeyCode::PageDown => buffer.scroll_down(page.max(1)),
+        KeyCode::Up => buffer.scroll_up(1),
+        KeyCode::Down => buffer.scroll_down(1),
+        KeyCode::Home => {
+            // Leave the oldest page on screen rather than scrolling past it
+            let oldest = buffer.line_count().saturating_sub(page as usize);
+            buffer.set_scroll(oldest.try_into().unwrap_or(u16::MAX));
+        }
+        KeyCode::End => buffer.scroll_to_bottom(),
+        _ => return false,
+    }
+    true
+}
+
+/// Corner of the modal grabbed for resizing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// What a mouse press at some cell would grab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModalHit {
+    /// Not on the modal
+    Outside,
+    /// Inside the modal: drag to move it
+    Move,
+    /// On a corner: drag to resize, keeping the opposite corner in place
+    Resize(Corner),
+}
+
+/// Columns from a corner that still grab it (rows get half, as cells are tall).
+pub const CORNER_GRAB: u16 = 2;
+
+/// Classify a press at (`col`, `row`) against the modal's `rect`.
+pub fn hit_test(rect: Rect, col: u16, row: u16) -> ModalHit {
+    let inside = col >= rect.x && col < rect.right() && row >= rect.y && row < rect.bottom();
+    if !inside {
+        return ModalHit::Outside;
+    }
+    let rows = (CORNER_GRAB / 2).max(1);
+    let left = col < rect.x + CORNER_GRAB;
+    let right = col + CORNER_GRAB >= rect.right();
+    let top = row < rect.y + rows;
+    let bottom = row + rows >= rect.bottom();
+    match (left, right, top, bottom) {
+        (true, _, true, _) => ModalHit::Resize(Corner::TopLeft),
+        (_, true, true, _) => ModalHit::Resize(Corner::TopRight),
+        (true, _, _, true) => ModalHit::Resize(Corner::BottomLeft),
+        (_, true, _, true) => ModalHit::Resize(Corner::BottomRight),
+        _ => ModalHit::Move,
+    }
+}
+
+/// A mouse drag in progress on the modal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MouseDrag {
+    /// Move or which corner is being resized
+    pub hit: ModalHit,
+    /// Cell the button went down on
+    pub start: (u16, u16),
+    /// The modal's rect when the drag started
+    pub origin: Rect,
+}
+
+impl MouseDrag {
+    /// Where the modal should be with the pointer at (`col`, `row`).
+    pub fn rect_at(&self, col: u16, row: u16) -> Rect {
+        let dx = col as i32 - self.start.0 as i32;
+        let dy = row as i32 - self.start.1 as i32;
+        let origin = self.origin;
+        let (mut left, mut top) = (origin.x as i32, origin.y as i32);
+        let (mut right, mut bottom) = (origin.right() as i32, origin.bottom() as i32);
+        let (min_w, min_h) = (MIN_CUSTOM_SIZE.0 as i32, MIN_CUSTOM_SIZE.1 as i32);
+
+        match self.hit {
+            ModalHit::Outside => {}
+            ModalHit::Move => {
+                left += dx;
+                top += dy;
+                right += dx;
+                bottom += dy;
+            }
+            ModalHit::Resize(corner) => {
+                // Move only the grabbed corner's edges, never past the minimum size
+                match corner {
+                    Corner::TopLeft | Corner::BottomLeft => left = (left + dx).min(right - min_w),
+                    Corner::TopRight | Corner::BottomRight => {
+                        right = (right + dx).max(left + min_w)
+                    }
+                }
+                match corner {
+                    Corner::TopLeft | Corner::TopRight => top = (top + dy).min(bottom - min_h),
+                    Corner::BottomLeft | Corner::BottomRight => {
+                        bottom = (bottom + dy).max(top + min_h)
+                    }
+                }
+            }
+        }
+        Rect {
+            x: left.max(0) as u16,
+            y: top.max(0) as u16,
+            width: (right - left) as u16,
+            height: (bottom - top) as u16,
+        }
+    }
+}
+
+/// Handle a mouse event: press on the visible modal to start a drag,
+/// move to drag or resize it within `container`, release to drop it.
+///
+/// Returns true if the modal moved or changed size.
+pub fn handle_mouse_event(
+    event: MouseEvent,
+    modal: &mut CameraModal,
+    container: Rect,
+    drag: &mut Option<MouseDrag>,
+) -> bool {
+    let (col, row) = (event.column, event.row);
+    match event.kind {
+        MouseEventKind::Down(MouseButton::Left) if modal.visible => {
+            let origin = modal.calculate_rect(container);
+            *drag = match hit_test(origin, col, row) {
+                ModalHit::Outside => None,
+                hit => Some(MouseDrag {
+                    hit,
+                    start: (col, row),
+                    origin,
+                }),
+            };
+            false
+        }
+        MouseEventKind::Drag(MouseButton::Left) => {
+            let Some(current) = drag else {
+                return false;
+            };
+            let (size, position) = (modal.size, modal.position);
+            let rect = current.rect_at(col, row);
+            if current.hit == ModalHit::Move {
+                // Moving keeps a preset size a preset
+                let (width, height) = size.dimensions();
+                modal.position = ModalPosition::at(container, rect.x, rect.y, width, height);
+            } else {
+                modal.place(container, rect);
+            }
+            modal.size != size || modal.position != position
+        }
+        MouseEventKind::Up(MouseButton::Left) => {
+            *drag = None;
+            false
+        }
+        _ => false,
+    }
+}
+
 /// Convert a crossterm KeyEvent to bytes that can be sent to the PTY.
 pub fn key_event_to_bytes(event: KeyEvent) -> Option<Vec<u8>> {
     let KeyEvent {
@@ -225,6 +739,261 @@ mod tests {
         assert_eq!(key_event_to_bytes(event), Some(vec![0x1B, b'c']));
     }
 
+    // ==================== Key Binding Tests ====================
+
+    #[test]
+    fn test_key_binding_parses_modifiers_and_key() {
+        let binding: KeyBinding = "ctrl+shift+o".parse().unwrap();
+        assert_eq!(binding.code, KeyCode::Char('o'));
+        assert_eq!(
+            binding.modifiers,
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT
+        );
+
+        let binding: KeyBinding = "Alt+F5".parse().unwrap();
+        assert_eq!(binding.code, KeyCode::F(5));
+        assert_eq!(binding.modifiers, KeyModifiers::ALT);
+
+        // A bare key is Alt+key, like the built-in hotkeys
+        let binding: KeyBinding = "c".parse().unwrap();
+        assert_eq!(binding.modifiers, KeyModifiers::ALT);
+
+        let binding: KeyBinding = "ctrl++".parse().unwrap();
+        assert_eq!(binding.code, KeyCode::Char('+'));
+    }
+
+    #[test]
+    fn test_key_binding_rejects_malformed_combos() {
+        for combo in [
+            "",
+            "ctrl+",
+            "+o",
+            "hyper+o",
+            "ctrl+ctrl+o",
+            "ctrl+ab",
+            "f13",
+        ] {
+            assert!(
+                combo.parse::<KeyBinding>().is_err(),
+                "{:?} should not parse",
+                combo
+            );
+        }
+        let err = "super+o".parse::<KeyBinding>().unwrap_err();
+        assert!(err.contains("'super' is not a modifier"), "{}", err);
+    }
+
+    #[test]
+    fn test_key_binding_display_round_trips() {
+        for combo in [
+            "ctrl+shift+o",
+            "alt+c",
+            "alt+space",
+            "ctrl+pageup",
+            "shift+f12",
+        ] {
+            let binding: KeyBinding = combo.parse().unwrap();
+            assert_eq!(binding.to_string(), combo);
+        }
+    }
+
+    #[test]
+    fn test_rebound_hotkey_replaces_default() {
+        let config = HotkeyConfig {
+            toggle_camera: "ctrl+shift+o".to_string(),
+            ..HotkeyConfig::default()
+        };
+        let hotkeys = Hotkeys::from_config(&config, false).unwrap();
+        let mut modal = CameraModal::new();
+
+        let event = KeyEvent::new(
+            KeyCode::Char('O'),
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+        );
+        assert!(matches!(
+            handle_key_event(event, &mut modal, &hotkeys),
+            KeyAction::Handled
+        ));
+        assert!(modal.visible);
+
+        // Alt+C is no longer bound, so the shell gets it
+        let event = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::ALT);
+        assert!(matches!(
+            handle_key_event(event, &mut modal, &hotkeys),
+            KeyAction::Forward(_)
+        ));
+
+        let bad = HotkeyConfig {
+            quit: "ctrl+".to_string(),
+            ..HotkeyConfig::default()
+        };
+        let err = Hotkeys::from_config(&bad, true).unwrap_err();
+        assert!(
+            err.to_string().starts_with("Invalid hotkeys.quit:"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_hotkeys_sharing_a_key_are_rejected() {
+        assert!(Hotkeys::default().conflicts().is_empty());
+
+        // Alt+O is toggle_color's default
+        let config = HotkeyConfig {
+            cycle_size: "o".to_string(),
+            ..HotkeyConfig::default()
+        };
+        let err = Hotkeys::from_config(&config, false).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid hotkeys.toggle_color: same key as hotkeys.cycle_size"
+        );
+
+        // Alt+Shift+T would also be transparency's coarse step
+        let config = HotkeyConfig {
+            cycle_gamma: Some("alt+shift+t".to_string()),
+            ..HotkeyConfig::default()
+        };
+        let err = Hotkeys::from_config(&config, false).unwrap_err();
+        assert!(err.to_string().contains("hotkeys.cycle_gamma"), "{}", err);
+
+        // The quit key only counts once it's enabled
+        let config = HotkeyConfig {
+            quit: "c".to_string(),
+            ..HotkeyConfig::default()
+        };
+        assert!(Hotkeys::from_config(&config, false).is_ok());
+        assert!(Hotkeys::from_config(&config, true).is_err());
+    }
+
+    // ==================== Scrollback Tests ====================
+
+    #[test]
+    fn test_scroll_keys_move_viewport() {
+        let mut buffer = PtyBuffer::new();
+        for i in 0..50 {
+            buffer.append_str(&format!("{}\n", i));
+        }
+        let shift = |code| KeyEvent::new(code, KeyModifiers::SHIFT);
+
+        assert!(handle_scroll_key(shift(KeyCode::PageUp), &mut buffer, 10));
+        assert_eq!(buffer.scroll(), 10);
+        assert!(handle_scroll_key(shift(KeyCode::Down), &mut buffer, 10));
+        assert_eq!(buffer.scroll(), 9);
+        assert!(handle_scroll_key(shift(KeyCode::Home), &mut buffer, 10));
+        assert_eq!(buffer.visible_content(10).lines().next(), Some("0"));
+        assert!(handle_scroll_key(shift(KeyCode::End), &mut buffer, 10));
+        assert!(!buffer.is_scrolled());
+
+        // Unshifted keys are the shell's
+        let plain = KeyEvent::new(KeyCode::PageUp, KeyModifiers::NONE);
+        assert!(!handle_scroll_key(plain, &mut buffer, 10));
+        assert_eq!(buffer.scroll(), 0);
+    }
+
+    // ==================== Mouse Tests ====================
+
+    #[test]
+    fn test_hit_test_regions() {
+        let rect = Rect::new(10, 5, 22, 12);
+
+        assert_eq!(hit_test(rect, 9, 5), ModalHit::Outside);
+        assert_eq!(hit_test(rect, 32, 10), ModalHit::Outside);
+        assert_eq!(hit_test(rect, 20, 17), ModalHit::Outside);
+
+        assert_eq!(hit_test(rect, 10, 5), ModalHit::Resize(Corner::TopLeft));
+        assert_eq!(hit_test(rect, 11, 5), ModalHit::Resize(Corner::TopLeft));
+        assert_eq!(hit_test(rect, 31, 5), ModalHit::Resize(Corner::TopRight));
+        assert_eq!(hit_test(rect, 10, 16), ModalHit::Resize(Corner::BottomLeft));
+        assert_eq!(
+            hit_test(rect, 30, 16),
+            ModalHit::Resize(Corner::BottomRight)
+        );
+
+        // Edges away from corners, and the middle, move the modal
+        assert_eq!(hit_test(rect, 12, 5), ModalHit::Move);
+        assert_eq!(hit_test(rect, 10, 6), ModalHit::Move);
+        assert_eq!(hit_test(rect, 20, 10), ModalHit::Move);
+    }
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn test_mouse_drag_moves_and_resizes_modal() {
+        use crate::terminal::ModalSize;
+
+        let container = Rect::new(0, 0, 100, 40);
+        let mut modal = CameraModal::new();
+        modal.visible = true;
+        let mut drag = None;
+        let start = modal.calculate_rect(container);
+        assert_eq!((start.x, start.y), (77, 27));
+
+        // Drag from the middle: moves, keeping the preset size
+        let down = MouseEventKind::Down(MouseButton::Left);
+        let held = MouseEventKind::Drag(MouseButton::Left);
+        handle_mouse_event(mouse(down, 85, 30), &mut modal, container, &mut drag);
+        assert!(handle_mouse_event(
+            mouse(held, 45, 10),
+            &mut modal,
+            container,
+            &mut drag
+        ));
+        let rect = modal.calculate_rect(container);
+        assert_eq!((rect.x, rect.y), (37, 7));
+        assert_eq!(modal.size, ModalSize::Small);
+
+        // Grab the bottom-right corner and pull it out
+        handle_mouse_event(
+            mouse(MouseEventKind::Up(MouseButton::Left), 45, 10),
+            &mut modal,
+            container,
+            &mut drag,
+        );
+        handle_mouse_event(mouse(down, 58, 18), &mut modal, container, &mut drag);
+        handle_mouse_event(mouse(held, 68, 23), &mut modal, container, &mut drag);
+        let rect = modal.calculate_rect(container);
+        assert_eq!(modal.size, ModalSize::Custom(32, 17));
+        assert_eq!((rect.x, rect.y), (37, 7));
+
+        // Shrinking past the minimum stops at MIN_CUSTOM_SIZE
+        handle_mouse_event(mouse(held, 0, 0), &mut modal, container, &mut drag);
+        assert_eq!(
+            modal.size,
+            ModalSize::Custom(MIN_CUSTOM_SIZE.0, MIN_CUSTOM_SIZE.1)
+        );
+    }
+
+    #[test]
+    fn test_mouse_ignored_outside_or_when_hidden() {
+        let container = Rect::new(0, 0, 100, 40);
+        let mut modal = CameraModal::new();
+        let mut drag = None;
+        let down = mouse(MouseEventKind::Down(MouseButton::Left), 85, 30);
+
+        handle_mouse_event(down, &mut modal, container, &mut drag);
+        assert!(drag.is_none(), "hidden modal can't be grabbed");
+
+        modal.visible = true;
+        handle_mouse_event(
+            mouse(MouseEventKind::Down(MouseButton::Left), 5, 5),
+            &mut modal,
+            container,
+            &mut drag,
+        );
+        assert!(drag.is_none());
+        let held = mouse(MouseEventKind::Drag(MouseButton::Left), 20, 20);
+        assert!(!handle_mouse_event(held, &mut modal, container, &mut drag));
+    }
+
     // ==================== Hotkey Handling Tests ====================
 
     #[test]
@@ -234,12 +1003,12 @@ mod tests {
 
         // Alt+C should toggle visibility
         let event = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::ALT);
-        let action = handle_key_event(event, &mut modal);
+        let action = handle_key_event(event, &mut modal, &Hotkeys::default());
         assert!(matches!(action, KeyAction::Handled));
         assert!(modal.visible);
 
         // Alt+C again should toggle back
-        let action2 = handle_key_event(event, &mut modal);
+        let action2 = handle_key_event(event, &mut modal, &Hotkeys::default());
         assert!(matches!(action2, KeyAction::Handled));
         assert!(!modal.visible);
     }
@@ -251,7 +1020,7 @@ mod tests {
 
         // Alt+C (uppercase) should also work
         let event = KeyEvent::new(KeyCode::Char('C'), KeyModifiers::ALT);
-        let action = handle_key_event(event, &mut modal);
+        let action = handle_key_event(event, &mut modal, &Hotkeys::default());
         assert!(matches!(action, KeyAction::Handled));
         assert!(modal.visible);
     }
@@ -265,7 +1034,7 @@ mod tests {
 
         // Alt+P should cycle position
         let event = KeyEvent::new(KeyCode::Char('p'), KeyModifiers::ALT);
-        let action = handle_key_event(event, &mut modal);
+        let action = handle_key_event(event, &mut modal, &Hotkeys::default());
         assert!(matches!(action, KeyAction::Handled));
         assert_eq!(modal.position, ModalPosition::BottomLeft);
     }
@@ -279,7 +1048,7 @@ mod tests {
 
         // Alt+S should cycle size
         let event = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::ALT);
-        let action = handle_key_event(event, &mut modal);
+        let action = handle_key_event(event, &mut modal, &Hotkeys::default());
         assert!(matches!(action, KeyAction::Handled));
         assert_eq!(modal.size, ModalSize::Medium);
     }
@@ -293,18 +1062,246 @@ mod tests {
 
         // Alt+A should cycle charset
         let event = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::ALT);
-        let action = handle_key_event(event, &mut modal);
+        let action = handle_key_event(event, &mut modal, &Hotkeys::default());
         assert!(matches!(action, KeyAction::Handled));
         assert_eq!(modal.charset, CharSet::Blocks);
     }
 
+    #[test]
+    fn test_handle_key_event_alt_t_shows_transparency_notice() {
+        let mut modal = CameraModal::new();
+        assert_eq!(modal.transparency, 80);
+
+        let event = KeyEvent::new(KeyCode::Char('t'), KeyModifiers::ALT);
+        let action = handle_key_event(event, &mut modal, &Hotkeys::default());
+        assert!(matches!(action, KeyAction::Handled));
+        assert_eq!(modal.transparency, 90);
+        assert_eq!(modal.current_notice(), Some("transparency 90%"));
+    }
+
+    #[test]
+    fn test_handle_key_event_alt_shift_t_takes_coarse_step() {
+        let mut modal = CameraModal::new();
+        modal.transparency = 0;
+
+        let event = KeyEvent::new(KeyCode::Char('T'), KeyModifiers::ALT | KeyModifiers::SHIFT);
+        handle_key_event(event, &mut modal, &Hotkeys::default());
+        assert_eq!(modal.transparency, 50);
+    }
+
+    #[test]
+    fn test_handle_key_event_alt_o_toggles_color() {
+        let mut modal = CameraModal::new();
+        assert!(!modal.color);
+
+        let event = KeyEvent::new(KeyCode::Char('o'), KeyModifiers::ALT);
+        let action = handle_key_event(event, &mut modal, &Hotkeys::default());
+        assert!(matches!(action, KeyAction::Handled));
+        assert!(modal.color);
+        assert_eq!(modal.current_notice(), Some("color on"));
+
+        handle_key_event(event, &mut modal, &Hotkeys::default());
+        assert!(!modal.color);
+        assert_eq!(modal.current_notice(), Some("color off"));
+    }
+
+    #[test]
+    fn test_handle_key_event_alt_i_toggles_invert() {
+        let mut modal = CameraModal::new();
+        assert!(!modal.invert);
+
+        let event = KeyEvent::new(KeyCode::Char('i'), KeyModifiers::ALT);
+        let action = handle_key_event(event, &mut modal, &Hotkeys::default());
+        assert!(matches!(action, KeyAction::Handled));
+        assert!(modal.invert);
+        assert_eq!(modal.current_notice(), Some("invert on"));
+    }
+
+    #[test]
+    fn test_handle_key_event_dither_when_bound() {
+        use crate::ascii::DitherMode;
+
+        let mut modal = CameraModal::new();
+        assert_eq!(modal.dither, DitherMode::None);
+
+        // Unbound by default: Alt+D stays readline's kill-word
+        let event = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::ALT);
+        let action = handle_key_event(event, &mut modal, &Hotkeys::default());
+        assert!(matches!(action, KeyAction::Forward(_)));
+
+        let config = HotkeyConfig {
+            cycle_dither: Some("d".to_string()),
+            ..HotkeyConfig::default()
+        };
+        let hotkeys = Hotkeys::from_config(&config, false).unwrap();
+        let action = handle_key_event(event, &mut modal, &hotkeys);
+        assert!(matches!(action, KeyAction::Handled));
+        assert_eq!(modal.dither, DitherMode::Ordered);
+        assert_eq!(modal.current_notice(), Some("dither ordered"));
+    }
+
+    #[test]
+    fn test_handle_key_event_auto_levels_when_bound() {
+        let mut modal = CameraModal::new();
+        assert!(!modal.auto_levels);
+
+        // Unbound by default: Alt+L stays readline's downcase-word
+        let event = KeyEvent::new(KeyCode::Char('l'), KeyModifiers::ALT);
+        let action = handle_key_event(event, &mut modal, &Hotkeys::default());
+        assert!(matches!(action, KeyAction::Forward(_)));
+
+        let config = HotkeyConfig {
+            toggle_auto_levels: Some("l".to_string()),
+            ..HotkeyConfig::default()
+        };
+        let hotkeys = Hotkeys::from_config(&config, false).unwrap();
+        let action = handle_key_event(event, &mut modal, &hotkeys);
+        assert!(matches!(action, KeyAction::Handled));
+        assert!(modal.auto_levels);
+        assert_eq!(modal.current_notice(), Some("auto-levels on"));
+    }
+
+    #[test]
+    fn test_handle_key_event_sharpen_when_bound() {
+        let mut modal = CameraModal::new();
+        assert!(!modal.sharpen);
+
+        // Unbound by default: Alt+H stays the shell's run-help
+        let event = KeyEvent::new(KeyCode::Char('h'), KeyModifiers::ALT);
+        let action = handle_key_event(event, &mut modal, &Hotkeys::default());
+        assert!(matches!(action, KeyAction::Forward(_)));
+
+        let config = HotkeyConfig {
+            toggle_sharpen: Some("h".to_string()),
+            ..HotkeyConfig::default()
+        };
+        let hotkeys = Hotkeys::from_config(&config, false).unwrap();
+        let action = handle_key_event(event, &mut modal, &hotkeys);
+        assert!(matches!(action, KeyAction::Handled));
+        assert!(modal.sharpen);
+        assert_eq!(modal.current_notice(), Some("sharpen on"));
+    }
+
+    #[test]
+    fn test_handle_key_event_mirror_when_bound() {
+        let mut modal = CameraModal::new();
+        assert!(!modal.mirror);
+
+        // Unbound by default: Alt+M stays with the shell
+        let event = KeyEvent::new(KeyCode::Char('m'), KeyModifiers::ALT);
+        let action = handle_key_event(event, &mut modal, &Hotkeys::default());
+        assert!(matches!(action, KeyAction::Forward(_)));
+        assert!(!modal.mirror);
+
+        let config = HotkeyConfig {
+            toggle_mirror: Some("m".to_string()),
+            ..HotkeyConfig::default()
+        };
+        let hotkeys = Hotkeys::from_config(&config, false).unwrap();
+        let action = handle_key_event(event, &mut modal, &hotkeys);
+        assert!(matches!(action, KeyAction::Handled));
+        assert!(modal.mirror);
+        assert_eq!(modal.current_notice(), Some("mirror on"));
+    }
+
+    #[test]
+    fn test_handle_key_event_gamma_when_bound() {
+        let mut modal = CameraModal::new();
+        assert_eq!(modal.gamma, 2.2);
+
+        let event = KeyEvent::new(KeyCode::Char('g'), KeyModifiers::ALT);
+        let action = handle_key_event(event, &mut modal, &Hotkeys::default());
+        assert!(matches!(action, KeyAction::Forward(_)));
+
+        let config = HotkeyConfig {
+            cycle_gamma: Some("g".to_string()),
+            ..HotkeyConfig::default()
+        };
+        let hotkeys = Hotkeys::from_config(&config, false).unwrap();
+        let action = handle_key_event(event, &mut modal, &hotkeys);
+        assert!(matches!(action, KeyAction::Handled));
+        assert_eq!(modal.gamma, 2.5);
+        assert_eq!(modal.current_notice(), Some("gamma 2.5"));
+    }
+
+    #[test]
+    fn test_handle_key_event_perf_when_bound() {
+        let mut modal = CameraModal::new();
+        assert!(!modal.perf);
+
+        // Unbound by default: Alt+F stays readline's forward-word
+        let event = KeyEvent::new(KeyCode::Char('f'), KeyModifiers::ALT);
+        let action = handle_key_event(event, &mut modal, &Hotkeys::default());
+        assert!(matches!(action, KeyAction::Forward(_)));
+
+        let config = HotkeyConfig {
+            toggle_perf: Some("f".to_string()),
+            ..HotkeyConfig::default()
+        };
+        let hotkeys = Hotkeys::from_config(&config, false).unwrap();
+        let action = handle_key_event(event, &mut modal, &hotkeys);
+        assert!(matches!(action, KeyAction::Handled));
+        assert!(modal.perf);
+        assert_eq!(modal.current_notice(), Some("perf on"));
+    }
+
+    #[test]
+    fn test_handle_key_event_alt_shift_arrows_nudge_position() {
+        use crate::terminal::ModalPosition;
+
+        let mut modal = CameraModal::new();
+        let nudge = KeyModifiers::ALT | KeyModifiers::SHIFT;
+
+        let event = KeyEvent::new(KeyCode::Left, nudge);
+        let action = handle_key_event(event, &mut modal, &Hotkeys::default());
+        assert!(matches!(action, KeyAction::Handled));
+        assert_eq!(modal.position, ModalPosition::Custom(950, 1000));
+
+        let event = KeyEvent::new(KeyCode::Right, nudge);
+        handle_key_event(event, &mut modal, &Hotkeys::default());
+        assert_eq!(modal.position, ModalPosition::BottomRight);
+
+        // Alt+arrow without Shift isn't a nudge
+        let event = KeyEvent::new(KeyCode::Left, KeyModifiers::ALT);
+        assert!(!matches!(
+            handle_key_event(event, &mut modal, &Hotkeys::default()),
+            KeyAction::Handled
+        ));
+        assert_eq!(modal.position, ModalPosition::BottomRight);
+    }
+
+    #[test]
+    fn test_handle_key_event_alt_q_quits_when_enabled() {
+        let mut modal = CameraModal::new();
+
+        let quit_enabled = Hotkeys::from_config(&HotkeyConfig::default(), true).unwrap();
+        let event = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::ALT);
+        let action = handle_key_event(event, &mut modal, &quit_enabled);
+        assert!(matches!(action, KeyAction::Quit));
+    }
+
+    #[test]
+    fn test_handle_key_event_alt_q_forwarded_when_disabled() {
+        let mut modal = CameraModal::new();
+
+        // Quit hotkey is opt-in, so Alt+Q reaches the shell by default
+        let event = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::ALT);
+        let action = handle_key_event(event, &mut modal, &Hotkeys::default());
+        match action {
+            KeyAction::Forward(bytes) => {
+                assert_eq!(bytes, vec![0x1B, b'q']);
+            }
+            _ => panic!("Expected Forward action for Alt+Q"),
+        }
+    }
+
     #[test]
     fn test_handle_key_event_other_alt_keys_forwarded() {
         let mut modal = CameraModal::new();
 
         // Alt+X (not a hotkey) should be forwarded to PTY
         let event = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::ALT);
-        let action = handle_key_event(event, &mut modal);
+        let action = handle_key_event(event, &mut modal, &Hotkeys::default());
         match action {
             KeyAction::Forward(bytes) => {
                 assert_eq!(bytes, vec![0x1B, b'x']); // ESC + x
@@ -319,7 +1316,7 @@ mod tests {
 
         // Regular 'a' (no modifier) should be forwarded
         let event = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE);
-        let action = handle_key_event(event, &mut modal);
+        let action = handle_key_event(event, &mut modal, &Hotkeys::default());
         match action {
             KeyAction::Forward(bytes) => {
                 assert_eq!(bytes, vec![b'a']);
@@ -334,7 +1331,7 @@ mod tests {
 
         // Ctrl+C should be forwarded (not our hotkey)
         let event = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
-        let action = handle_key_event(event, &mut modal);
+        let action = handle_key_event(event, &mut modal, &Hotkeys::default());
         match action {
             KeyAction::Forward(bytes) => {
                 assert_eq!(bytes, vec![0x03]); // ETX (Ctrl+C)
@@ -1,24 +1,84 @@
 //! space-recorder: TUI app that renders webcam as ASCII art overlay while hosting a shell.
 
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches};
 use std::io::Read;
+use std::time::Duration;
 use tokio::sync::mpsc;
 
-use space_recorder::camera::{CameraCapture, CameraSettings, Resolution};
-use space_recorder::cli::{self, Args, Command};
-use space_recorder::event_loop;
-use space_recorder::pty::{self, PtyHost, PtySize};
+use space_recorder::ascii::SnapshotSettings;
+use space_recorder::camera::{CameraCapture, CameraSettings, ResolutionFallback};
+use space_recorder::cli::{self, Args, Command, ExitCode};
+use space_recorder::config::{self, Config};
+use space_recorder::event_loop::{self, EventLoopConfig, SessionEnd};
+use space_recorder::input::Hotkeys;
+use space_recorder::pty::{self, PtyError, PtyHost, PtySize, SessionRecorder};
 use space_recorder::terminal::{self, CameraModal, StatusBar};
 
 #[tokio::main]
 async fn main() {
-    let args = Args::parse();
+    let matches = Args::command().get_matches();
+    let mut args = Args::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+
+    // Settings not given on the command line come from the config file
+    let mut config = None;
+    if matches!(args.command, None | Some(Command::AsciiSnapshot { .. })) {
+        let merged = args.load_config().and_then(|loaded| {
+            args.apply_config(&loaded, &matches)?;
+            Ok(loaded)
+        });
+        match merged {
+            Ok(loaded) => config = Some(loaded),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                ExitCode::ConfigError.exit();
+            }
+        }
+    }
 
     // Handle subcommands
     if let Some(cmd) = args.command {
         match cmd {
-            Command::ListCameras => {
-                cli::list_cameras();
+            Command::ListCameras { verbose } => {
+                cli::list_cameras(verbose);
+                return;
+            }
+            Command::ListPresets => {
+                cli::list_presets();
+                return;
+            }
+            Command::Completions { shell } => {
+                cli::write_completions(shell, &mut std::io::stdout());
+                return;
+            }
+            Command::ReportBug { output } => {
+                cli::report_bug(output.as_deref(), args.config.as_deref());
+                return;
+            }
+            Command::Version { json } => {
+                cli::print_version(json);
+                return;
+            }
+            Command::AsciiSnapshot { cols, rows } => {
+                let settings = SnapshotSettings {
+                    camera: CameraSettings {
+                        device: args.camera.clone(),
+                        resolution: args.resolution,
+                        resolution_fallback: ResolutionFallback::default(),
+                        fps: 15,
+                        mirror: args.mirror,
+                        rotation: args.rotate,
+                        grayscale: args.grayscale,
+                    },
+                    cols,
+                    rows,
+                    charset: args.charset.into(),
+                    invert: args.invert,
+                };
+                cli::ascii_snapshot(&settings);
+                return;
+            }
+            Command::Man { output } => {
+                cli::export_man_page(output.as_deref());
                 return;
             }
             Command::Config { action } => {
@@ -28,24 +88,52 @@ async fn main() {
         }
     }
 
+    let hotkey_config = config
+        .as_ref()
+        .map(|c| c.hotkeys.clone())
+        .unwrap_or_default();
+    let hotkeys = match Hotkeys::from_config(&hotkey_config, args.quit_hotkey) {
+        Ok(hotkeys) => hotkeys,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::ConfigError.exit();
+        }
+    };
+
     let shell = pty::select_shell(args.shell.as_deref());
 
-    // Get terminal size
+    // Get terminal size, less the status bar's row
     let (cols, rows) = crossterm::terminal::size().unwrap_or((80, 24));
-    let size = PtySize {
-        rows,
-        cols,
-        pixel_width: 0,
-        pixel_height: 0,
-    };
+    let size = PtySize::for_terminal(cols, rows, u16::from(!args.no_status));
+
+    // Let the shell know it's inside space-recorder; [pty.env] adds to that
+    let mut env = config
+        .as_ref()
+        .map(|c| c.pty.env.clone())
+        .unwrap_or_default();
+    env.entry("SPACE_RECORDER".to_string())
+        .or_insert_with(|| "1".to_string());
 
     // Spawn PTY with the shell
-    let pty = match PtyHost::spawn(&shell, size) {
+    let pty = match PtyHost::spawn_with_args(&shell, &args.shell_argv(), &env, size) {
         Ok(p) => p,
         Err(e) => {
             eprintln!("Failed to spawn shell: {}", e);
-            std::process::exit(1);
+            ExitCode::from(&e).exit();
+        }
+    };
+
+    let mut recorder = match args
+        .record_session
+        .as_deref()
+        .map(|path| SessionRecorder::create(path, size))
+    {
+        Some(Ok(recorder)) => Some(recorder),
+        Some(Err(e)) => {
+            eprintln!("Failed to start session recording: {}", e);
+            ExitCode::from(&e).exit();
         }
+        None => None,
     };
 
     // Split the PTY into reader (for background thread) and writer (for main thread)
@@ -55,19 +143,49 @@ async fn main() {
     let (tx, rx) = mpsc::channel::<Vec<u8>>(64);
 
     // Spawn background thread to read from PTY (blocking reads need their own thread)
-    let reader_handle = std::thread::spawn(move || {
-        pty_reader_thread(reader, tx);
-    });
+    let reader_handle = std::thread::spawn(move || pty_reader_thread(reader, tx));
 
     // Enter raw mode with automatic cleanup on exit/panic
     let _raw_guard = terminal::RawModeGuard::enter().expect("Failed to enter raw mode");
+    let _mouse_guard = if args.mouse {
+        match terminal::MouseCaptureGuard::enter() {
+            Ok(guard) => Some(guard),
+            Err(e) => {
+                eprintln!("Warning: Failed to enable mouse capture: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
 
     // Initialize camera modal state with CLI args
     let mut camera_modal = CameraModal::new();
     camera_modal.position = args.position.into();
     camera_modal.size = args.size.into();
-    camera_modal.charset = args.charset.into();
+    camera_modal.set_charset(args.charset.clone().into());
+    camera_modal.supersample = args.render_scale;
+    camera_modal.set_refresh_rate(args.modal_refresh_rate);
+    camera_modal.transparency_step = args.transparency_step;
     camera_modal.visible = !args.no_camera;
+    camera_modal.border = config.as_ref().is_some_and(|c| c.modal.border);
+    let color_depth = args
+        .color_depth
+        .map_or_else(terminal::ColorDepth::detect, Into::into);
+    camera_modal.color = color_depth != terminal::ColorDepth::Monochrome;
+    camera_modal.color_depth = color_depth;
+    camera_modal.invert = args.invert;
+    camera_modal.mirror = args.mirror;
+    camera_modal.perf = args.perf;
+    camera_modal.auto_levels = args.auto_levels;
+    if let Some(amount) = args.sharpen {
+        camera_modal.sharpen = true;
+        camera_modal.sharpen_amount = amount;
+    }
+    camera_modal.braille_threshold = args.braille_threshold;
+    if let Some(aspect) = args.char_aspect {
+        camera_modal.set_cell_aspect(aspect);
+    }
 
     // Initialize status bar (visible unless --no-status flag is set)
     let status_bar = StatusBar::with_visibility(!args.no_status);
@@ -75,10 +193,13 @@ async fn main() {
     // Initialize camera capture if camera is enabled
     let mut camera_capture: Option<CameraCapture> = if !args.no_camera {
         let settings = CameraSettings {
-            device_index: args.camera,
-            resolution: Resolution::MEDIUM, // 640x480 - good balance of speed and quality
-            fps: 15,                        // Lower FPS for ASCII rendering is fine
+            device: args.camera.clone(),
+            resolution: args.resolution,
+            resolution_fallback: ResolutionFallback::default(),
+            fps: 15, // Lower FPS for ASCII rendering is fine
             mirror: args.mirror,
+            rotation: args.rotate,
+            grayscale: args.grayscale,
         };
         match CameraCapture::open(settings) {
             Ok(mut cam) => {
@@ -98,6 +219,14 @@ async fn main() {
         None
     };
 
+    // Pick up config file edits while running
+    let config_path = args.config.clone().unwrap_or_else(config::default_path);
+    let reload = config.map(|current| event_loop::ConfigReload {
+        updates: Config::watch(config_path),
+        profile: args.profile.clone(),
+        current,
+    });
+
     // Run the async I/O loop
     let result = event_loop::run(
         pty_split,
@@ -105,44 +234,74 @@ async fn main() {
         &mut camera_modal,
         &status_bar,
         camera_capture.as_mut(),
-        args.invert,
+        &hotkeys,
+        reload,
+        recorder.as_mut(),
+        &EventLoopConfig {
+            refresh_interval: Duration::from_millis(args.pty_refresh_ms.into()),
+        },
     )
     .await;
 
     // Wait for reader thread to finish (it will exit when PTY closes)
-    let _ = reader_handle.join();
+    let read_result = reader_handle.join().unwrap_or(Ok(()));
+
+    // Flush the recording whichever way the session ended
+    let finished = recorder.map(SessionRecorder::finish);
+
+    // Restore the terminal before printing anything or exiting
+    drop(_mouse_guard);
+    drop(_raw_guard);
+
+    if let Some(Err(e)) = finished {
+        eprintln!("\nWarning: Failed to save session recording: {}", e);
+    }
 
     // Handle any errors from the I/O loop
-    if let Err(e) = result {
-        // Restore terminal before printing error
-        drop(_raw_guard);
-        eprintln!("\nError: {}", e);
-        std::process::exit(1);
+    match result {
+        Err(e) => {
+            eprintln!("\nError: {}", e);
+            ExitCode::PipelineFailure.exit();
+        }
+        // Exit with the shell's own code, so `--exec` works in scripts
+        Ok(SessionEnd::ShellExited(status)) => {
+            std::process::exit(i32::try_from(status.exit_code()).unwrap_or(1));
+        }
+        Ok(SessionEnd::OutputClosed) => {
+            if let Err(e) = read_result {
+                eprintln!("\nError: {}", e);
+                ExitCode::from(&e).exit();
+            }
+        }
+        Ok(SessionEnd::Quit) => {}
     }
 }
 
 /// Background thread that reads from PTY and sends data through channel.
 /// This runs in a separate thread because PTY reads are blocking.
-fn pty_reader_thread(mut reader: Box<dyn Read + Send>, tx: mpsc::Sender<Vec<u8>>) {
+///
+/// Any read error is returned; once the shell has exited that's just the
+/// PTY hanging up (EIO on Linux), which the event loop tells apart.
+fn pty_reader_thread(
+    mut reader: Box<dyn Read + Send>,
+    tx: mpsc::Sender<Vec<u8>>,
+) -> Result<(), PtyError> {
     let mut buf = [0u8; 4096];
 
     loop {
         match reader.read(&mut buf) {
             Ok(0) => {
                 // EOF - shell closed
-                break;
+                return Ok(());
             }
             Ok(n) => {
                 // Send the data to the main thread using blocking_send for sync context
                 // If the receiver is dropped, this will fail and we'll exit
                 if tx.blocking_send(buf[..n].to_vec()).is_err() {
-                    break;
+                    return Ok(());
                 }
             }
-            Err(_) => {
-                // I/O error - exit the thread
-                break;
-            }
+            Err(e) => return Err(PtyError::ReadFailed(e)),
         }
     }
 }
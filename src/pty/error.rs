@@ -13,6 +13,9 @@ pub enum PtyError {
     WriterFailed(Box<dyn std::error::Error + Send + Sync>),
     /// PTY I/O error
     IoError(std::io::Error),
+    /// Reading shell output failed while the shell was still running
+    /// (the shell exiting closes the PTY, which is not an error)
+    ReadFailed(std::io::Error),
     /// Failed to resize PTY
     ResizeFailed(Box<dyn std::error::Error + Send + Sync>),
 }
@@ -25,6 +28,7 @@ impl std::fmt::Display for PtyError {
             PtyError::ReaderFailed(e) => write!(f, "Failed to get PTY reader: {}", e),
             PtyError::WriterFailed(e) => write!(f, "Failed to get PTY writer: {}", e),
             PtyError::IoError(e) => write!(f, "PTY I/O error: {}", e),
+            PtyError::ReadFailed(e) => write!(f, "Failed to read shell output: {}", e),
             PtyError::ResizeFailed(e) => write!(f, "Failed to resize PTY: {}", e),
         }
     }
@@ -33,7 +37,7 @@ impl std::fmt::Display for PtyError {
 impl std::error::Error for PtyError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            PtyError::IoError(e) => Some(e),
+            PtyError::IoError(e) | PtyError::ReadFailed(e) => Some(e),
             _ => None,
         }
     }
@@ -1,7 +1,9 @@
 //! PTY host implementation - spawns user's shell and relays I/O
 
 use portable_pty::{Child, CommandBuilder, MasterPty, native_pty_system};
+use std::collections::HashMap;
 use std::io::{Read, Write};
+use std::time::{Duration, Instant};
 
 use super::error::PtyError;
 use super::size::PtySize;
@@ -28,6 +30,26 @@ impl PtyHost {
     /// # Returns
     /// A PtyHost instance with read/write handles to the shell
     pub fn spawn(shell: &str, size: PtySize) -> Result<Self, PtyError> {
+        Self::spawn_with_args(shell, &[], &HashMap::new(), size)
+    }
+
+    /// Spawn a shell in a PTY with extra arguments and environment
+    ///
+    /// The shell inherits this process's environment with `TERM` set to
+    /// xterm-256color; `env` is applied on top, so only the variables it
+    /// names (possibly including `TERM`) are overridden.
+    ///
+    /// # Arguments
+    /// * `shell` - Path to the shell to spawn (e.g., "/bin/zsh")
+    /// * `args` - Arguments after the shell's name (e.g., `["-l"]` or `["-c", "make"]`)
+    /// * `env` - Variables to add or override
+    /// * `size` - Initial terminal size
+    pub fn spawn_with_args(
+        shell: &str,
+        args: &[String],
+        env: &HashMap<String, String>,
+        size: PtySize,
+    ) -> Result<Self, PtyError> {
         let pty_system = native_pty_system();
 
         let pair = pty_system
@@ -35,7 +57,11 @@ impl PtyHost {
             .map_err(|e| PtyError::PtyCreationFailed(e.into()))?;
 
         let mut cmd = CommandBuilder::new(shell);
+        cmd.args(args);
         cmd.env("TERM", "xterm-256color");
+        for (key, value) in env {
+            cmd.env(key, value);
+        }
 
         let child = pair
             .slave
@@ -67,6 +93,12 @@ impl PtyHost {
             .map_err(|e| PtyError::ResizeFailed(e.into()))
     }
 
+    /// The PTY's current window size, as the shell sees it
+    pub fn size(&self) -> Result<PtySize, PtyError> {
+        let size = self.master.get_size().map_err(std::io::Error::other)?;
+        Ok(size.into())
+    }
+
     /// Write bytes to the shell's stdin
     pub fn write(&mut self, data: &[u8]) -> Result<usize, PtyError> {
         let n = self.writer.write(data)?;
@@ -84,6 +116,11 @@ impl PtyHost {
         Ok(self.child.try_wait()?)
     }
 
+    /// Block until the shell process exits, returning its status
+    pub fn wait(&mut self) -> Result<portable_pty::ExitStatus, PtyError> {
+        Ok(self.child.wait()?)
+    }
+
     /// Kill the shell process
     pub fn kill(&mut self) -> Result<(), PtyError> {
         Ok(self.child.kill()?)
@@ -137,6 +174,32 @@ impl PtyHostSplit {
         Ok(self.child.try_wait()?)
     }
 
+    /// Block until the shell process exits, returning its status
+    pub fn wait(&mut self) -> Result<portable_pty::ExitStatus, PtyError> {
+        Ok(self.child.wait()?)
+    }
+
+    /// Wait up to `timeout` for the shell to exit.
+    ///
+    /// The PTY's output closes as the shell exits, slightly before the
+    /// process can be reaped; this bridges that gap. `None` means the shell
+    /// is still running.
+    pub fn wait_timeout(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<Option<portable_pty::ExitStatus>, PtyError> {
+        let start = Instant::now();
+        loop {
+            if let Some(status) = self.try_wait()? {
+                return Ok(Some(status));
+            }
+            if start.elapsed() >= timeout {
+                return Ok(None);
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
     /// Kill the shell process
     pub fn kill(&mut self) -> Result<(), PtyError> {
         Ok(self.child.kill()?)
@@ -155,6 +218,12 @@ impl PtyHostSplit {
             .resize(size.into())
             .map_err(|e| PtyError::ResizeFailed(e.into()))
     }
+
+    /// The PTY's current window size, as the shell sees it
+    pub fn size(&self) -> Result<PtySize, PtyError> {
+        let size = self.master.get_size().map_err(std::io::Error::other)?;
+        Ok(size.into())
+    }
 }
 
 #[cfg(test)]
@@ -187,4 +256,116 @@ mod tests {
             std::thread::sleep(std::time::Duration::from_millis(50));
         }
     }
+
+    /// Read everything the child writes until it exits.
+    fn read_to_exit(pty: &mut PtyHost) -> String {
+        let mut output = Vec::new();
+        let mut buf = [0u8; 1024];
+        // Ends with EOF, or EIO on Linux once the child closes its side
+        while let Ok(n @ 1..) = pty.read(&mut buf) {
+            output.extend_from_slice(&buf[..n]);
+        }
+        String::from_utf8_lossy(&output).into_owned()
+    }
+
+    fn wait_for_exit(pty: &mut PtyHost) -> portable_pty::ExitStatus {
+        let start = std::time::Instant::now();
+        loop {
+            if let Some(status) = pty.try_wait().expect("Should check status") {
+                return status;
+            }
+            assert!(
+                start.elapsed() < std::time::Duration::from_secs(2),
+                "did not exit"
+            );
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+    }
+
+    #[test]
+    fn test_spawn_with_args_passes_argv() {
+        let args: Vec<String> = ["-c", "echo \"[$0|$1|$2]\"", "zero", "a b", "-x"]
+            .map(String::from)
+            .into();
+        let mut pty =
+            PtyHost::spawn_with_args("/bin/sh", &args, &HashMap::new(), PtySize::default())
+                .expect("Should spawn");
+
+        assert!(read_to_exit(&mut pty).contains("[zero|a b|-x]"));
+    }
+
+    #[test]
+    fn test_exec_false_exits_nonzero() {
+        let args = ["-c".to_string(), "false".to_string()];
+        let mut pty =
+            PtyHost::spawn_with_args("/bin/sh", &args, &HashMap::new(), PtySize::default())
+                .expect("Should spawn");
+
+        assert!(!wait_for_exit(&mut pty).success());
+    }
+
+    #[test]
+    fn test_spawn_with_env_adds_to_inherited() {
+        let env = HashMap::from([
+            ("SPACE_RECORDER_TEST".to_string(), "injected".to_string()),
+            ("TERM".to_string(), "dumb".to_string()),
+        ]);
+        let args = ["-c".to_string(), "env".to_string()];
+        let mut pty = PtyHost::spawn_with_args("/bin/sh", &args, &env, PtySize::default())
+            .expect("Should spawn");
+        let output = read_to_exit(&mut pty);
+
+        assert!(
+            output.contains("SPACE_RECORDER_TEST=injected"),
+            "{}",
+            output
+        );
+        assert!(output.contains("TERM=dumb"), "{}", output);
+        // Inherited variables are still there
+        assert!(output.contains("PATH="), "{}", output);
+    }
+
+    #[test]
+    fn test_resize_updates_winsize() {
+        let mut pty = PtyHost::spawn("/bin/cat", PtySize::default()).expect("Should spawn");
+        assert_eq!(pty.size().expect("Should read size").rows, 24);
+
+        pty.resize(PtySize::for_terminal(100, 31, 1))
+            .expect("Should resize");
+        let size = pty.size().expect("Should read size");
+        assert_eq!((size.cols, size.rows), (100, 30));
+
+        let _ = pty.kill();
+    }
+
+    #[test]
+    fn test_wait_reports_exit_code() {
+        let args = ["-c".to_string(), "exit 3".to_string()];
+        let mut pty =
+            PtyHost::spawn_with_args("/bin/sh", &args, &HashMap::new(), PtySize::default())
+                .expect("Should spawn");
+        let status = pty.wait().expect("Should wait");
+        assert_eq!(status.exit_code(), 3);
+        assert!(!status.success());
+    }
+
+    #[test]
+    fn test_split_wait_timeout() {
+        let args = ["-c".to_string(), "exit 3".to_string()];
+        let pty = PtyHost::spawn_with_args("/bin/sh", &args, &HashMap::new(), PtySize::default())
+            .expect("Should spawn");
+        let (_reader, mut split) = pty.split();
+        let status = split
+            .wait_timeout(Duration::from_secs(2))
+            .expect("Should wait")
+            .expect("Shell should have exited");
+        assert_eq!(status.exit_code(), 3);
+
+        // A shell that keeps running times out instead
+        let pty = PtyHost::spawn("/bin/cat", PtySize::default()).expect("Should spawn");
+        let (_reader, mut split) = pty.split();
+        let waited = split.wait_timeout(Duration::from_millis(50));
+        assert!(waited.expect("Should wait").is_none());
+        let _ = split.kill();
+    }
 }
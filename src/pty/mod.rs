@@ -9,13 +9,16 @@
 //! - [`size`] - Terminal size configuration
 //! - [`host`] - PTY host implementation
 //! - [`shell`] - Shell selection utilities
+//! - [`recorder`] - asciicast session recording
 
 mod error;
 mod host;
+mod recorder;
 mod shell;
 mod size;
 
 pub use error::PtyError;
 pub use host::{PtyHost, PtyHostSplit};
+pub use recorder::SessionRecorder;
 pub use shell::{default_shell, select_shell};
 pub use size::PtySize;
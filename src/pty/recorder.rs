@@ -0,0 +1,217 @@
+//! Session recording in the asciicast v2 format
+//!
+//! A `.cast` file is JSON Lines: a header object with the terminal size,
+//! then one `[time, code, data]` array per event, where `time` is seconds
+//! since the session started. `asciinema play` replays it.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use super::error::PtyError;
+use super::size::PtySize;
+
+/// Writes PTY output as asciicast v2 events.
+pub struct SessionRecorder<W: Write = BufWriter<File>> {
+    out: W,
+    start: Instant,
+    /// Time of the last event, so timestamps never go backwards
+    last: f64,
+    /// Trailing bytes of a UTF-8 sequence split across output chunks
+    pending: Vec<u8>,
+}
+
+impl SessionRecorder {
+    /// Create (or truncate) the `.cast` file at `path` for a `size` terminal.
+    pub fn create(path: &Path, size: PtySize) -> Result<Self, PtyError> {
+        let file = File::create(path)?;
+        Ok(Self::new(BufWriter::new(file), size)?)
+    }
+}
+
+impl<W: Write> SessionRecorder<W> {
+    /// Start a recording on `out`, writing the header line.
+    pub fn new(mut out: W, size: PtySize) -> io::Result<Self> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        writeln!(
+            out,
+            r#"{{"version": 2, "width": {}, "height": {}, "timestamp": {}, "env": {{"TERM": "xterm-256color"}}}}"#,
+            size.cols, size.rows, timestamp
+        )?;
+        Ok(Self {
+            out,
+            start: Instant::now(),
+            last: 0.0,
+            pending: Vec::new(),
+        })
+    }
+
+    /// Record a chunk of shell output.
+    pub fn output(&mut self, data: &[u8]) -> io::Result<()> {
+        self.pending.extend_from_slice(data);
+        let text = take_utf8(&mut self.pending);
+        if text.is_empty() {
+            return Ok(());
+        }
+        self.event("o", &text)
+    }
+
+    /// Record the terminal being resized.
+    pub fn resize(&mut self, size: PtySize) -> io::Result<()> {
+        self.event("r", &format!("{}x{}", size.cols, size.rows))
+    }
+
+    /// Flush everything recorded so far and hand back the writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        if !self.pending.is_empty() {
+            let rest = String::from_utf8_lossy(&self.pending).into_owned();
+            self.pending.clear();
+            self.event("o", &rest)?;
+        }
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    fn event(&mut self, code: &str, data: &str) -> io::Result<()> {
+        self.last = self.start.elapsed().as_secs_f64().max(self.last);
+        writeln!(
+            self.out,
+            "[{:.6}, \"{}\", \"{}\"]",
+            self.last,
+            code,
+            escape_json(data)
+        )
+    }
+}
+
+/// Decode the complete UTF-8 prefix of `bytes`, leaving an unfinished
+/// trailing sequence in place for the next chunk. Invalid bytes become U+FFFD.
+fn take_utf8(bytes: &mut Vec<u8>) -> String {
+    let mut text = String::new();
+    let mut rest = bytes.as_slice();
+    loop {
+        match std::str::from_utf8(rest) {
+            Ok(valid) => {
+                text.push_str(valid);
+                rest = &[];
+                break;
+            }
+            Err(e) => {
+                let (valid, after) = rest.split_at(e.valid_up_to());
+                // Checked by from_utf8 above
+                text.push_str(std::str::from_utf8(valid).unwrap_or_default());
+                match e.error_len() {
+                    Some(len) => {
+                        text.push(char::REPLACEMENT_CHARACTER);
+                        rest = &after[len..];
+                    }
+                    None => {
+                        rest = after;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    let keep = rest.len();
+    bytes.drain(..bytes.len() - keep);
+    text
+}
+
+fn escape_json(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c < ' ' || c == '\u{7f}' => {
+                escaped.push_str(&format!("\\u{:04x}", c as u32));
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(chunks: &[&[u8]]) -> Vec<String> {
+        let size = PtySize {
+            rows: 24,
+            cols: 80,
+            ..PtySize::default()
+        };
+        let mut recorder = SessionRecorder::new(Vec::new(), size).unwrap();
+        for chunk in chunks {
+            recorder.output(chunk).unwrap();
+        }
+        let out = recorder.finish().unwrap();
+        String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Seconds value of an event line like `[0.123456, "o", "..."]`.
+    fn time(event: &str) -> f64 {
+        let end = event.find(',').unwrap();
+        event[1..end].parse().unwrap()
+    }
+
+    #[test]
+    fn test_recording_has_header_and_monotonic_events() {
+        let lines = record(&[b"$ echo hi\r\n", b"hi\r\n", b"$ "]);
+
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with(r#"{"version": 2, "width": 80, "height": 24, "timestamp": "#));
+        assert!(lines[0].ends_with(r#""env": {"TERM": "xterm-256color"}}"#));
+
+        assert_eq!(
+            lines[1].split_once(", ").unwrap().1,
+            r#""o", "$ echo hi\r\n"]"#
+        );
+        let times: Vec<f64> = lines[1..].iter().map(|l| time(l)).collect();
+        assert!(times.windows(2).all(|w| w[0] <= w[1]), "{:?}", times);
+    }
+
+    #[test]
+    fn test_output_is_json_escaped() {
+        let lines = record(&[b"\x1b[31m\"red\"\\\x1b[0m"]);
+        assert_eq!(
+            lines[1].split_once(", ").unwrap().1,
+            r#""o", "\u001b[31m\"red\"\\\u001b[0m"]"#
+        );
+    }
+
+    #[test]
+    fn test_utf8_split_across_chunks() {
+        // "é" is 0xC3 0xA9; the first chunk ends mid-character
+        let lines = record(&[b"caf\xc3", b"\xa9!"]);
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].ends_with(r#""o", "caf"]"#));
+        assert!(lines[2].ends_with(r#""o", "é!"]"#));
+    }
+
+    #[test]
+    fn test_resize_event() {
+        let mut recorder = SessionRecorder::new(Vec::new(), PtySize::default()).unwrap();
+        recorder
+            .resize(PtySize {
+                rows: 40,
+                cols: 120,
+                ..PtySize::default()
+            })
+            .unwrap();
+        let out = String::from_utf8(recorder.finish().unwrap()).unwrap();
+        assert!(out.lines().nth(1).unwrap().ends_with(r#""r", "120x40"]"#));
+    }
+}
@@ -22,6 +22,18 @@ impl Default for PtySize {
     }
 }
 
+impl PtySize {
+    /// Size for a `cols` x `rows` terminal with `reserved_rows` kept for the
+    /// app's own use (e.g. the status bar), leaving the shell at least one row.
+    pub fn for_terminal(cols: u16, rows: u16, reserved_rows: u16) -> Self {
+        Self {
+            rows: rows.saturating_sub(reserved_rows).max(1),
+            cols,
+            ..Self::default()
+        }
+    }
+}
+
 impl From<PtySize> for PortablePtySize {
     fn from(size: PtySize) -> Self {
         PortablePtySize {
@@ -33,6 +45,17 @@ impl From<PtySize> for PortablePtySize {
     }
 }
 
+impl From<PortablePtySize> for PtySize {
+    fn from(size: PortablePtySize) -> Self {
+        PtySize {
+            rows: size.rows,
+            cols: size.cols,
+            pixel_width: size.pixel_width,
+            pixel_height: size.pixel_height,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -43,4 +66,11 @@ mod tests {
         assert_eq!(size.rows, 24);
         assert_eq!(size.cols, 80);
     }
+
+    #[test]
+    fn test_pty_size_reserves_rows() {
+        let size = PtySize::for_terminal(120, 40, 1);
+        assert_eq!((size.cols, size.rows), (120, 39));
+        assert_eq!(PtySize::for_terminal(80, 1, 1).rows, 1);
+    }
 }
@@ -2,9 +2,20 @@
 //!
 //! This module handles rendering the ASCII camera overlay on top of
 //! the terminal without disturbing the underlying PTY output.
+//!
+//! `DiffRenderer` keeps the last frame's cells and only rewrites the ones
+//! that changed, which matters over slow links like SSH.
+//!
+//! Colors are written at the modal's `ColorDepth`: 24-bit, the nearest
+//! 256-palette entry, or not at all.
+//!
+//! `render_overlay_at` draws a bare `AsciiFrame` at an absolute screen
+//! position, for floating the camera over a session that isn't ours.
+//!
+//! `render_scrollback` draws a scrolled-back window of shell output.
 
-use crate::ascii;
-use crate::terminal::{CameraModal, ModalPosition, ModalSize};
+use crate::terminal::{AsciiFrame, CameraModal, CellColor, ModalPosition, ModalSize, PtyBuffer};
+pub use crate::terminal::{ColorDepth, rgb_to_ansi256};
 use ratatui::layout::Rect;
 use std::io::Write;
 
@@ -30,10 +41,7 @@ pub fn clear_modal_area(
         visible: true,
         position,
         size,
-        charset: ascii::CharSet::Standard,
-        border: false,
-        frame: None,
-        transparency: 80,
+        ..CameraModal::new()
     };
 
     let modal_rect = temp_modal.calculate_rect(container);
@@ -61,205 +69,682 @@ pub fn clear_modal_area(
     Ok(())
 }
 
-/// Render the camera modal overlay on top of the terminal.
+/// One drawn character cell of the overlay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    /// Glyph (always one column wide)
+    pub ch: char,
+    /// Foreground color, or the terminal's default
+    pub fg: Option<CellColor>,
+    /// Reverse video (used for notices)
+    pub reverse: bool,
+}
+
+impl Cell {
+    fn plain(ch: char) -> Self {
+        Self {
+            ch,
+            fg: None,
+            reverse: false,
+        }
+    }
+}
+
+/// The overlay's cells at a screen position (0-based).
 ///
-/// Uses ANSI escape codes to position and draw the overlay without
-/// disturbing the underlying PTY output. This approach:
-/// 1. Saves cursor position
-/// 2. Moves to modal location
-/// 3. Draws each line of the ASCII frame
-/// 4. Restores cursor position
-pub fn render_camera_overlay(
-    stdout: &mut std::io::Stdout,
-    modal: &CameraModal,
-    term_cols: u16,
-    term_rows: u16,
-) -> std::io::Result<()> {
-    let Some(ref frame) = modal.frame else {
-        return Ok(());
-    };
+/// `None` cells are transparent: they're never drawn, so whatever is on
+/// screen there shows through.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CellGrid {
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+    /// How cell colors are written
+    pub depth: ColorDepth,
+    cells: Vec<Option<Cell>>,
+}
 
-    // Calculate modal position
-    let container = Rect {
-        x: 0,
-        y: 0,
-        width: term_cols,
-        height: term_rows,
-    };
-    let modal_rect = modal.calculate_rect(container);
+impl CellGrid {
+    /// An all-transparent grid covering `rect`.
+    pub fn new(rect: Rect) -> Self {
+        Self {
+            x: rect.x,
+            y: rect.y,
+            width: rect.width,
+            height: rect.height,
+            depth: ColorDepth::default(),
+            cells: vec![None; rect.width as usize * rect.height as usize],
+        }
+    }
+
+    /// The cell at grid column `col`, row `row`.
+    pub fn get(&self, col: u16, row: u16) -> Option<Cell> {
+        if col >= self.width || row >= self.height {
+            return None;
+        }
+        self.cells[row as usize * self.width as usize + col as usize]
+    }
+
+    /// Set the cell at `col`, `row`; out-of-bounds cells are ignored.
+    pub fn set(&mut self, col: u16, row: u16, cell: Option<Cell>) {
+        if col < self.width && row < self.height {
+            self.cells[row as usize * self.width as usize + col as usize] = cell;
+        }
+    }
+
+    /// The cells of `modal` (border, frame, and notice) in a `term_cols` x
+    /// `term_rows` area, or `None` without a frame.
+    pub fn from_modal(modal: &CameraModal, term_cols: u16, term_rows: u16) -> Option<Self> {
+        let frame = modal.frame.as_ref()?;
+        let modal_rect = modal.calculate_rect(Rect::new(0, 0, term_cols, term_rows));
+        let mut grid = Self::new(modal_rect);
+        grid.depth = modal.effective_color_depth();
+        let (width, height) = (modal_rect.width, modal_rect.height);
+        let inset = u16::from(modal.border);
+
+        if modal.border && width >= 2 && height >= 2 {
+            for col in 1..width - 1 {
+                grid.set(col, 0, Some(Cell::plain('─')));
+                grid.set(col, height - 1, Some(Cell::plain('─')));
+            }
+            for row in 1..height - 1 {
+                grid.set(0, row, Some(Cell::plain('│')));
+                grid.set(width - 1, row, Some(Cell::plain('│')));
+            }
+            grid.set(0, 0, Some(Cell::plain('┌')));
+            grid.set(width - 1, 0, Some(Cell::plain('┐')));
+            grid.set(0, height - 1, Some(Cell::plain('└')));
+            grid.set(width - 1, height - 1, Some(Cell::plain('┘')));
+        }
+
+        let inner_width = width.saturating_sub(2 * inset);
+        let inner_height = height.saturating_sub(2 * inset);
+        let threshold = brightness_threshold(modal.transparency);
+        for row in 0..frame.height.min(inner_height) {
+            for col in 0..frame.width.min(inner_width) {
+                let cell = frame_cell(frame, col, row, grid.depth, threshold);
+                grid.set(col + inset, row + inset, cell);
+            }
+        }
+
+        if let Some(notice) = modal.current_notice() {
+            for (col, ch) in notice.chars().take(inner_width as usize).enumerate() {
+                let cell = Cell {
+                    reverse: true,
+                    ..Cell::plain(ch)
+                };
+                grid.set(col as u16 + inset, inset, Some(cell));
+            }
+        }
+
+        Some(grid)
+    }
+}
+
+/// Escape sequences that turn the screen showing `prev` into `next`.
+///
+/// Only cells that differ are written: a cursor move where the changed cells
+/// aren't contiguous, a color change where the color differs from the last
+/// glyph written, then the glyph. If the grids cover different areas (or
+/// color depths), every drawn cell of `next` is written. Returns an empty
+/// string when nothing changed.
+pub fn render_diff(prev: &CellGrid, next: &CellGrid) -> String {
+    let same_area = (prev.x, prev.y, prev.width, prev.height, prev.depth)
+        == (next.x, next.y, next.width, next.height, next.depth);
 
-    // Build the output string with ANSI escape codes
     let mut output = String::new();
+    push_cells(&mut output, same_area.then_some(prev), next, None);
 
-    // Save cursor position (using DEC sequence - different slot than SCO \x1b[s)
-    output.push_str("\x1b7");
+    if output.is_empty() {
+        return output;
+    }
+    // Save the cursor (DEC slot) and hide it while drawing to reduce flicker
+    format!("\x1b7\x1b[?25l{}\x1b[0m\x1b[?25h\x1b8", output)
+}
 
-    // Hide cursor during rendering to reduce flicker
-    output.push_str("\x1b[?25l");
+/// Append the escapes that draw `next`'s cells, skipping any equal in `prev`.
+///
+/// Transparent and skipped cells are never written: the cursor steps over
+/// them (`ESC[nC`) within a row and jumps (`ESC[row;colH`) between rows, so
+/// whatever is on screen there stays. A cell drawn in `prev` that turns
+/// transparent is blanked with a space, as `clear_modal_area` does. `pen`
+/// is the style the terminal is known to be in, or `None` if unknown.
+fn push_cells(
+    output: &mut String,
+    prev: Option<&CellGrid>,
+    next: &CellGrid,
+    mut pen: Option<(Option<CellColor>, bool)>,
+) {
+    let mut cursor: Option<(u16, u16)> = None;
+    for row in 0..next.height {
+        for col in 0..next.width {
+            let cell = match (next.get(col, row), prev.and_then(|p| p.get(col, row))) {
+                (Some(cell), _) => cell,
+                (None, Some(_)) => Cell::plain(' '),
+                (None, None) => continue,
+            };
+            if prev.is_some_and(|prev| prev.get(col, row) == Some(cell)) {
+                continue;
+            }
 
-    // Calculate inner area (accounting for border if present)
-    let inner_x = if modal.border {
-        modal_rect.x + 1
-    } else {
-        modal_rect.x
-    };
-    let inner_y = if modal.border {
-        modal_rect.y + 1
-    } else {
-        modal_rect.y
-    };
-    let inner_width = if modal.border {
-        modal_rect.width.saturating_sub(2)
-    } else {
-        modal_rect.width
-    };
-    let inner_height = if modal.border {
-        modal_rect.height.saturating_sub(2)
-    } else {
-        modal_rect.height
+            let (x, y) = (next.x + col, next.y + row);
+            match cursor {
+                Some(at) if at == (x, y) => {}
+                Some((at_x, at_y)) if at_y == y && at_x < x => {
+                    output.push_str(&format!("\x1b[{}C", x - at_x));
+                }
+                _ => output.push_str(&format!("\x1b[{};{}H", y + 1, x + 1)),
+            }
+            if pen != Some((cell.fg, cell.reverse)) {
+                push_style(output, &cell, pen, next.depth);
+                pen = Some((cell.fg, cell.reverse));
+            }
+            output.push(cell.ch);
+            cursor = Some((x + 1, y));
+        }
+    }
+}
+
+/// Write the SGR codes to switch from `pen` (unknown if `None`) to `cell`'s style.
+fn push_style(
+    output: &mut String,
+    cell: &Cell,
+    pen: Option<(Option<CellColor>, bool)>,
+    depth: ColorDepth,
+) {
+    let (old_fg, old_reverse) = match pen {
+        Some((fg, reverse)) => (Some(fg), Some(reverse)),
+        None => (None, None),
     };
+    if old_fg != Some(cell.fg) {
+        match cell.fg {
+            Some(c) => push_fg(output, c, depth),
+            None => output.push_str("\x1b[39m"),
+        }
+    }
+    if old_reverse != Some(cell.reverse) {
+        output.push_str(if cell.reverse { "\x1b[7m" } else { "\x1b[27m" });
+    }
+}
+
+/// Renders the overlay by diffing against the previous frame.
+///
+/// The previous frame is only a guess at what's on screen: call
+/// `invalidate` whenever the overlay area is cleared or drawn over (shell
+/// output, resize) so the next frame is drawn in full.
+#[derive(Debug, Default)]
+pub struct DiffRenderer {
+    prev: Option<CellGrid>,
+}
+
+impl DiffRenderer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget the previous frame, so the next one is drawn in full.
+    pub fn invalidate(&mut self) {
+        self.prev = None;
+    }
+
+    /// Escape sequences for `next`, remembering it for the next call.
+    pub fn render(&mut self, next: CellGrid) -> String {
+        let output = render_diff(self.prev.as_ref().unwrap_or(&CellGrid::default()), &next);
+        self.prev = Some(next);
+        output
+    }
 
-    // Draw border if enabled
-    if modal.border {
-        render_border(&mut output, &modal_rect, inner_width, inner_height, inner_y);
+    /// Draw `modal`'s changed cells, writing nothing for an unchanged frame.
+    pub fn render_modal(
+        &mut self,
+        stdout: &mut std::io::Stdout,
+        modal: &CameraModal,
+        term_cols: u16,
+        term_rows: u16,
+    ) -> std::io::Result<()> {
+        let Some(grid) = CellGrid::from_modal(modal, term_cols, term_rows) else {
+            return Ok(());
+        };
+        let output = self.render(grid);
+        if !output.is_empty() {
+            stdout.write_all(output.as_bytes())?;
+            stdout.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Write the SGR foreground code for `color` at `depth` (nothing for monochrome).
+fn push_fg(output: &mut String, color: CellColor, depth: ColorDepth) {
+    match depth {
+        // ANSI true color (24-bit): ESC[38;2;R;G;Bm for foreground
+        ColorDepth::TrueColor => {
+            output.push_str(&format!("\x1b[38;2;{};{};{}m", color.r, color.g, color.b));
+        }
+        ColorDepth::Indexed256 => {
+            let index = rgb_to_ansi256(color.r, color.g, color.b);
+            output.push_str(&format!("\x1b[38;5;{}m", index));
+        }
+        ColorDepth::Monochrome => {}
     }
+}
 
-    // Draw ASCII frame content line by line with colors
-    render_frame_content(
-        &mut output,
-        frame,
-        modal.transparency,
-        inner_x,
-        inner_y,
-        inner_width,
-        inner_height,
+/// How `render_overlay_at` draws a frame.
+#[derive(Debug, Clone, Copy)]
+pub struct OverlayOptions {
+    /// How the frame's colors (if any) are written
+    pub depth: ColorDepth,
+    /// Save the cursor before drawing and restore it after, so the
+    /// underlying program's cursor doesn't move
+    pub save_cursor: bool,
+    /// Transparency percentage, as `CameraModal::transparency`: cells it
+    /// makes transparent are skipped so the screen underneath shows through.
+    /// `None` draws every cell.
+    pub transparency: Option<u8>,
+}
+
+impl Default for OverlayOptions {
+    fn default() -> Self {
+        Self {
+            depth: ColorDepth::TrueColor,
+            save_cursor: true,
+            transparency: None,
+        }
+    }
+}
+
+/// Escape sequences that draw `frame` with its top-left corner at screen
+/// column `col`, row `row` (1-based, as in `ESC[{row};{col}H`).
+///
+/// Each line starts with its own cursor move, so the block floats over
+/// whatever is on screen without relying on line wrapping. Cells are written
+/// as `render_diff` writes them, starting from the default style.
+pub fn render_overlay_at(
+    frame: &AsciiFrame,
+    col: u16,
+    row: u16,
+    options: &OverlayOptions,
+) -> String {
+    let area = Rect::new(
+        col.saturating_sub(1),
+        row.saturating_sub(1),
+        frame.width,
+        frame.height,
     );
+    let mut grid = CellGrid::new(area);
+    grid.depth = options.depth;
+    let threshold = options.transparency.map_or(0, brightness_threshold);
+    for y in 0..grid.height {
+        for x in 0..grid.width {
+            grid.set(x, y, frame_cell(frame, x, y, options.depth, threshold));
+        }
+    }
 
-    // Reset colors and show cursor
-    output.push_str("\x1b[0m"); // Reset all attributes
-    output.push_str("\x1b[?25h");
+    let mut output = String::new();
+    if options.save_cursor {
+        output.push_str("\x1b7");
+    }
+    push_cells(&mut output, None, &grid, Some((None, false)));
+    output.push_str("\x1b[0m");
+    if options.save_cursor {
+        output.push_str("\x1b8");
+    }
+    output
+}
 
-    // Restore cursor position (using DEC sequence to match save)
-    output.push_str("\x1b8");
+/// Keep the bottom row of a `term_rows`-tall terminal for the status bar.
+///
+/// Sets the scroll region to the rows above it, so shell output scrolls
+/// without pushing the status bar off screen.
+pub fn reserve_status_row(stdout: &mut std::io::Stdout, term_rows: u16) -> std::io::Result<()> {
+    let region = term_rows.saturating_sub(1).max(1);
+    write!(stdout, "\x1b7\x1b[1;{}r\x1b8", region)?;
+    stdout.flush()
+}
 
-    // Write all at once for efficiency
-    stdout.write_all(output.as_bytes())?;
-    stdout.flush()?;
+/// Reset the scroll region and blank the status row (on exit).
+pub fn release_status_row(stdout: &mut std::io::Stdout, term_rows: u16) -> std::io::Result<()> {
+    write!(stdout, "\x1b7\x1b[r\x1b[{};1H\x1b[2K\x1b8", term_rows)?;
+    stdout.flush()
+}
 
-    Ok(())
+/// Draw `text` in reverse video across the bottom row, padded or cut to `term_cols`.
+pub fn render_status_line(
+    stdout: &mut std::io::Stdout,
+    text: &str,
+    term_cols: u16,
+    term_rows: u16,
+) -> std::io::Result<()> {
+    let text: String = text
+        .chars()
+        .chain(std::iter::repeat(' '))
+        .take(term_cols as usize)
+        .collect();
+    write!(
+        stdout,
+        "\x1b7\x1b[{};1H\x1b[0m\x1b[7m{}\x1b[0m\x1b8",
+        term_rows, text
+    )?;
+    stdout.flush()
 }
 
-/// Render the modal border using box-drawing characters.
-fn render_border(
-    output: &mut String,
-    modal_rect: &Rect,
-    inner_width: u16,
-    inner_height: u16,
-    inner_y: u16,
-) {
-    // Top border
-    output.push_str(&format!("\x1b[{};{}H", modal_rect.y + 1, modal_rect.x + 1));
-    output.push('┌');
-    for _ in 0..inner_width {
-        output.push('─');
-    }
-    output.push('┐');
-
-    // Bottom border
-    output.push_str(&format!(
-        "\x1b[{};{}H",
-        modal_rect.y + modal_rect.height,
-        modal_rect.x + 1
-    ));
-    output.push('└');
-    for _ in 0..inner_width {
-        output.push('─');
-    }
-    output.push('┘');
-
-    // Side borders
-    for row in 0..inner_height {
-        let y = inner_y + row + 1;
-        // Left border
-        output.push_str(&format!("\x1b[{};{}H│", y, modal_rect.x + 1));
-        // Right border
-        output.push_str(&format!("\x1b[{};{}H│", y, modal_rect.x + modal_rect.width));
+/// Draw the scrolled-back window of `buffer` over the top `term_rows` rows.
+///
+/// Autowrap is off while drawing, so long lines are cut at the right edge.
+/// A line shows what followed its last carriage return, as the terminal did.
+pub fn render_scrollback(buffer: &PtyBuffer, term_rows: u16) -> String {
+    let content = buffer.visible_content(term_rows as usize);
+    let mut lines = content.split('\n');
+    let mut output = String::from("\x1b[?25l\x1b[?7l\x1b[0m");
+    for row in 1..=term_rows {
+        output.push_str(&format!("\x1b[{};1H\x1b[2K", row));
+        if let Some(line) = lines.next() {
+            let line = line.trim_end_matches('\r');
+            output.push_str(line.rsplit('\r').next().unwrap_or(line));
+            output.push_str("\x1b[0m");
+        }
     }
+    output.push_str("\x1b[?7h");
+    output
 }
 
-/// Render the ASCII frame content with transparency support.
+/// Brightness (sum of RGB) below which a colored cell is left transparent.
 ///
-/// Skips pixels below the brightness threshold to let terminal content show through.
-fn render_frame_content(
-    output: &mut String,
-    frame: &crate::terminal::AsciiFrame,
-    transparency: u8,
-    inner_x: u16,
-    inner_y: u16,
-    inner_width: u16,
-    inner_height: u16,
-) {
-    let lines: Vec<&[char]> = frame.chars.chunks(frame.width as usize).collect();
-    let has_colors = frame.colors.is_some();
-    let colors = frame.colors.as_ref();
-
-    // Calculate brightness threshold from transparency setting
-    // Higher transparency = lower threshold = more pixels skipped
-    // transparency=0 -> threshold=765 (nothing transparent, draw everything)
-    // transparency=80 -> threshold=153 (only draw very dark pixels)
-    // transparency=100 -> threshold=0 (everything transparent)
+/// 765 (every color but white) at transparency 0, 153 at the default 80, and
+/// 0 (nothing) at 100.
+fn brightness_threshold(transparency: u8) -> u16 {
     let max_brightness: u16 = 765; // 255 * 3
-    let brightness_threshold = (max_brightness as u32 * (100 - transparency as u32) / 100) as u16;
-
-    for (row, line) in lines.iter().enumerate().take(inner_height as usize) {
-        let y = inner_y + row as u16 + 1; // +1 for 1-based ANSI coordinates
-        let base_x = inner_x + 1; // +1 for 1-based ANSI coordinates
-
-        let chars_to_write = line.len().min(inner_width as usize);
-        let row_start = row * frame.width as usize;
-
-        // Track if we need to reposition cursor (after skipping transparent pixels)
-        let mut need_reposition = true;
-
-        for (col, &c) in line[..chars_to_write].iter().enumerate() {
-            let mut is_transparent = false;
-
-            if has_colors {
-                if let Some(colors) = colors {
-                    let idx = row_start + col;
-                    if idx < colors.len() {
-                        let color = &colors[idx];
-                        let brightness = color.r as u16 + color.g as u16 + color.b as u16;
-
-                        if brightness < brightness_threshold {
-                            // Skip this pixel - it's too dark, let background show
-                            is_transparent = true;
-                            need_reposition = true;
-                        } else {
-                            // Position cursor if needed
-                            if need_reposition {
-                                output.push_str(&format!("\x1b[{};{}H", y, base_x + col as u16));
-                                need_reposition = false;
-                            }
-                            // ANSI true color (24-bit): ESC[38;2;R;G;Bm for foreground
-                            output.push_str(&format!(
-                                "\x1b[38;2;{};{};{}m",
-                                color.r, color.g, color.b
-                            ));
-                        }
-                    }
-                }
-            } else {
-                // No colors - position if needed
-                if need_reposition {
-                    output.push_str(&format!("\x1b[{};{}H", y, base_x + col as u16));
-                    need_reposition = false;
-                }
-            }
+    (max_brightness as u32 * (100 - transparency.min(100) as u32) / 100) as u16
+}
+
+/// Whether a cell is left undrawn so the terminal shows through.
+///
+/// Colored cells darker than `threshold` (from `brightness_threshold`) are.
+/// Frames without colors have only the glyph to go on, so a blank cell
+/// counts as black.
+fn is_transparent(ch: char, color: Option<&CellColor>, threshold: u16) -> bool {
+    let brightness = match color {
+        Some(c) => c.r as u16 + c.g as u16 + c.b as u16,
+        None if ch == ' ' => 0,
+        None => return false,
+    };
+    brightness < threshold
+}
+
+/// The overlay cell for `frame`'s `col`, `row`, or `None` if it's transparent.
+fn frame_cell(
+    frame: &AsciiFrame,
+    col: u16,
+    row: u16,
+    depth: ColorDepth,
+    threshold: u16,
+) -> Option<Cell> {
+    let idx = row as usize * frame.width as usize + col as usize;
+    let ch = *frame.chars.get(idx)?;
+    let fg = match frame.colors.as_ref() {
+        Some(colors) => Some(colors.get(idx)?),
+        None => None,
+    };
+    if is_transparent(ch, fg, threshold) {
+        return None;
+    }
+    Some(Cell {
+        fg: fg.filter(|_| depth != ColorDepth::Monochrome).copied(),
+        ..Cell::plain(ch)
+    })
+}
 
-            if !is_transparent {
-                output.push(c);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(text: &[&str]) -> CellGrid {
+        let width = text[0].chars().count() as u16;
+        let mut grid = CellGrid::new(Rect::new(10, 5, width, text.len() as u16));
+        for (row, line) in text.iter().enumerate() {
+            for (col, ch) in line.chars().enumerate() {
+                grid.set(col as u16, row as u16, Some(Cell::plain(ch)));
             }
         }
+        grid
+    }
+
+    #[test]
+    fn test_scrollback_fills_rows_without_wrapping() {
+        let mut buffer = PtyBuffer::new();
+        buffer.append_str("one\r\n50%\r100%\r\nthree\r\n");
+        buffer.scroll_up(1);
+
+        let output = render_scrollback(&buffer, 3);
+        assert!(output.starts_with("\x1b[?25l\x1b[?7l"));
+        assert!(output.contains("\x1b[1;1H\x1b[2Kone\x1b[0m"));
+        assert!(output.contains("\x1b[2;1H\x1b[2K100%\x1b[0m"));
+        assert!(output.ends_with("\x1b[3;1H\x1b[2K\x1b[?7h"));
+        assert!(!output.contains("three"));
+    }
+
+    #[test]
+    fn test_overlay_at_positions_each_line() {
+        let chars = "abcdefghi".chars().collect();
+        let frame = AsciiFrame::from_chars(chars, 3, 3);
+        let options = OverlayOptions {
+            save_cursor: false,
+            ..OverlayOptions::default()
+        };
+
+        assert_eq!(
+            render_overlay_at(&frame, 10, 5, &options),
+            "\x1b[5;10Habc\x1b[6;10Hdef\x1b[7;10Hghi\x1b[0m"
+        );
+
+        let saved = render_overlay_at(&frame, 10, 5, &OverlayOptions::default());
+        assert!(saved.starts_with("\x1b7\x1b[5;10Habc"));
+        assert!(saved.ends_with("\x1b[0m\x1b8"));
+    }
+
+    #[test]
+    fn test_overlay_at_writes_colors_once_per_change() {
+        let red = CellColor { r: 255, g: 0, b: 0 };
+        let frame = AsciiFrame::from_chars_colored(vec!['#'; 4], vec![red; 4], 2, 2);
+        let options = OverlayOptions {
+            depth: ColorDepth::Indexed256,
+            save_cursor: false,
+            ..OverlayOptions::default()
+        };
+
+        let output = render_overlay_at(&frame, 1, 1, &options);
+        assert_eq!(output, "\x1b[1;1H\x1b[38;5;196m##\x1b[2;1H##\x1b[0m");
+    }
+
+    #[test]
+    fn test_transparent_cells_are_skipped_not_painted() {
+        let bright = CellColor {
+            r: 200,
+            g: 200,
+            b: 200,
+        };
+        let dark = CellColor {
+            r: 10,
+            g: 10,
+            b: 10,
+        };
+        let frame = AsciiFrame::from_chars_colored(
+            "a.b..c".chars().collect(),
+            vec![bright, dark, bright, dark, dark, bright],
+            6,
+            1,
+        );
+        let mut modal = CameraModal::new();
+        modal.border = false;
+        modal.color = false;
+        modal.transparency = 80;
+        modal.set_frame(frame.clone());
+        let grid = CellGrid::from_modal(&modal, 80, 24).unwrap();
+
+        // The dark cells leave no glyph, just a cursor advance past them
+        let output = DiffRenderer::new().render(grid.clone());
+        assert_eq!(
+            output,
+            format!(
+                "\x1b7\x1b[?25l\x1b[{};{}H\x1b[39m\x1b[27ma\x1b[1Cb\x1b[2Cc\x1b[0m\x1b[?25h\x1b8",
+                grid.y + 1,
+                grid.x + 1
+            )
+        );
+
+        // The same rule applies to a frame drawn at a screen position
+        let options = OverlayOptions {
+            depth: ColorDepth::Monochrome,
+            save_cursor: false,
+            transparency: Some(80),
+        };
+        let output = render_overlay_at(&frame, 1, 1, &options);
+        assert!(!output.contains('.'), "{:?}", output);
+
+        // Without transparency every cell is drawn
+        let opaque = OverlayOptions {
+            transparency: None,
+            ..options
+        };
+        assert_eq!(
+            render_overlay_at(&frame, 1, 1, &opaque),
+            "\x1b[1;1Ha.b..c\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_transparent_run_at_row_end_emits_no_move() {
+        let mut next = CellGrid::new(Rect::new(10, 5, 4, 2));
+        next.set(0, 0, Some(Cell::plain('a')));
+        next.set(1, 0, Some(Cell::plain('b')));
+        next.set(0, 1, Some(Cell::plain('c')));
+
+        // Nothing after "ab" on the first row: the next write is the second row
+        assert_eq!(
+            render_diff(&CellGrid::default(), &next),
+            "\x1b7\x1b[?25l\x1b[6;11H\x1b[39m\x1b[27mab\x1b[7;11Hc\x1b[0m\x1b[?25h\x1b8"
+        );
+    }
+
+    #[test]
+    fn test_blank_cells_show_through_without_colors() {
+        let frame = AsciiFrame::from_chars("  ab  c".chars().collect(), 7, 1);
+        let ghost = OverlayOptions {
+            save_cursor: false,
+            transparency: Some(80),
+            ..OverlayOptions::default()
+        };
+        let output = render_overlay_at(&frame, 5, 2, &ghost);
+        assert!(output.starts_with("\x1b[2;7Hab"), "{:?}", output);
+        assert_eq!(output.matches(' ').count(), 0);
+
+        // The diff renderer leaves the same cells untouched
+        let mut modal = CameraModal::new();
+        modal.set_frame(frame);
+        let grid = CellGrid::from_modal(&modal, 80, 24).unwrap();
+        let inset = u16::from(modal.border);
+        assert_eq!(grid.get(inset, inset), None);
+        assert_eq!(grid.get(inset + 2, inset).map(|c| c.ch), Some('a'));
+    }
+
+    #[test]
+    fn test_unchanged_frame_renders_nothing() {
+        let frame = grid(&["@@##", "..::"]);
+        assert_eq!(render_diff(&frame, &frame.clone()), "");
+
+        let mut renderer = DiffRenderer::new();
+        assert!(!renderer.render(frame.clone()).is_empty());
+        assert_eq!(renderer.render(frame), "");
+    }
+
+    #[test]
+    fn test_single_changed_cell_is_one_move_and_glyph() {
+        let prev = grid(&["@@##", "..::"]);
+        let mut next = prev.clone();
+        next.set(2, 1, Some(Cell::plain('%')));
+
+        // Row 1, col 2 of a grid at (10, 5) is screen row 7, column 13 (1-based)
+        assert_eq!(
+            render_diff(&prev, &next),
+            "\x1b7\x1b[?25l\x1b[7;13H\x1b[39m\x1b[27m%\x1b[0m\x1b[?25h\x1b8"
+        );
+    }
+
+    #[test]
+    fn test_drawn_cell_turning_transparent_is_blanked() {
+        let prev = grid(&["@@##"]);
+        let mut next = prev.clone();
+        next.set(1, 0, None);
+
+        assert_eq!(
+            render_diff(&prev, &next),
+            "\x1b7\x1b[?25l\x1b[6;12H\x1b[39m\x1b[27m \x1b[0m\x1b[?25h\x1b8"
+        );
+        // Once blanked, the cell stays untouched while it remains transparent
+        assert_eq!(render_diff(&next, &next.clone()), "");
+    }
+
+    #[test]
+    fn test_contiguous_changes_share_a_cursor_move_and_color() {
+        let prev = grid(&["@@##"]);
+        let red = CellColor { r: 255, g: 0, b: 0 };
+        let mut next = prev.clone();
+        for col in 1..3 {
+            let cell = Cell {
+                fg: Some(red),
+                ..Cell::plain('x')
+            };
+            next.set(col, 0, Some(cell));
+        }
+
+        let output = render_diff(&prev, &next);
+        assert_eq!(output.matches('H').count(), 1, "{:?}", output);
+        assert_eq!(output.matches("38;2;255;0;0").count(), 1, "{:?}", output);
+        assert!(output.contains("xx"));
+    }
+
+    #[test]
+    fn test_color_depth_selects_escape() {
+        let prev = grid(&["ab"]);
+        let red = Cell {
+            fg: Some(CellColor { r: 255, g: 0, b: 0 }),
+            ..Cell::plain('a')
+        };
+        let mut next = prev.clone();
+        next.set(0, 0, Some(red));
+        assert!(render_diff(&prev, &next).contains("\x1b[38;2;255;0;0m"));
+
+        let mut indexed = next.clone();
+        indexed.depth = ColorDepth::Indexed256;
+        let output = render_diff(&next, &indexed);
+        assert!(output.contains("\x1b[38;5;196m"), "{:?}", output);
+
+        // Monochrome writes no color, only the default foreground
+        let mut mono = next.clone();
+        mono.depth = ColorDepth::Monochrome;
+        assert!(!render_diff(&next, &mono).contains("38;"));
+    }
+
+    #[test]
+    fn test_moved_grid_redraws_and_skips_transparent_cells() {
+        let prev = grid(&["ab"]);
+        let mut next = prev.clone();
+        next.x += 1;
+        next.set(0, 0, None);
+
+        let output = render_diff(&prev, &next);
+        assert!(!output.contains('a'));
+        assert!(
+            output.contains("\x1b[6;13H\x1b[39m\x1b[27mb"),
+            "{:?}",
+            output
+        );
+
+        // After an invalidate the same frame is drawn again in full
+        let mut renderer = DiffRenderer::new();
+        renderer.render(next.clone());
+        renderer.invalidate();
+        assert_eq!(renderer.render(next), output);
     }
 }
@@ -0,0 +1,144 @@
+//! Terminal color capability detection.
+
+/// Colors the overlay is drawn with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorDepth {
+    /// 24-bit `38;2;R;G;B` colors
+    #[default]
+    TrueColor,
+    /// The xterm 256-color palette (`38;5;N`), nearest match per cell
+    Indexed256,
+    /// Plain glyphs in the terminal's default foreground
+    Monochrome,
+}
+
+impl ColorDepth {
+    /// Best depth the terminal advertises through `$COLORTERM` and `$TERM`.
+    pub fn detect() -> Self {
+        Self::from_env(
+            std::env::var("COLORTERM").ok().as_deref(),
+            std::env::var("TERM").ok().as_deref(),
+        )
+    }
+
+    fn from_env(colorterm: Option<&str>, term: Option<&str>) -> Self {
+        if colorterm_is_truecolor(colorterm) {
+            ColorDepth::TrueColor
+        } else if term.is_some_and(|t| t.to_ascii_lowercase().contains("256color")) {
+            ColorDepth::Indexed256
+        } else {
+            ColorDepth::Monochrome
+        }
+    }
+}
+
+/// Levels of each channel in the palette's 6x6x6 color cube (indices 16-231).
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Nearest xterm 256-color palette index for an RGB color.
+///
+/// Picks whichever is closer of the 6x6x6 color cube and the 24-step gray
+/// ramp (232-255), ignoring the 16 system colors that themes redefine.
+pub fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    let nearest_level = |v: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|&i| CUBE_LEVELS[i].abs_diff(v))
+            .unwrap_or(0)
+    };
+    let (ri, gi, bi) = (nearest_level(r), nearest_level(g), nearest_level(b));
+    let cube = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+    let cube_index = 16 + 36 * ri + 6 * gi + bi;
+
+    // Gray ramp levels are 8, 18, ..., 238
+    let mean = (r as u16 + g as u16 + b as u16) / 3;
+    let step = (mean.saturating_sub(3) / 10).min(23) as u8;
+    let level = 8 + 10 * step;
+    let gray = (level, level, level);
+
+    let distance = |(pr, pg, pb): (u8, u8, u8)| {
+        let d = |a: u8, b: u8| (a.abs_diff(b) as u32).pow(2);
+        d(pr, r) + d(pg, g) + d(pb, b)
+    };
+    if distance(gray) < distance(cube) {
+        232 + step
+    } else {
+        cube_index as u8
+    }
+}
+
+/// Check whether the terminal advertises 24-bit (truecolor) support.
+///
+/// Terminals that support it set `COLORTERM=truecolor` (or `24bit`). The
+/// modal's colors come from `ColorDepth::detect`, which falls back to the
+/// 256-color palette or plain glyphs; this is the truecolor check alone, for
+/// diagnostics.
+pub fn supports_truecolor() -> bool {
+    colorterm_is_truecolor(std::env::var("COLORTERM").ok().as_deref())
+}
+
+fn colorterm_is_truecolor(colorterm: Option<&str>) -> bool {
+    matches!(
+        colorterm.map(str::to_ascii_lowercase).as_deref(),
+        Some("truecolor") | Some("24bit")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_colorterm_truecolor_values() {
+        assert!(colorterm_is_truecolor(Some("truecolor")));
+        assert!(colorterm_is_truecolor(Some("24bit")));
+        assert!(colorterm_is_truecolor(Some("TrueColor")));
+    }
+
+    #[test]
+    fn test_color_depth_from_env() {
+        assert_eq!(
+            ColorDepth::from_env(Some("truecolor"), Some("xterm-256color")),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(
+            ColorDepth::from_env(None, Some("xterm-256color")),
+            ColorDepth::Indexed256
+        );
+        assert_eq!(
+            ColorDepth::from_env(Some(""), Some("screen-256color")),
+            ColorDepth::Indexed256
+        );
+        assert_eq!(
+            ColorDepth::from_env(None, Some("xterm")),
+            ColorDepth::Monochrome
+        );
+        assert_eq!(ColorDepth::from_env(None, None), ColorDepth::Monochrome);
+    }
+
+    #[test]
+    fn test_rgb_to_ansi256_known_colors() {
+        assert_eq!(rgb_to_ansi256(255, 0, 0), 196);
+        assert_eq!(rgb_to_ansi256(0, 255, 0), 46);
+        assert_eq!(rgb_to_ansi256(0, 0, 255), 21);
+        assert_eq!(rgb_to_ansi256(0, 0, 0), 16);
+        assert_eq!(rgb_to_ansi256(255, 255, 255), 231);
+        // Exact cube entries map to themselves
+        assert_eq!(rgb_to_ansi256(95, 135, 175), 67);
+        assert_eq!(rgb_to_ansi256(255, 135, 0), 208);
+    }
+
+    #[test]
+    fn test_rgb_to_ansi256_grays_use_ramp() {
+        assert_eq!(rgb_to_ansi256(128, 128, 128), 244);
+        assert_eq!(rgb_to_ansi256(8, 8, 8), 232);
+        assert_eq!(rgb_to_ansi256(238, 238, 238), 255);
+        assert_eq!(rgb_to_ansi256(130, 128, 126), 244);
+    }
+
+    #[test]
+    fn test_colorterm_other_values() {
+        assert!(!colorterm_is_truecolor(None));
+        assert!(!colorterm_is_truecolor(Some("")));
+        assert!(!colorterm_is_truecolor(Some("256color")));
+    }
+}
@@ -1,7 +1,7 @@
 //! ASCII frame types for camera modal display.
 
 /// RGB color for a character cell.
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct CellColor {
     pub r: u8,
     pub g: u8,
@@ -82,6 +82,12 @@ impl AsciiFrame {
             .collect::<Vec<_>>()
             .join("\n")
     }
+
+    /// Whether the grid holds exactly `width * height` cells (and colors).
+    pub fn is_complete(&self) -> bool {
+        let cells = (self.width as usize) * (self.height as usize);
+        self.chars.len() == cells && self.colors.as_ref().is_none_or(|c| c.len() == cells)
+    }
 }
 
 #[cfg(test)]
@@ -156,4 +162,13 @@ mod tests {
         assert!(frame.colors.is_some());
         assert_eq!(frame.colors.as_ref().unwrap().len(), 2);
     }
+
+    #[test]
+    fn test_ascii_frame_is_complete() {
+        assert!(AsciiFrame::new(4, 3).is_complete());
+        assert!(!AsciiFrame::from_chars(vec!['#'; 5], 3, 2).is_complete());
+        let short_colors =
+            AsciiFrame::from_chars_colored(vec!['#'; 6], vec![CellColor::default(); 4], 3, 2);
+        assert!(!short_colors.is_complete());
+    }
 }
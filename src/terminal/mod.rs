@@ -1,5 +1,6 @@
 //! Terminal management module - raw mode, TUI wrapper, modal types, and cleanup.
 
+mod color;
 mod frame;
 mod modal;
 mod pty_buffer;
@@ -9,9 +10,13 @@ mod status_bar;
 mod tui;
 
 // Re-export public types from submodules
+pub use color::{ColorDepth, rgb_to_ansi256, supports_truecolor};
 pub use frame::{AsciiFrame, CellColor};
-pub use modal::{CameraModal, ModalPosition, ModalSize};
-pub use pty_buffer::PtyBuffer;
-pub use raw_mode::RawModeGuard;
+pub use modal::{
+    COARSE_STEP_FACTOR, CameraModal, DEFAULT_OVERLAY_REFRESH_INTERVAL, DEFAULT_TRANSPARENCY_STEP,
+    MIN_CUSTOM_SIZE, ModalPosition, ModalSize, NOTICE_DURATION, NUDGE_STEP, SNAP_DISTANCE,
+};
+pub use pty_buffer::{DEFAULT_SCROLLBACK_LINES, PtyBuffer};
+pub use raw_mode::{MouseCaptureGuard, RawModeGuard};
 pub use status_bar::StatusBar;
 pub use tui::Tui;
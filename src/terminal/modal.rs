@@ -3,9 +3,15 @@
 //! Contains position, size, and state types for the floating camera preview.
 
 use ratatui::layout::Rect;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use super::color::ColorDepth;
 use super::frame::AsciiFrame;
-use crate::ascii::CharSet;
+use crate::ascii::{
+    CharSet, DEFAULT_BRAILLE_THRESHOLD, DEFAULT_SHARPEN_AMOUNT, DitherMode, GAMMA,
+    calculate_dimensions_with_aspect, next_gamma,
+};
 
 /// Position of the camera modal on screen.
 ///
@@ -24,9 +30,29 @@ pub enum ModalPosition {
     BottomRight,
     /// Centered in the container
     Center,
+    /// Free placement as fractions of the available travel, in permille
+    /// (0 = left/top edge, 1000 = right/bottom edge, both with the 1-char margin)
+    Custom(u16, u16),
 }
 
+/// How far one nudge moves the modal, in permille of the available travel.
+pub const NUDGE_STEP: i16 = 50;
+
+/// Custom positions this close to an anchor (per axis, in permille) snap to it.
+pub const SNAP_DISTANCE: u16 = 30;
+
 impl ModalPosition {
+    /// All positions, in cycle order.
+    pub fn all() -> &'static [ModalPosition] {
+        &[
+            ModalPosition::TopLeft,
+            ModalPosition::TopRight,
+            ModalPosition::BottomRight,
+            ModalPosition::BottomLeft,
+            ModalPosition::Center,
+        ]
+    }
+
     /// Calculate the rectangle for the modal within a container.
     ///
     /// Returns a `Rect` positioned according to this variant, with:
@@ -64,6 +90,14 @@ impl ModalPosition {
                 container.x + (container.width.saturating_sub(width)) / 2,
                 container.y + (container.height.saturating_sub(height)) / 2,
             ),
+            ModalPosition::Custom(fx, fy) => {
+                let travel_x = container.width.saturating_sub(width + MARGIN * 2) as u32;
+                let travel_y = container.height.saturating_sub(height + MARGIN * 2) as u32;
+                (
+                    container.x + MARGIN + (travel_x * (*fx).min(1000) as u32 / 1000) as u16,
+                    container.y + MARGIN + (travel_y * (*fy).min(1000) as u32 / 1000) as u16,
+                )
+            }
         };
 
         Rect {
@@ -76,17 +110,74 @@ impl ModalPosition {
 
     /// Cycle to the next position.
     ///
-    /// Order: TopLeft -> TopRight -> BottomRight -> BottomLeft -> Center -> TopLeft
+    /// Order: TopLeft -> TopRight -> BottomRight -> BottomLeft -> Center -> TopLeft.
+    /// A custom position cycles back to TopLeft.
     pub fn next(&self) -> Self {
         match self {
             ModalPosition::TopLeft => ModalPosition::TopRight,
             ModalPosition::TopRight => ModalPosition::BottomRight,
             ModalPosition::BottomRight => ModalPosition::BottomLeft,
             ModalPosition::BottomLeft => ModalPosition::Center,
-            ModalPosition::Center => ModalPosition::TopLeft,
+            ModalPosition::Center | ModalPosition::Custom(..) => ModalPosition::TopLeft,
+        }
+    }
+
+    /// Normalized (permille) coordinates of this position.
+    pub fn fractions(&self) -> (u16, u16) {
+        match self {
+            ModalPosition::TopLeft => (0, 0),
+            ModalPosition::TopRight => (1000, 0),
+            ModalPosition::BottomLeft => (0, 1000),
+            ModalPosition::BottomRight => (1000, 1000),
+            ModalPosition::Center => (500, 500),
+            ModalPosition::Custom(fx, fy) => ((*fx).min(1000), (*fy).min(1000)),
         }
     }
 
+    /// Position at the given permille coordinates, snapped to the nearest
+    /// preset anchor if within SNAP_DISTANCE on both axes.
+    pub fn snapped(fx: u16, fy: u16) -> Self {
+        let (fx, fy) = (fx.min(1000), fy.min(1000));
+        Self::all()
+            .iter()
+            .copied()
+            .find(|anchor| {
+                let (ax, ay) = anchor.fractions();
+                ax.abs_diff(fx) <= SNAP_DISTANCE && ay.abs_diff(fy) <= SNAP_DISTANCE
+            })
+            .unwrap_or(ModalPosition::Custom(fx, fy))
+    }
+
+    /// The custom position that `calculate_rect` places at column `x`, row `y`
+    /// for a modal of `width` x `height`, clamped so it stays inside the margin.
+    pub fn at(container: Rect, x: u16, y: u16, width: u16, height: u16) -> Self {
+        const MARGIN: u16 = 1;
+
+        // Round up so calculate_rect's rounding down lands back on the same cell
+        let fraction = |offset: u16, travel: u16| match travel {
+            0 => 0,
+            _ => (offset.min(travel) as u32 * 1000).div_ceil(travel as u32) as u16,
+        };
+        let width = width.min(container.width.saturating_sub(MARGIN * 2));
+        let height = height.min(container.height.saturating_sub(MARGIN * 2));
+        let offset_x = x.saturating_sub(container.x + MARGIN);
+        let offset_y = y.saturating_sub(container.y + MARGIN);
+        ModalPosition::Custom(
+            fraction(offset_x, container.width.saturating_sub(width + MARGIN * 2)),
+            fraction(
+                offset_y,
+                container.height.saturating_sub(height + MARGIN * 2),
+            ),
+        )
+    }
+
+    /// Move by (dx, dy) nudge steps, snapping to an anchor when close to one.
+    pub fn nudge(&self, dx: i16, dy: i16) -> Self {
+        let (fx, fy) = self.fractions();
+        let shift = |f: u16, d: i16| (f as i16 + d * NUDGE_STEP).clamp(0, 1000) as u16;
+        Self::snapped(shift(fx, dx), shift(fy, dy))
+    }
+
     /// Get a human-readable name for the position.
     pub fn name(&self) -> &'static str {
         match self {
@@ -95,6 +186,7 @@ impl ModalPosition {
             ModalPosition::BottomLeft => "bottom-left",
             ModalPosition::BottomRight => "bottom-right",
             ModalPosition::Center => "center",
+            ModalPosition::Custom(..) => "custom",
         }
     }
 }
@@ -115,9 +207,26 @@ pub enum ModalSize {
     XLarge,
     /// Huge: 120x60 inner (122x62 with border)
     Huge,
+    /// Any outer width and height (e.g. after resizing with the mouse),
+    /// at least MIN_CUSTOM_SIZE
+    Custom(u16, u16),
 }
 
+/// Smallest outer (width, height) a custom size can shrink to.
+pub const MIN_CUSTOM_SIZE: (u16, u16) = (8, 4);
+
 impl ModalSize {
+    /// All sizes, in cycle order.
+    pub fn all() -> &'static [ModalSize] {
+        &[
+            ModalSize::Small,
+            ModalSize::Medium,
+            ModalSize::Large,
+            ModalSize::XLarge,
+            ModalSize::Huge,
+        ]
+    }
+
     /// Get the outer dimensions (including border).
     ///
     /// Returns (width, height) in characters.
@@ -128,6 +237,10 @@ impl ModalSize {
             ModalSize::Large => (62, 32),
             ModalSize::XLarge => (82, 42),
             ModalSize::Huge => (122, 62),
+            ModalSize::Custom(width, height) => (
+                (*width).max(MIN_CUSTOM_SIZE.0),
+                (*height).max(MIN_CUSTOM_SIZE.1),
+            ),
         }
     }
 
@@ -141,19 +254,24 @@ impl ModalSize {
             ModalSize::Large => (60, 30),
             ModalSize::XLarge => (80, 40),
             ModalSize::Huge => (120, 60),
+            ModalSize::Custom(..) => {
+                let (width, height) = self.dimensions();
+                (width - 2, height - 2)
+            }
         }
     }
 
     /// Cycle to the next size.
     ///
-    /// Order: Small -> Medium -> Large -> XLarge -> Huge -> Small
+    /// Order: Small -> Medium -> Large -> XLarge -> Huge -> Small.
+    /// A custom size cycles back to Small.
     pub fn next(&self) -> Self {
         match self {
             ModalSize::Small => ModalSize::Medium,
             ModalSize::Medium => ModalSize::Large,
             ModalSize::Large => ModalSize::XLarge,
             ModalSize::XLarge => ModalSize::Huge,
-            ModalSize::Huge => ModalSize::Small,
+            ModalSize::Huge | ModalSize::Custom(..) => ModalSize::Small,
         }
     }
 
@@ -165,10 +283,23 @@ impl ModalSize {
             ModalSize::Large => "large",
             ModalSize::XLarge => "xlarge",
             ModalSize::Huge => "huge",
+            ModalSize::Custom(..) => "custom",
         }
     }
 }
 
+/// Percentage points one transparency hotkey press moves by default.
+pub const DEFAULT_TRANSPARENCY_STEP: u8 = 10;
+
+/// Multiplier for a coarse (Shift) transparency step.
+pub const COARSE_STEP_FACTOR: u8 = 5;
+
+/// How long a transient notice (e.g. "transparency 40%") stays on the modal.
+pub const NOTICE_DURATION: Duration = Duration::from_millis(1500);
+
+/// Default overlay redraw interval (~15 FPS).
+pub const DEFAULT_OVERLAY_REFRESH_INTERVAL: Duration = Duration::from_millis(67);
+
 /// Camera modal state for the TUI overlay.
 ///
 /// Controls the floating camera preview window that displays
@@ -181,15 +312,55 @@ pub struct CameraModal {
     pub position: ModalPosition,
     /// Size preset
     pub size: ModalSize,
-    /// Current ASCII frame to display
-    pub frame: Option<AsciiFrame>,
+    /// Current ASCII frame to display.
+    ///
+    /// Only ever replaced whole by `set_frame`: the frame behind the `Arc` is
+    /// complete and immutable, so rendering never sees a half-filled grid.
+    pub frame: Option<Arc<AsciiFrame>>,
     /// Whether to show a border around the modal
     pub border: bool,
     /// Character set for ASCII rendering
     pub charset: CharSet,
+    /// User-supplied charset, visited after Extended when cycling
+    pub custom_charset: Option<CharSet>,
     /// Transparency level (0-100, higher = more transparent)
     /// Dark pixels below this threshold are skipped
     pub transparency: u8,
+    /// Percentage points each transparency hotkey press adds
+    pub transparency_step: u8,
+    /// Transient notice text and when it was shown (expires after NOTICE_DURATION)
+    pub notice: Option<(String, Instant)>,
+    /// Supersampling factor for the downsample step (1 = off)
+    pub supersample: u8,
+    /// How often the overlay is redrawn, independent of the camera capture rate
+    pub refresh_interval: Duration,
+    /// Draw cells in their sampled colors instead of plain glyphs
+    pub color: bool,
+    /// Palette for colored cells (detected from the terminal, or `--color-depth`)
+    pub color_depth: ColorDepth,
+    /// Invert brightness so dense glyphs mean dark pixels (for light terminals)
+    pub invert: bool,
+    /// Performance mode: nearest-neighbor sampling on a reduced grid, no gamma or dithering
+    pub perf: bool,
+    /// Dithering applied when mapping brightness to characters
+    pub dither: DitherMode,
+    /// Character height/width for fitting the grid to the camera's aspect
+    /// ratio; None fills the whole modal
+    pub char_aspect: Option<f32>,
+    /// Brightness (0-255) at which braille dots light up
+    pub braille_threshold: u8,
+    /// Stretch contrast to the full range before mapping (helps in dim rooms)
+    pub auto_levels: bool,
+    /// Whether the unsharp mask runs before downsampling
+    pub sharpen: bool,
+    /// Unsharp mask strength used while `sharpen` is on
+    pub sharpen_amount: f32,
+    /// Display gamma used when mapping brightness to characters
+    pub gamma: f32,
+    /// Frame rate the camera is actually delivering, for the status bar
+    pub camera_fps: Option<f32>,
+    /// Mirror the camera horizontally (selfie view)
+    pub mirror: bool,
 }
 
 impl Default for CameraModal {
@@ -202,7 +373,10 @@ impl CameraModal {
     /// Create a new camera modal with default settings.
     ///
     /// Defaults: visible=false, position=BottomRight, size=Small, border=false,
-    /// charset=Standard, transparency=80
+    /// charset=Standard, transparency=80 (step 10), supersample=1, refresh=15fps, color=false,
+    /// invert=false, perf=false, dither=None, char_aspect=None, braille_threshold=80,
+    /// auto_levels=false, sharpen=false (amount 1.0), gamma=2.2, camera_fps=None,
+    /// mirror=false
     pub fn new() -> Self {
         Self {
             visible: false,
@@ -211,7 +385,25 @@ impl CameraModal {
             frame: None,
             border: false,
             charset: CharSet::default(),
+            custom_charset: None,
             transparency: 80,
+            transparency_step: DEFAULT_TRANSPARENCY_STEP,
+            notice: None,
+            supersample: 1,
+            refresh_interval: DEFAULT_OVERLAY_REFRESH_INTERVAL,
+            color: false,
+            color_depth: ColorDepth::TrueColor,
+            invert: false,
+            perf: false,
+            dither: DitherMode::None,
+            char_aspect: None,
+            braille_threshold: DEFAULT_BRAILLE_THRESHOLD,
+            auto_levels: false,
+            sharpen: false,
+            sharpen_amount: DEFAULT_SHARPEN_AMOUNT,
+            gamma: GAMMA,
+            camera_fps: None,
+            mirror: false,
         }
     }
 
@@ -225,34 +417,175 @@ impl CameraModal {
         self.position = self.position.next();
     }
 
+    /// Nudge the position by (dx, dy) steps, snapping to nearby anchors.
+    pub fn nudge_position(&mut self, dx: i16, dy: i16) {
+        self.position = self.position.nudge(dx, dy);
+    }
+
     /// Cycle to the next size.
     pub fn cycle_size(&mut self) {
         self.size = self.size.next();
     }
 
-    /// Cycle to the next character set.
+    /// Cycle to the next character set (including the custom one, if set).
     pub fn cycle_charset(&mut self) {
-        self.charset = self.charset.next();
+        self.charset = self.charset.next(self.custom_charset.as_ref());
+    }
+
+    /// Switch to a charset, remembering it for cycling if it's custom.
+    pub fn set_charset(&mut self, charset: CharSet) {
+        if matches!(charset, CharSet::Custom(_)) {
+            self.custom_charset = Some(charset.clone());
+        }
+        self.charset = charset;
+    }
+
+    /// Toggle color rendering.
+    pub fn toggle_color(&mut self) {
+        self.color = !self.color;
+    }
+
+    /// Colors the overlay is actually drawn with.
+    ///
+    /// Monochrome while color is off. Turning color on for a terminal that
+    /// looked monochrome draws truecolor, as Alt+O always has.
+    pub fn effective_color_depth(&self) -> ColorDepth {
+        match (self.color, self.color_depth) {
+            (false, _) => ColorDepth::Monochrome,
+            (true, ColorDepth::Monochrome) => ColorDepth::TrueColor,
+            (true, depth) => depth,
+        }
+    }
+
+    /// Toggle brightness inversion.
+    pub fn toggle_invert(&mut self) {
+        self.invert = !self.invert;
+    }
+
+    /// Toggle performance mode.
+    pub fn toggle_perf(&mut self) {
+        self.perf = !self.perf;
+    }
+
+    /// Toggle horizontal mirroring.
+    pub fn toggle_mirror(&mut self) {
+        self.mirror = !self.mirror;
+    }
+
+    /// Toggle the auto-levels contrast stretch.
+    pub fn toggle_auto_levels(&mut self) {
+        self.auto_levels = !self.auto_levels;
+    }
+
+    /// Toggle the unsharp mask.
+    pub fn toggle_sharpen(&mut self) {
+        self.sharpen = !self.sharpen;
+    }
+
+    /// Unsharp mask strength to apply this frame (0.0 while sharpening is off).
+    pub fn effective_sharpen(&self) -> f32 {
+        if self.sharpen {
+            self.sharpen_amount
+        } else {
+            0.0
+        }
+    }
+
+    /// Cycle to the next gamma step (1.0 → 1.5 → 2.0 → 2.2 → 2.5 → 1.0).
+    pub fn cycle_gamma(&mut self) {
+        self.gamma = next_gamma(self.gamma);
     }
 
-    /// Cycle transparency in steps of 10 (0 -> 10 -> 20 -> ... -> 100 -> 0).
-    pub fn cycle_transparency(&mut self) {
+    /// Cycle to the next dithering mode.
+    pub fn cycle_dither(&mut self) {
+        self.dither = self.dither.next();
+    }
+
+    /// Raise transparency by `transparency_step`, or 5x that when `coarse`.
+    ///
+    /// Steps stop at 100 rather than overshooting; the press after that wraps
+    /// back to 0 (e.g. step 30: 0 -> 30 -> 60 -> 90 -> 100 -> 0).
+    pub fn cycle_transparency(&mut self, coarse: bool) {
+        let step = if coarse {
+            self.transparency_step.saturating_mul(COARSE_STEP_FACTOR)
+        } else {
+            self.transparency_step
+        };
         self.transparency = if self.transparency >= 100 {
             0
         } else {
-            self.transparency + 10
+            self.transparency.saturating_add(step).min(100)
         };
     }
 
+    /// Show a short notice on the modal for NOTICE_DURATION.
+    pub fn show_notice(&mut self, text: impl Into<String>) {
+        self.notice = Some((text.into(), Instant::now()));
+    }
+
+    /// Get the current notice text, if one is showing and hasn't expired.
+    pub fn current_notice(&self) -> Option<&str> {
+        self.notice
+            .as_ref()
+            .filter(|(_, shown_at)| shown_at.elapsed() < NOTICE_DURATION)
+            .map(|(text, _)| text.as_str())
+    }
+
+    /// Set the overlay redraw rate in frames per second (clamped to at least 1).
+    ///
+    /// The interval is kept to the nanosecond, so the rate reads back as set
+    /// (60 FPS is 16.67ms, not a 16ms tick that reads as 63).
+    pub fn set_refresh_rate(&mut self, fps: u32) {
+        self.refresh_interval = Duration::from_nanos(1_000_000_000 / fps.max(1) as u64);
+    }
+
+    /// Get the overlay redraw rate in frames per second, rounded to the nearest whole number.
+    pub fn refresh_rate(&self) -> u32 {
+        let nanos = self.refresh_interval.as_nanos().max(1) as f64;
+        (1e9 / nanos).round() as u32
+    }
+
+    /// Set the character aspect from a cell width/height ratio (e.g. 0.5 for typical fonts).
+    pub fn set_cell_aspect(&mut self, width_over_height: f32) {
+        self.char_aspect = Some(1.0 / width_over_height);
+    }
+
+    /// ASCII grid size for a camera image of the given pixel size.
+    ///
+    /// Fills the modal's inner area, or with `char_aspect` set, the largest
+    /// grid inside it that keeps the image's aspect ratio.
+    pub fn grid_dimensions(&self, img_width: u32, img_height: u32) -> (u16, u16) {
+        let (width, height) = self.size.inner_dimensions();
+        match self.char_aspect {
+            Some(aspect) => {
+                calculate_dimensions_with_aspect(img_width, img_height, width, height, aspect)
+            }
+            None => (width, height),
+        }
+    }
+
     /// Calculate the rectangle for this modal in the given container.
     pub fn calculate_rect(&self, container: Rect) -> Rect {
         let (width, height) = self.size.dimensions();
         self.position.calculate_rect(container, width, height)
     }
 
-    /// Update the ASCII frame.
-    pub fn set_frame(&mut self, frame: AsciiFrame) {
-        self.frame = Some(frame);
+    /// Move and resize the modal to cover `rect` (e.g. while dragging it).
+    ///
+    /// The result is a custom size and position, shrunk to fit and moved
+    /// back inside `container` if `rect` reaches past its edges.
+    pub fn place(&mut self, container: Rect, rect: Rect) {
+        self.size = ModalSize::Custom(
+            rect.width.min(container.width.saturating_sub(2)),
+            rect.height.min(container.height.saturating_sub(2)),
+        );
+        let (width, height) = self.size.dimensions();
+        self.position = ModalPosition::at(container, rect.x, rect.y, width, height);
+    }
+
+    /// Swap in a fully built ASCII frame.
+    pub fn set_frame(&mut self, frame: impl Into<Arc<AsciiFrame>>) {
+        self.frame = Some(frame.into());
     }
 
     /// Clear the ASCII frame.
@@ -1,18 +1,39 @@
 //! Buffer for storing PTY output.
 
+use std::collections::VecDeque;
+
+/// Lines a `PtyBuffer` keeps by default.
+pub const DEFAULT_SCROLLBACK_LINES: usize = 10_000;
+
+/// Bytes a single line may hold; anything past it is cut from the front.
+///
+/// Progress bars redraw one line with `\r` and never end it, so without a
+/// cap the line still being written grows for as long as the build runs.
+pub const MAX_LINE_BYTES: usize = 16 * 1024;
+
 /// Buffer for storing PTY output.
 ///
-/// This buffer accumulates raw PTY output and provides a view for rendering.
-/// For MVP, this stores the raw output string that gets rendered as a Paragraph.
-/// Future versions may implement VT100 parsing for proper terminal emulation.
+/// Output is kept as a ring of lines (the oldest are dropped past `max_lines`)
+/// and viewed through a scroll offset counted in lines up from the bottom.
+/// For MVP, lines are raw text rendered as a Paragraph; future versions may
+/// implement VT100 parsing for proper terminal emulation.
+///
+/// Output can also be `queue`d as it arrives and applied in one `flush` per
+/// draw, so a burst of small chunks costs a single update.
 #[derive(Debug)]
 pub struct PtyBuffer {
-    /// Raw output content (accumulated from PTY)
-    content: String,
+    /// Completed lines, oldest first
+    lines: VecDeque<String>,
+    /// Text after the last newline (the line still being written)
+    partial: String,
     /// Scroll offset (lines from the end)
     scroll: u16,
     /// Maximum number of lines to keep (prevents unbounded growth)
     max_lines: usize,
+    /// Raw output queued since the last flush
+    pending: Vec<u8>,
+    /// Content updates applied so far
+    updates: u64,
 }
 
 impl Default for PtyBuffer {
@@ -24,19 +45,18 @@ impl Default for PtyBuffer {
 impl PtyBuffer {
     /// Create a new empty PTY buffer.
     pub fn new() -> Self {
-        Self {
-            content: String::new(),
-            scroll: 0,
-            max_lines: 10_000, // Keep last 10k lines by default
-        }
+        Self::with_max_lines(DEFAULT_SCROLLBACK_LINES)
     }
 
     /// Create a new buffer with a custom max lines limit.
     pub fn with_max_lines(max_lines: usize) -> Self {
         Self {
-            content: String::new(),
+            lines: VecDeque::new(),
+            partial: String::new(),
             scroll: 0,
             max_lines,
+            pending: Vec::new(),
+            updates: 0,
         }
     }
 
@@ -47,27 +67,85 @@ impl PtyBuffer {
     pub fn append(&mut self, data: &[u8]) {
         // Convert bytes to string, replacing invalid UTF-8 sequences
         let text = String::from_utf8_lossy(data);
-        self.content.push_str(&text);
-
-        // Trim to max_lines if exceeded
-        self.trim_to_max_lines();
+        self.append_str(&text);
     }
 
     /// Append a string directly.
+    ///
+    /// At the bottom the view follows new output; scrolled up, it stays on
+    /// the lines the user is reading.
     pub fn append_str(&mut self, text: &str) {
-        self.content.push_str(text);
-        self.trim_to_max_lines();
+        if text.is_empty() {
+            return;
+        }
+        self.updates += 1;
+        let before = self.line_count();
+        let mut pieces = text.split('\n');
+        if let Some(first) = pieces.next() {
+            self.partial.push_str(first);
+        }
+        for piece in pieces {
+            let mut line = std::mem::replace(&mut self.partial, piece.to_string());
+            cap_line(&mut line);
+            self.lines.push_back(line);
+        }
+        cap_line(&mut self.partial);
+        if self.scroll > 0 {
+            let added = self.line_count() - before;
+            self.scroll = self
+                .scroll
+                .saturating_add(added.try_into().unwrap_or(u16::MAX));
+        }
+
+        // Trim to max_lines if exceeded
+        while self.line_count() > self.max_lines && self.lines.pop_front().is_some() {}
+    }
+
+    /// Queue raw PTY output to be applied on the next `flush`.
+    pub fn queue(&mut self, data: &[u8]) {
+        self.pending.extend_from_slice(data);
+    }
+
+    /// Whether output is waiting for a `flush`.
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Apply everything queued since the last flush as one update.
+    ///
+    /// Returns the raw bytes applied (e.g. to pass through to the terminal),
+    /// or `None` if nothing was queued.
+    pub fn flush(&mut self) -> Option<Vec<u8>> {
+        if self.pending.is_empty() {
+            return None;
+        }
+        let batch = std::mem::take(&mut self.pending);
+        self.append(&batch);
+        Some(batch)
+    }
+
+    /// Number of content updates applied so far (one per append or flush).
+    pub fn updates(&self) -> u64 {
+        self.updates
     }
 
     /// Clear the buffer contents.
     pub fn clear(&mut self) {
-        self.content.clear();
+        self.lines.clear();
+        self.partial.clear();
+        self.pending.clear();
         self.scroll = 0;
     }
 
-    /// Get the raw content as a string slice.
-    pub fn content(&self) -> &str {
-        &self.content
+    /// Get the raw content as a string.
+    pub fn content(&self) -> String {
+        let mut content = String::new();
+        for line in &self.lines {
+            content.push_str(line);
+            content.push('\n');
+        }
+        content.push_str(&self.partial);
+        content
     }
 
     /// Get the current scroll offset.
@@ -90,39 +168,24 @@ impl PtyBuffer {
         self.scroll = self.scroll.saturating_sub(lines);
     }
 
+    /// Jump back to the newest output (and follow it again).
+    pub fn scroll_to_bottom(&mut self) {
+        self.scroll = 0;
+    }
+
+    /// Whether the view is scrolled up from the newest output.
+    pub fn is_scrolled(&self) -> bool {
+        self.scroll > 0
+    }
+
     /// Get the number of lines in the buffer.
     pub fn line_count(&self) -> usize {
-        self.content.lines().count()
+        self.lines.len() + usize::from(!self.partial.is_empty())
     }
 
     /// Check if the buffer is empty.
     pub fn is_empty(&self) -> bool {
-        self.content.is_empty()
-    }
-
-    /// Trim the buffer to keep only the last max_lines lines.
-    fn trim_to_max_lines(&mut self) {
-        let line_count = self.content.lines().count();
-        if line_count > self.max_lines {
-            // Find the byte index where we should start keeping content
-            let lines_to_remove = line_count - self.max_lines;
-            let mut lines_seen = 0;
-            let mut byte_index = 0;
-
-            for (i, c) in self.content.char_indices() {
-                if c == '\n' {
-                    lines_seen += 1;
-                    if lines_seen >= lines_to_remove {
-                        byte_index = i + 1; // Start after the newline
-                        break;
-                    }
-                }
-            }
-
-            if byte_index > 0 && byte_index < self.content.len() {
-                self.content = self.content[byte_index..].to_string();
-            }
-        }
+        self.lines.is_empty() && self.partial.is_empty()
     }
 
     /// Get visible content for rendering (accounting for scroll offset).
@@ -130,11 +193,12 @@ impl PtyBuffer {
     /// Returns lines from the end of the buffer, offset by scroll position.
     /// This is suitable for rendering in a fixed-height viewport.
     pub fn visible_content(&self, viewport_height: usize) -> String {
-        if self.content.is_empty() || viewport_height == 0 {
+        if self.is_empty() || viewport_height == 0 {
             return String::new();
         }
 
-        let lines: Vec<&str> = self.content.lines().collect();
+        let partial = (!self.partial.is_empty()).then_some(&self.partial);
+        let lines: Vec<&String> = self.lines.iter().chain(partial).collect();
         let total_lines = lines.len();
 
         // Calculate the range of lines to show
@@ -142,7 +206,29 @@ impl PtyBuffer {
         let end = total_lines.saturating_sub(scroll);
         let start = end.saturating_sub(viewport_height);
 
-        lines[start..end].join("\n")
+        lines[start..end]
+            .iter()
+            .map(|line| line.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Cut `line` down to `MAX_LINE_BYTES`, first dropping what a carriage
+/// return would have overwritten, then the oldest bytes.
+fn cap_line(line: &mut String) {
+    if line.len() <= MAX_LINE_BYTES {
+        return;
+    }
+    if let Some(cr) = line.trim_end_matches('\r').rfind('\r') {
+        line.drain(..=cr);
+    }
+    if line.len() > MAX_LINE_BYTES {
+        let mut start = line.len() - MAX_LINE_BYTES;
+        while !line.is_char_boundary(start) {
+            start += 1;
+        }
+        line.drain(..start);
     }
 }
 
@@ -278,4 +364,91 @@ mod tests {
         assert!(buf.is_empty());
         assert_eq!(buf.max_lines, 10_000);
     }
+
+    #[test]
+    fn test_pty_buffer_drops_oldest_past_capacity() {
+        let mut buf = PtyBuffer::with_max_lines(4);
+        for i in 1..=10 {
+            buf.append_str(&format!("Line {}\n", i));
+        }
+
+        assert_eq!(buf.line_count(), 4);
+        assert_eq!(buf.content(), "Line 7\nLine 8\nLine 9\nLine 10\n");
+    }
+
+    #[test]
+    fn test_pty_buffer_scroll_selects_window() {
+        let mut buf = PtyBuffer::new();
+        for i in 1..=10 {
+            buf.append_str(&format!("Line {}\n", i));
+        }
+
+        assert_eq!(buf.visible_content(3), "Line 8\nLine 9\nLine 10");
+        buf.scroll_up(4);
+        assert_eq!(buf.visible_content(3), "Line 4\nLine 5\nLine 6");
+        buf.scroll_down(1);
+        assert_eq!(buf.visible_content(3), "Line 5\nLine 6\nLine 7");
+        buf.scroll_to_bottom();
+        assert!(!buf.is_scrolled());
+        assert_eq!(buf.visible_content(3), "Line 8\nLine 9\nLine 10");
+    }
+
+    #[test]
+    fn test_pty_buffer_new_output_follows_unless_scrolled() {
+        let mut buf = PtyBuffer::new();
+        buf.append_str("a\nb\nc\n");
+        buf.append_str("d\n");
+        assert_eq!(buf.visible_content(2), "c\nd");
+
+        // Scrolled up, the same lines stay in view as output arrives
+        buf.scroll_up(1);
+        assert_eq!(buf.visible_content(2), "b\nc");
+        buf.append_str("e\nf\n");
+        assert_eq!(buf.visible_content(2), "b\nc");
+
+        // A partial line doesn't shift the view until it's finished
+        buf.append_str("g");
+        assert_eq!(buf.visible_content(2), "b\nc");
+    }
+
+    #[test]
+    fn test_pty_buffer_queued_writes_flush_as_one_update() {
+        let mut buf = PtyBuffer::new();
+        for chunk in ["cargo ", "build\n", "   Compiling a\n", "   Compiling b\n"] {
+            buf.queue(chunk.as_bytes());
+        }
+        // Nothing is applied until the next draw flushes
+        assert!(buf.is_empty());
+        assert!(buf.has_pending());
+        assert_eq!(buf.updates(), 0);
+
+        let batch = buf.flush().unwrap();
+        assert_eq!(batch, b"cargo build\n   Compiling a\n   Compiling b\n");
+        assert_eq!(buf.updates(), 1);
+        assert_eq!(buf.line_count(), 3);
+
+        // An idle interval is no update at all
+        assert!(buf.flush().is_none());
+        assert_eq!(buf.updates(), 1);
+    }
+
+    #[test]
+    fn test_pty_buffer_progress_line_stays_bounded() {
+        let mut buf = PtyBuffer::new();
+        for i in 0..20_000 {
+            buf.queue(format!("\rDownloading {}%", i % 100).as_bytes());
+            buf.flush();
+        }
+        assert_eq!(buf.line_count(), 1);
+        assert!(buf.content().len() <= MAX_LINE_BYTES);
+        assert!(buf.content().ends_with("Downloading 99%"));
+
+        // A long line without carriage returns keeps its newest bytes
+        buf.clear();
+        buf.append_str(&"é".repeat(MAX_LINE_BYTES));
+        buf.append_str("end\n");
+        let content = buf.content();
+        assert!(content.len() <= MAX_LINE_BYTES + 1);
+        assert!(content.ends_with("éend\n"));
+    }
 }
@@ -8,6 +8,9 @@ use std::sync::atomic::{AtomicBool, Ordering};
 /// Static flag to track if raw mode is active (for panic handler)
 pub(crate) static RAW_MODE_ACTIVE: AtomicBool = AtomicBool::new(false);
 
+/// Static flag to track if mouse capture is on (for panic handler)
+static MOUSE_CAPTURE_ACTIVE: AtomicBool = AtomicBool::new(false);
+
 /// Guard that ensures terminal is restored to normal mode on drop.
 /// This handles both normal exits and panics.
 pub struct RawModeGuard {
@@ -55,6 +58,31 @@ impl Drop for RawModeGuard {
     }
 }
 
+/// Guard that turns on mouse reporting and turns it off again on drop.
+///
+/// While it's held, the terminal sends clicks and drags to the app instead of
+/// selecting text.
+pub struct MouseCaptureGuard {
+    _private: (),
+}
+
+impl MouseCaptureGuard {
+    /// Enable mouse capture until the guard is dropped.
+    pub fn enter() -> io::Result<Self> {
+        install_panic_hook();
+        crossterm::execute!(io::stdout(), crossterm::event::EnableMouseCapture)?;
+        MOUSE_CAPTURE_ACTIVE.store(true, Ordering::SeqCst);
+        Ok(Self { _private: () })
+    }
+}
+
+impl Drop for MouseCaptureGuard {
+    fn drop(&mut self) {
+        MOUSE_CAPTURE_ACTIVE.store(false, Ordering::SeqCst);
+        let _ = crossterm::execute!(io::stdout(), crossterm::event::DisableMouseCapture);
+    }
+}
+
 /// Install a panic hook that restores terminal state before panicking.
 /// This ensures the terminal is usable even if the app panics.
 pub(crate) fn install_panic_hook() {
@@ -69,6 +97,9 @@ pub(crate) fn install_panic_hook() {
 
     panic::set_hook(Box::new(move |panic_info| {
         // Restore terminal before showing panic message
+        if MOUSE_CAPTURE_ACTIVE.swap(false, Ordering::SeqCst) {
+            let _ = crossterm::execute!(io::stdout(), crossterm::event::DisableMouseCapture);
+        }
         if RAW_MODE_ACTIVE.load(Ordering::SeqCst) {
             // Leave alternate screen first
             let _ = crossterm::execute!(io::stdout(), crossterm::terminal::LeaveAlternateScreen,);
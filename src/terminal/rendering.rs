@@ -6,16 +6,18 @@
 
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 
-use super::{CameraModal, PtyBuffer, StatusBar};
+use super::{AsciiFrame, CameraModal, ColorDepth, PtyBuffer, StatusBar, rgb_to_ansi256};
 
 /// Render a camera modal to a ratatui frame at the given area.
 ///
 /// This renders the modal with:
 /// - A cleared background for overlay effect
 /// - Optional border (controlled by modal.border)
-/// - ASCII frame content if available
+/// - ASCII frame content if available, in per-cell color (at the modal's
+///   effective `ColorDepth`) when `modal.color` is set and the frame carries colors
 ///
 /// # Arguments
 /// * `frame` - The ratatui frame to render to
@@ -44,12 +46,54 @@ pub fn render_modal(frame: &mut ratatui::Frame, modal: &CameraModal, area: Rect)
 
     // Render ASCII frame content
     if let Some(ref ascii_frame) = modal.frame {
-        let text = ascii_frame.to_string_display();
-        let paragraph = Paragraph::new(text).style(Style::default().fg(Color::White));
+        let depth = modal.effective_color_depth();
+        let paragraph = match ascii_frame.colors {
+            Some(ref colors) if depth != ColorDepth::Monochrome => {
+                Paragraph::new(colored_lines(ascii_frame, colors, depth))
+            }
+            _ => {
+                let text = ascii_frame.to_string_display();
+                Paragraph::new(text).style(Style::default().fg(Color::White))
+            }
+        };
         frame.render_widget(paragraph, inner);
     }
 }
 
+/// Build one line of single-cell spans per frame row, each with its foreground
+/// in `depth` (RGB, or the nearest 256-palette index).
+fn colored_lines<'a>(
+    ascii_frame: &AsciiFrame,
+    colors: &[super::CellColor],
+    depth: ColorDepth,
+) -> Vec<Line<'a>> {
+    let width = ascii_frame.width as usize;
+    if width == 0 {
+        return Vec::new();
+    }
+
+    ascii_frame
+        .chars
+        .chunks(width)
+        .zip(colors.chunks(width))
+        .map(|(row_chars, row_colors)| {
+            row_chars
+                .iter()
+                .zip(row_colors)
+                .map(|(&c, color)| {
+                    let fg = match depth {
+                        ColorDepth::Indexed256 => {
+                            Color::Indexed(rgb_to_ansi256(color.r, color.g, color.b))
+                        }
+                        _ => Color::Rgb(color.r, color.g, color.b),
+                    };
+                    Span::styled(c.to_string(), Style::default().fg(fg))
+                })
+                .collect::<Line>()
+        })
+        .collect()
+}
+
 /// Render PTY output to a ratatui frame.
 ///
 /// # Arguments
@@ -57,7 +101,8 @@ pub fn render_modal(frame: &mut ratatui::Frame, modal: &CameraModal, area: Rect)
 /// * `pty_buffer` - The PTY output buffer to render
 /// * `area` - The area to render the PTY content in
 pub fn render_pty_output(frame: &mut ratatui::Frame, pty_buffer: &PtyBuffer, area: Rect) {
-    let pty_content = pty_buffer.content();
+    // Only the window at the buffer's scroll offset
+    let pty_content = pty_buffer.visible_content(area.height as usize);
     let pty_paragraph = Paragraph::new(pty_content);
     frame.render_widget(pty_paragraph, area);
 }
@@ -133,3 +178,63 @@ pub fn render_full_frame(
         render_status_bar(frame, sb, modal, area);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terminal::CellColor;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    fn colored_modal(color: bool) -> CameraModal {
+        let mut modal = CameraModal::new();
+        modal.visible = true;
+        modal.color = color;
+        let (w, h) = modal.size.inner_dimensions();
+        let cells = (w as usize) * (h as usize);
+        modal.set_frame(AsciiFrame::from_chars_colored(
+            vec!['#'; cells],
+            vec![
+                CellColor {
+                    r: 200,
+                    g: 10,
+                    b: 30
+                };
+                cells
+            ],
+            w,
+            h,
+        ));
+        modal
+    }
+
+    fn first_glyph_fg(modal: &CameraModal) -> Color {
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        terminal.draw(|f| render_modal(f, modal, f.area())).unwrap();
+        let rect = modal.calculate_rect(Rect::new(0, 0, 80, 24));
+        terminal.backend().buffer()[(rect.x, rect.y)].fg
+    }
+
+    #[test]
+    fn test_render_modal_truecolor_when_enabled() {
+        assert_eq!(
+            first_glyph_fg(&colored_modal(true)),
+            Color::Rgb(200, 10, 30)
+        );
+    }
+
+    #[test]
+    fn test_render_modal_indexed_colors() {
+        let mut modal = colored_modal(true);
+        modal.color_depth = ColorDepth::Indexed256;
+        assert_eq!(
+            first_glyph_fg(&modal),
+            Color::Indexed(rgb_to_ansi256(200, 10, 30))
+        );
+    }
+
+    #[test]
+    fn test_render_modal_falls_back_to_white_glyphs() {
+        assert_eq!(first_glyph_fg(&colored_modal(false)), Color::White);
+    }
+}
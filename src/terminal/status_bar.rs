@@ -4,7 +4,8 @@ use super::modal::CameraModal;
 
 /// Status bar for displaying camera state at the bottom of the screen.
 ///
-/// Shows: camera on/off | position | size | charset
+/// Shows: camera on/off | position | size | charset | measured camera FPS (once
+/// known) | refresh rate, plus "perf" while performance mode is on
 #[derive(Debug, Clone)]
 pub struct StatusBar {
     /// Whether the status bar is visible
@@ -35,14 +36,21 @@ impl StatusBar {
 
     /// Format the status bar text based on the camera modal state.
     ///
-    /// Format: " cam:on/off | position | size | charset "
+    /// Format: " cam:on/off | position | size | charset[ | cam Nfps] | Nfps[ | perf] "
     pub fn format(&self, modal: &CameraModal) -> String {
+        let camera_fps = modal
+            .camera_fps
+            .map(|fps| format!(" | cam {:.0}fps", fps))
+            .unwrap_or_default();
         format!(
-            " {} | {} | {} | {} ",
+            " {} | {} | {} | {}{} | {}fps{} ",
             if modal.visible { "cam:on" } else { "cam:off" },
             modal.position.name(),
             modal.size.name(),
             modal.charset.name(),
+            camera_fps,
+            modal.refresh_rate(),
+            if modal.perf { " | perf" } else { "" },
         )
     }
 }
@@ -153,6 +153,67 @@ fn test_grayscale_empty_frame() {
     assert!(gray.is_empty());
 }
 
+#[test]
+fn test_grayscale_gray_frame_passes_through() {
+    let frame = Frame {
+        data: vec![0, 64, 200, 255],
+        width: 2,
+        height: 2,
+        format: FrameFormat::Gray,
+        timestamp: Instant::now(),
+    };
+    assert_eq!(to_grayscale(&frame), vec![0, 64, 200, 255]);
+
+    let mut buffer = vec![9; 10];
+    assert_eq!(to_grayscale_into(&frame, &mut buffer), 4);
+    assert_eq!(buffer, vec![0, 64, 200, 255]);
+}
+
+#[test]
+fn test_grayscale_yuv_frames_use_y_samples() {
+    // 4x2 YUYV macroblock: two rows of two Y0 U Y1 V pairs
+    let yuyv = Frame {
+        data: vec![
+            16, 90, 81, 240, 126, 54, 235, 34, //
+            41, 128, 145, 128, 210, 16, 180, 200,
+        ],
+        width: 4,
+        height: 2,
+        format: FrameFormat::Yuyv,
+        timestamp: Instant::now(),
+    };
+    assert_eq!(
+        to_grayscale(&yuyv),
+        vec![16, 81, 126, 235, 41, 145, 210, 180]
+    );
+
+    // The same luma as NV12, with one UV pair per 2x2 block
+    let nv12 = Frame {
+        data: vec![16, 81, 126, 235, 41, 145, 210, 180, 90, 240, 54, 34],
+        width: 4,
+        height: 2,
+        format: FrameFormat::Nv12,
+        timestamp: Instant::now(),
+    };
+    let mut buffer = Vec::new();
+    assert_eq!(to_grayscale_into(&nv12, &mut buffer), 8);
+    assert_eq!(buffer, to_grayscale(&yuyv));
+}
+
+#[test]
+fn test_downsample_colors_gray_frame() {
+    let frame = Frame {
+        data: vec![100, 200],
+        width: 2,
+        height: 1,
+        format: FrameFormat::Gray,
+        timestamp: Instant::now(),
+    };
+    let mut colors = Vec::new();
+    assert_eq!(downsample_colors_into(&frame, 1, 1, &mut colors), 1);
+    assert_eq!((colors[0].r, colors[0].g, colors[0].b), (150, 150, 150));
+}
+
 #[test]
 fn test_grayscale_mid_gray() {
     // Mid-gray: R=128, G=128, B=128
@@ -173,6 +234,40 @@ fn test_downsample_1to1() {
     assert_eq!(result[0], 128);
 }
 
+#[test]
+fn test_downsample_kernel_2to1_halves_rows_vs_1to1() {
+    let gray = vec![100u8; 64 * 64];
+
+    let square = downsample_kernel(&gray, 64, 64, DownsampleKernel::new(4, 4));
+    let tall = downsample_kernel(&gray, 64, 64, DownsampleKernel::new(4, 8));
+
+    assert_eq!(DownsampleKernel::new(4, 4).grid_size(64, 64), (16, 16));
+    assert_eq!(DownsampleKernel::new(4, 8).grid_size(64, 64), (16, 8));
+    assert_eq!(square.len(), 16 * 16);
+    assert_eq!(tall.len(), 16 * 8);
+    assert!(tall.iter().all(|&v| v == 100));
+}
+
+#[test]
+fn test_downsample_kernel_averages_tall_blocks() {
+    // 2x4 source, one 2x4 block: top half 0, bottom half 200 -> 100
+    let gray = vec![0, 0, 0, 0, 200, 200, 200, 200];
+    let result = downsample_kernel(&gray, 2, 4, DownsampleKernel::new(2, 4));
+    assert_eq!(result, vec![100]);
+}
+
+#[test]
+fn test_downsample_kernel_default_follows_char_aspect() {
+    let kernel = DownsampleKernel::default();
+    assert_eq!(
+        kernel,
+        DownsampleKernel::for_aspect(4, DEFAULT_CHAR_ASPECT_RATIO)
+    );
+    assert_eq!((kernel.block_w, kernel.block_h), (4, 8));
+    // Zero factors are clamped so the grid size is always defined
+    assert_eq!(DownsampleKernel::new(0, 0).grid_size(3, 2), (3, 2));
+}
+
 #[test]
 fn test_downsample_2x2_to_1x1() {
     // 2x2 image averaged into single character
@@ -377,6 +472,373 @@ fn test_downsample_checkerboard() {
     assert!(result.iter().all(|&v| v == 127));
 }
 
+#[test]
+fn test_downsample_supersampled_uses_2x_intermediate_grid() {
+    let gray: Vec<u8> = (0..64).map(|i| (i * 4) as u8).collect(); // 8x8 gradient
+    let mut scratch = Vec::new();
+    let mut buffer = Vec::new();
+
+    let written = downsample_supersampled_into(&gray, 8, 8, 2, 2, 2, &mut scratch, &mut buffer);
+
+    assert_eq!(written, 4);
+    assert_eq!(buffer.len(), 4);
+    assert_eq!(
+        scratch.len(),
+        4 * 4,
+        "intermediate grid should be 2x per axis"
+    );
+}
+
+#[test]
+fn test_downsample_supersampled_factor_1_matches_plain() {
+    let gray: Vec<u8> = (0..(7 * 5)).map(|i| (i * 7 % 256) as u8).collect();
+    let mut scratch = Vec::new();
+    let mut buffer = Vec::new();
+
+    downsample_supersampled_into(&gray, 7, 5, 3, 2, 1, &mut scratch, &mut buffer);
+
+    assert_eq!(buffer, downsample(&gray, 7, 5, 3, 2));
+    assert!(
+        scratch.is_empty(),
+        "factor 1 should skip the intermediate grid"
+    );
+}
+
+#[test]
+fn test_downsample_supersampled_even_division_matches_plain() {
+    // When cells divide evenly, averaging averages gives the same result
+    #[rustfmt::skip]
+    let gray = vec![
+        10, 20,  30, 40,
+        50, 60,  70, 80,
+        90, 100, 110, 120,
+        130, 140, 150, 160,
+    ];
+    let mut scratch = Vec::new();
+    let mut buffer = Vec::new();
+
+    downsample_supersampled_into(&gray, 4, 4, 2, 2, 2, &mut scratch, &mut buffer);
+
+    assert_eq!(buffer, vec![35, 55, 115, 135]);
+}
+
+#[test]
+fn test_downsample_supersampled_large_factor_on_small_image_stays_uniform() {
+    let mut scratch = Vec::new();
+    let mut buffer = Vec::new();
+
+    // A 4x fine grid (16x8) would be larger than the 6x3 image
+    let gray = vec![200u8; 6 * 3];
+    downsample_supersampled_into(&gray, 6, 3, 4, 2, 4, &mut scratch, &mut buffer);
+    assert_eq!(buffer, vec![200; 8]);
+
+    // The factor drops to what fits (3x here), not straight to 1
+    let gray = vec![90u8; 10 * 10];
+    downsample_supersampled_into(&gray, 10, 10, 3, 3, 4, &mut scratch, &mut buffer);
+    assert_eq!(buffer, vec![90; 9]);
+    assert_eq!(scratch.len(), 9 * 9);
+}
+
+#[test]
+fn test_downsample_supersampled_empty_input() {
+    let mut scratch = Vec::new();
+    let mut buffer = vec![1, 2, 3];
+
+    let written = downsample_supersampled_into(&[], 0, 0, 4, 4, 2, &mut scratch, &mut buffer);
+
+    assert_eq!(written, 0);
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_downsample_nearest_samples_cell_centers() {
+    // 4x2 RGB frame, one distinct pixel per column
+    let mut data = Vec::new();
+    for _ in 0..2 {
+        data.extend_from_slice(&[0, 0, 0, 100, 100, 100, 200, 200, 200, 255, 0, 0]);
+    }
+    let frame = make_frame(data, 4, 2);
+    let mut brightness = Vec::new();
+    let mut colors = Vec::new();
+
+    let written = downsample_nearest_into(&frame, 2, 1, 1, &mut brightness, &mut colors);
+
+    // Cell centers land on columns 1 and 3
+    assert_eq!(written, 2);
+    assert_eq!(brightness, vec![100, 76]);
+    assert_eq!((colors[1].r, colors[1].g, colors[1].b), (255, 0, 0));
+}
+
+#[test]
+fn test_downsample_nearest_step_repeats_samples() {
+    let data: Vec<u8> = (0..16u8).flat_map(|v| [v * 10; 3]).collect();
+    let frame = make_frame(data, 4, 4);
+    let mut brightness = Vec::new();
+    let mut colors = Vec::new();
+
+    let written = downsample_nearest_into(&frame, 4, 4, 2, &mut brightness, &mut colors);
+
+    assert_eq!(written, 16);
+    assert_eq!(
+        brightness,
+        vec![
+            0, 0, 20, 20, //
+            0, 0, 20, 20, //
+            80, 80, 100, 100, //
+            80, 80, 100, 100,
+        ]
+    );
+}
+
+#[test]
+fn test_downsample_nearest_empty_input() {
+    let frame = make_frame(vec![], 0, 0);
+    let mut brightness = vec![1, 2];
+    let mut colors = vec![CellColor::default()];
+
+    assert_eq!(
+        downsample_nearest_into(&frame, 4, 4, 2, &mut brightness, &mut colors),
+        0
+    );
+    assert!(brightness.is_empty());
+    assert!(colors.is_empty());
+}
+
+// ==================== Parallel (rayon) Tests ====================
+
+#[cfg(feature = "rayon")]
+fn noisy_frame(width: u32, height: u32) -> Frame {
+    // Deterministic pseudo-random pixels so no two rows are alike
+    let mut state = 0x2545_f491u32;
+    let data = (0..width * height * 3)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            (state >> 24) as u8
+        })
+        .collect();
+    make_frame(data, width, height)
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn test_parallel_grayscale_matches_serial() {
+    let frame = noisy_frame(1920, 1080);
+    let mut parallel = Vec::new();
+
+    let count = to_grayscale_par_into(&frame, &mut parallel);
+
+    assert_eq!(count, 1920 * 1080);
+    assert_eq!(parallel, to_grayscale(&frame));
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn test_parallel_downsample_matches_serial() {
+    let frame = noisy_frame(1920, 1080);
+    let gray = to_grayscale(&frame);
+    let mut parallel = Vec::new();
+
+    for (cols, rows) in [(120, 60), (37, 23), (1, 1)] {
+        downsample_par_into(&gray, 1920, 1080, cols, rows, &mut parallel);
+        assert_eq!(parallel, downsample(&gray, 1920, 1080, cols, rows));
+    }
+
+    // Large frames take the parallel path through the regular entry point too
+    assert!(gray.len() >= PARALLEL_MIN_PIXELS);
+    let mut dispatched = Vec::new();
+    downsample_into(&gray, 1920, 1080, 120, 60, &mut dispatched);
+    assert_eq!(dispatched, downsample(&gray, 1920, 1080, 120, 60));
+}
+
+// ==================== Auto-Levels Tests ====================
+
+#[test]
+fn test_auto_levels_expands_low_contrast() {
+    let mut gray: Vec<u8> = (0..1000).map(|i| 100 + (i % 41) as u8).collect();
+
+    auto_levels(&mut gray, DEFAULT_CLIP_PERCENT);
+
+    let min = *gray.iter().min().unwrap();
+    let max = *gray.iter().max().unwrap();
+    assert!(min <= 10, "min {}", min);
+    assert!(max >= 245, "max {}", max);
+}
+
+#[test]
+fn test_auto_levels_clips_outliers() {
+    // 1% outliers at each end shouldn't hold the range open at 2% clipping
+    let mut gray = vec![120u8; 49];
+    gray.extend(vec![130u8; 49]);
+    gray.extend([0, 255]);
+
+    auto_levels(&mut gray, 2.0);
+
+    assert_eq!(gray[0], 0);
+    assert_eq!(gray[97], 255);
+}
+
+#[test]
+fn test_auto_levels_flat_and_empty_unchanged() {
+    let mut flat = vec![128u8; 64];
+    auto_levels(&mut flat, DEFAULT_CLIP_PERCENT);
+    assert!(flat.iter().all(|&v| v == 128));
+
+    let mut empty: Vec<u8> = Vec::new();
+    auto_levels(&mut empty, DEFAULT_CLIP_PERCENT);
+    assert!(empty.is_empty());
+}
+
+// ==================== Sharpen Tests ====================
+
+/// 16x4 image with a soft step edge in the middle of each row.
+fn soft_step() -> Vec<u8> {
+    let row: Vec<u8> = (0..16u32)
+        .map(|x| match x {
+            0..=6 => 60,
+            7 => 100,
+            8 => 150,
+            _ => 190,
+        })
+        .collect();
+    row.repeat(4)
+}
+
+fn max_horizontal_gradient(gray: &[u8], width: usize) -> u8 {
+    gray.chunks(width)
+        .flat_map(|row| row.windows(2).map(|p| p[0].abs_diff(p[1])))
+        .max()
+        .unwrap_or(0)
+}
+
+#[test]
+fn test_unsharp_steepens_step_edge() {
+    let original = soft_step();
+    let mut sharpened = original.clone();
+
+    unsharp(&mut sharpened, 16, 4, 1.0);
+
+    assert!(
+        max_horizontal_gradient(&sharpened, 16) > max_horizontal_gradient(&original, 16),
+        "{:?}",
+        &sharpened[..16]
+    );
+}
+
+#[test]
+fn test_unsharp_zero_amount_unchanged() {
+    let original = soft_step();
+    let mut gray = original.clone();
+
+    unsharp(&mut gray, 16, 4, 0.0);
+
+    assert_eq!(gray, original);
+}
+
+#[test]
+fn test_unsharp_flat_image_unchanged() {
+    let mut gray = vec![128u8; 36];
+    unsharp(&mut gray, 6, 6, 2.0);
+    assert!(gray.iter().all(|&v| v == 128));
+}
+
+// ==================== Edge Detection Tests ====================
+
+fn checkerboard(width: u32, height: u32, square: u32) -> Vec<u8> {
+    (0..height)
+        .flat_map(|y| {
+            (0..width).map(move |x| {
+                if (x / square + y / square).is_multiple_of(2) {
+                    0
+                } else {
+                    255
+                }
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn test_edge_detection_flat_image_has_no_edges() {
+    let gray = vec![128; 16 * 16];
+    for threshold in [0.0, 0.25, 0.5, 1.0] {
+        let edges = apply_edge_detection(&gray, 16, 16, threshold);
+        assert!(edges.iter().all(|&m| m == 0), "threshold {}", threshold);
+    }
+}
+
+#[test]
+fn test_edge_detection_threshold_filters_weak_gradients() {
+    // Dim the top row of squares so its edges are weaker than the full-contrast ones
+    let mut gray = checkerboard(16, 16, 4);
+    for px in gray.iter_mut().take(16 * 4) {
+        *px = px.saturating_sub(200);
+    }
+    let count = |threshold: f32| {
+        apply_edge_detection(&gray, 16, 16, threshold)
+            .iter()
+            .filter(|&&m| m > 0)
+            .count()
+    };
+
+    let low = count(0.05);
+    let high = count(0.9);
+    assert!(low > 0);
+    assert!(high < low, "high threshold {} vs low {}", high, low);
+    assert_eq!(count(DEFAULT_EDGE_THRESHOLD), count(0.0));
+}
+
+#[test]
+fn test_structure_aware_flat_image_is_smooth() {
+    let gray = vec![128; 16 * 16];
+    for threshold in [0.0, DEFAULT_STRUCTURE_THRESHOLD, 1.0] {
+        let chars = map_structure_aware(
+            &gray,
+            16,
+            16,
+            4,
+            4,
+            &STRUCTURE_CHARSET_ASCII,
+            false,
+            threshold,
+        );
+        assert!(chars.iter().all(|&c| c == '+'), "threshold {}", threshold);
+    }
+}
+
+#[test]
+fn test_structure_aware_threshold_controls_structure_chars() {
+    // Vertical stripes: every cell straddles a hard edge
+    let gray: Vec<u8> = (0..32 * 32u32)
+        .map(|i| {
+            if ((i % 32) / 2).is_multiple_of(2) {
+                0
+            } else {
+                255
+            }
+        })
+        .collect();
+    let structure_count = |threshold: f32| {
+        map_structure_aware(
+            &gray,
+            32,
+            32,
+            8,
+            8,
+            &STRUCTURE_CHARSET_ASCII,
+            false,
+            threshold,
+        )
+        .iter()
+        .filter(|c| !STRUCTURE_CHARSET_ASCII.smooth.contains(c))
+        .count()
+    };
+
+    assert!(structure_count(0.05) > 0);
+    assert!(structure_count(1.0) < structure_count(0.05));
+}
+
 // ==================== Character Mapping Tests ====================
 
 #[test]
@@ -437,6 +899,87 @@ fn test_map_invert_mid_brightness() {
     assert_eq!(inverted[0], '=');
 }
 
+#[test]
+fn test_map_gamma_mid_gray_invert_changes_glyph() {
+    // Gamma runs first: 128 -> 186 ('*'); inverted 255-186=69 -> idx 2 (':')
+    let brightness = vec![128];
+    let normal = map_to_chars_gamma(&brightness, STANDARD_CHARSET, false);
+    let inverted = map_to_chars_gamma(&brightness, STANDARD_CHARSET, true);
+
+    assert_eq!(normal[0], '*');
+    assert_eq!(inverted[0], ':');
+}
+
+#[test]
+fn test_map_gamma_into_matches_gamma_with_invert() {
+    let brightness: Vec<u8> = (0..=255).step_by(15).collect();
+    let mut buffer = Vec::new();
+    for invert in [false, true] {
+        map_to_chars_gamma_into(&brightness, STANDARD_CHARSET, invert, &mut buffer);
+        assert_eq!(
+            buffer,
+            map_to_chars_gamma(&brightness, STANDARD_CHARSET, invert)
+        );
+    }
+}
+
+#[test]
+fn test_map_gamma_invert_extremes() {
+    let chars = map_to_chars_gamma(&[0, 255], STANDARD_CHARSET, true);
+    assert_eq!(chars, vec!['@', ' ']);
+}
+
+#[test]
+fn test_gamma_lut_one_is_identity() {
+    let lut = gamma_lut(1.0);
+    for (i, &v) in lut.iter().enumerate() {
+        assert_eq!(v as usize, i);
+    }
+}
+
+#[test]
+fn test_gamma_lut_default_matches_gamma_correct() {
+    let lut = gamma_lut(GAMMA);
+    for i in 0..=255u8 {
+        assert_eq!(lut[i as usize], gamma_correct(i));
+    }
+}
+
+#[test]
+fn test_gamma_lut_shifts_mid_tones_relative_to_identity() {
+    // The mapper encodes with x^(1/gamma), so a higher gamma lifts mid-tones
+    // toward the dense end of the ramp; the extremes stay fixed
+    let linear = gamma_lut(1.0);
+    let lut = gamma_lut(2.2);
+    assert!(lut[128] > linear[128]);
+    assert_eq!((lut[0], lut[255]), (0, 255));
+    assert!(gamma_lut(2.5)[128] > lut[128]);
+}
+
+#[test]
+fn test_map_gamma_lut_one_matches_plain_mapping() {
+    let brightness: Vec<u8> = (0..=255).collect();
+    let mut buffer = Vec::new();
+    map_to_chars_gamma_lut_into(
+        &brightness,
+        STANDARD_CHARSET,
+        false,
+        &gamma_lut(1.0),
+        &mut buffer,
+    );
+    assert_eq!(buffer, map_to_chars(&brightness, STANDARD_CHARSET, false));
+}
+
+#[test]
+fn test_next_gamma_steps_and_wraps() {
+    assert_eq!(next_gamma(1.0), 1.5);
+    assert_eq!(next_gamma(GAMMA), 2.5);
+    assert_eq!(next_gamma(2.5), 1.0);
+    // Off-step values move to the next step above them
+    assert_eq!(next_gamma(1.7), 2.0);
+    assert_eq!(GAMMA_STEPS.len(), 5);
+}
+
 #[test]
 fn test_map_custom_charset() {
     // Test with a custom 3-level charset
@@ -547,6 +1090,78 @@ fn test_map_gradient_produces_ordered_chars() {
     }
 }
 
+// ==================== Dithering Tests ====================
+
+#[test]
+fn test_dither_mode_cycle() {
+    assert_eq!(DitherMode::default(), DitherMode::None);
+    assert_eq!(DitherMode::None.next(), DitherMode::Ordered);
+    assert_eq!(DitherMode::Ordered.next(), DitherMode::FloydSteinberg);
+    assert_eq!(DitherMode::FloydSteinberg.next(), DitherMode::None);
+    assert_eq!(DitherMode::FloydSteinberg.name(), "floyd-steinberg");
+}
+
+#[test]
+fn test_floyd_steinberg_into_matches_allocating() {
+    let brightness: Vec<u8> = (0..64).map(|i| (i * 4) as u8).collect();
+    let expected = map_to_chars_floyd_steinberg(&brightness, 8, 8, STANDARD_CHARSET, false, true);
+
+    let mut error = Vec::new();
+    let mut buffer = vec!['x'; 3];
+    let written = map_to_chars_floyd_steinberg_into(
+        &brightness,
+        8,
+        8,
+        STANDARD_CHARSET,
+        false,
+        true,
+        &mut error,
+        &mut buffer,
+    );
+
+    assert_eq!(written, 64);
+    assert_eq!(buffer, expected);
+    assert_eq!(
+        map_to_chars_dithered(&brightness, 8, 8, STANDARD_CHARSET, false, true),
+        expected
+    );
+}
+
+#[test]
+fn test_ordered_dither_into_matches_allocating() {
+    let brightness: Vec<u8> = (0..64).map(|i| (i * 4) as u8).collect();
+    let mut buffer = Vec::new();
+
+    map_to_chars_ordered_dither_into(&brightness, 8, STANDARD_CHARSET, false, true, &mut buffer);
+
+    assert_eq!(
+        buffer,
+        map_to_chars_ordered_dither(&brightness, 8, STANDARD_CHARSET, false, true)
+    );
+}
+
+#[test]
+fn test_floyd_steinberg_differs_from_ordered_on_gradient() {
+    // Vertical gradient: dark at the top, bright at the bottom
+    let (w, h) = (32u16, 32u16);
+    let brightness: Vec<u8> = (0..h as usize)
+        .flat_map(|y| std::iter::repeat_n((y * 255 / (h as usize - 1)) as u8, w as usize))
+        .collect();
+
+    let histogram = |chars: &[char]| {
+        MINIMAL_CHARSET
+            .iter()
+            .map(|c| chars.iter().filter(|&x| x == c).count())
+            .collect::<Vec<_>>()
+    };
+
+    let floyd = map_to_chars_floyd_steinberg(&brightness, w, h, MINIMAL_CHARSET, false, false);
+    let ordered = map_to_chars_ordered_dither(&brightness, w, MINIMAL_CHARSET, false, false);
+
+    assert_eq!(floyd.len(), ordered.len());
+    assert_ne!(histogram(&floyd), histogram(&ordered));
+}
+
 // ==================== Blocks Charset Tests ====================
 
 #[test]
@@ -757,6 +1372,26 @@ fn test_default_char_aspect_ratio() {
     assert_eq!(DEFAULT_CHAR_ASPECT_RATIO, 2.0);
 }
 
+#[test]
+fn test_cell_aspect_range_bounds() {
+    assert!(CELL_ASPECT_RANGE.contains(&0.5));
+    assert!(CELL_ASPECT_RANGE.contains(&1.0));
+    assert!(!CELL_ASPECT_RANGE.contains(&0.29));
+    assert!(!CELL_ASPECT_RANGE.contains(&3.1));
+}
+
+#[test]
+fn test_calculate_dimensions_cell_aspect_halves_rows() {
+    // 16:9 source, same column budget; a 0.5 cell (twice as tall as wide) needs half the rows
+    let (w_half, h_half) = calculate_dimensions_with_aspect(1920, 1080, 80, 200, 1.0 / 0.5);
+    let (w_square, h_square) = calculate_dimensions_with_aspect(1920, 1080, 80, 200, 1.0 / 1.0);
+
+    assert_eq!(w_half, 80);
+    assert_eq!(w_square, 80);
+    let ratio = h_half as f32 / h_square as f32;
+    assert!((ratio - 0.5).abs() < 0.05, "{} vs {}", h_half, h_square);
+}
+
 #[test]
 fn test_calculate_dimensions_zero_inputs() {
     // Zero image dimensions
@@ -982,21 +1617,23 @@ fn test_charset_chars() {
     assert_eq!(CharSet::Standard.chars(), STANDARD_CHARSET);
     assert_eq!(CharSet::Blocks.chars(), BLOCKS_CHARSET);
     assert_eq!(CharSet::Minimal.chars(), MINIMAL_CHARSET);
+    assert_eq!(CharSet::Extended.chars(), EXTENDED_CHARSET);
     assert!(CharSet::Braille.chars().is_empty()); // Braille uses different rendering
 }
 
 #[test]
 fn test_charset_next_cycle() {
-    assert_eq!(CharSet::Standard.next(), CharSet::Blocks);
-    assert_eq!(CharSet::Blocks.next(), CharSet::Minimal);
-    assert_eq!(CharSet::Minimal.next(), CharSet::Braille);
-    assert_eq!(CharSet::Braille.next(), CharSet::Standard);
+    assert_eq!(CharSet::Standard.next(None), CharSet::Blocks);
+    assert_eq!(CharSet::Blocks.next(None), CharSet::Minimal);
+    assert_eq!(CharSet::Minimal.next(None), CharSet::Braille);
+    assert_eq!(CharSet::Braille.next(None), CharSet::Extended);
+    assert_eq!(CharSet::Extended.next(None), CharSet::Standard);
 }
 
 #[test]
 fn test_charset_full_cycle() {
     let start = CharSet::Standard;
-    let after_cycle = start.next().next().next().next();
+    let after_cycle = start.next(None).next(None).next(None).next(None).next(None);
     assert_eq!(start, after_cycle);
 }
 
@@ -1006,6 +1643,81 @@ fn test_charset_names() {
     assert_eq!(CharSet::Blocks.name(), "blocks");
     assert_eq!(CharSet::Minimal.name(), "minimal");
     assert_eq!(CharSet::Braille.name(), "braille");
+    assert_eq!(CharSet::Extended.name(), "extended");
+}
+
+#[test]
+fn test_extended_charset_ordering() {
+    assert_eq!(EXTENDED_CHARSET.len(), 70);
+    assert_eq!(EXTENDED_CHARSET[0], ' '); // darkest
+    assert_eq!(EXTENDED_CHARSET[69], '$'); // brightest
+    let unique: std::collections::HashSet<_> = EXTENDED_CHARSET.iter().collect();
+    assert_eq!(unique.len(), EXTENDED_CHARSET.len());
+}
+
+#[test]
+fn test_extended_charset_gradient_uses_more_glyphs_than_standard() {
+    let gradient: Vec<u8> = (0..=255).collect();
+    let distinct = |charset: CharSet| {
+        map_to_chars(&gradient, charset.chars(), false)
+            .into_iter()
+            .collect::<std::collections::HashSet<_>>()
+            .len()
+    };
+
+    assert_eq!(distinct(CharSet::Standard), STANDARD_CHARSET.len());
+    assert_eq!(distinct(CharSet::Extended), EXTENDED_CHARSET.len());
+    assert!(distinct(CharSet::Extended) > distinct(CharSet::Standard));
+}
+
+#[test]
+fn test_charset_all_matches_cycle_order() {
+    let all = CharSet::all();
+    for (i, charset) in all.iter().enumerate() {
+        assert_eq!(charset.next(None), all[(i + 1) % all.len()]);
+        assert!(!charset.description().is_empty());
+    }
+}
+
+#[test]
+fn test_charset_from_ramp() {
+    let charset = CharSet::from_ramp(" .oO@").unwrap();
+    assert_eq!(charset.chars(), &[' ', '.', 'o', 'O', '@']);
+    assert_eq!(charset.name(), "custom");
+    assert!(!charset.is_braille());
+}
+
+#[test]
+fn test_charset_from_ramp_rejects_short_ramp() {
+    assert_eq!(CharSet::from_ramp("#"), Err(CharSetError::RampTooShort(1)));
+    assert_eq!(CharSet::from_ramp(""), Err(CharSetError::RampTooShort(0)));
+}
+
+#[test]
+fn test_charset_custom_ramp_maps_dark_to_light() {
+    let charset = CharSet::from_ramp("_X").unwrap();
+    assert_eq!(
+        map_to_chars(&[0, 255], charset.chars(), false),
+        vec!['_', 'X']
+    );
+    assert_eq!(
+        map_to_chars_gamma(&[0, 255], charset.chars(), false),
+        vec!['_', 'X']
+    );
+}
+
+#[test]
+fn test_charset_custom_cycles_last() {
+    let custom = CharSet::from_ramp(" .#").unwrap();
+    assert_eq!(CharSet::Extended.next(Some(&custom)), custom);
+    assert_eq!(custom.next(Some(&custom)), CharSet::Standard);
+    assert_eq!(CharSet::Standard.next(Some(&custom)), CharSet::Blocks);
+    assert_eq!(CharSet::Extended.next(None), CharSet::Standard);
+    // Only a custom ramp is visited after Extended
+    assert_eq!(
+        CharSet::Extended.next(Some(&CharSet::Braille)),
+        CharSet::Standard
+    );
 }
 
 #[test]
@@ -28,10 +28,11 @@
 
 use image::GenericImageView;
 use space_recorder::ascii::{
-    BLOCKS_CHARSET, CellColor, CharSet, MINIMAL_CHARSET, STANDARD_CHARSET, STRUCTURE_CHARSET_ASCII,
-    calculate_dimensions, downsample, downsample_colors_into, downsample_contrast,
-    downsample_edge_preserve, map_structure_aware, map_to_chars, map_to_chars_dithered,
-    map_to_chars_gamma, map_to_chars_ordered_dither, render_braille, to_grayscale,
+    BLOCKS_CHARSET, CellColor, CharSet, DEFAULT_STRUCTURE_THRESHOLD, EXTENDED_CHARSET,
+    MINIMAL_CHARSET, STANDARD_CHARSET, STRUCTURE_CHARSET_ASCII, calculate_dimensions, downsample,
+    downsample_colors_into, downsample_contrast, downsample_edge_preserve, map_structure_aware,
+    map_to_chars, map_to_chars_dithered, map_to_chars_gamma, map_to_chars_ordered_dither,
+    render_braille, to_grayscale,
 };
 use space_recorder::camera::{Frame, FrameFormat};
 use std::fs;
@@ -318,10 +319,12 @@ fn generate_diff(expected: &str, actual: &str) -> String {
 fn run_visual_test(config: VisualTestConfig) {
     let frame = load_test_image(config.image_name);
 
-    let charset = match config.charset {
+    let charset = match &config.charset {
         CharSet::Standard => STANDARD_CHARSET,
         CharSet::Blocks => BLOCKS_CHARSET,
         CharSet::Minimal => MINIMAL_CHARSET,
+        CharSet::Extended => EXTENDED_CHARSET,
+        CharSet::Custom(chars) => chars,
         CharSet::Braille => {
             // Skip braille for now - it uses different rendering
             return;
@@ -1037,6 +1040,7 @@ fn compare_structure_aware() {
             h,
             &STRUCTURE_CHARSET_ASCII,
             true,
+            DEFAULT_STRUCTURE_THRESHOLD,
         );
 
         html.push_str(&format!("<h2>{}</h2>\n", image));
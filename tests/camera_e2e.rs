@@ -5,7 +5,9 @@
 //! - Frames captured at reasonable rate (~15+ fps)
 //! - App handles missing camera gracefully
 
-use space_recorder::camera::{CameraCapture, CameraError, CameraSettings, list_devices};
+use space_recorder::camera::{
+    CameraCapture, CameraError, CameraSettings, DeviceSelector, list_devices,
+};
 use std::thread;
 use std::time::{Duration, Instant};
 
@@ -45,8 +47,8 @@ fn test_camera_opens_without_error() {
     let mut camera = result.unwrap();
     println!("Camera opened successfully");
     println!(
-        "  Settings: device_index={}, mirror={}",
-        camera.settings().device_index,
+        "  Settings: device={}, mirror={}",
+        camera.settings().device,
         camera.settings().mirror
     );
 
@@ -58,8 +60,11 @@ fn test_camera_opens_without_error() {
         start_result.err()
     );
 
-    println!("  Actual resolution: {:?}", camera.actual_resolution());
-    println!("  Actual FPS: {:?}", camera.actual_fps());
+    println!(
+        "  Negotiated resolution: {:?}",
+        camera.negotiated_resolution()
+    );
+    println!("  Negotiated FPS: {:?}", camera.negotiated_fps());
 
     // Clean up
     camera.stop();
@@ -142,7 +147,7 @@ fn test_frame_capture_rate() {
 fn test_handles_missing_camera() {
     // Use an invalid device index
     let settings = CameraSettings {
-        device_index: 999,
+        device: DeviceSelector::Index(999),
         ..CameraSettings::default()
     };
 
@@ -4,8 +4,13 @@
 //! without requiring a real terminal.
 
 use ratatui::layout::Rect;
-use space_recorder::ascii::CharSet;
-use space_recorder::terminal::{AsciiFrame, CameraModal, ModalPosition, ModalSize, StatusBar};
+use space_recorder::ascii::{CharSet, DitherMode};
+use space_recorder::terminal::{
+    AsciiFrame, CameraModal, ColorDepth, DEFAULT_OVERLAY_REFRESH_INTERVAL,
+    DEFAULT_TRANSPARENCY_STEP, MIN_CUSTOM_SIZE, ModalPosition, ModalSize, NOTICE_DURATION,
+    NUDGE_STEP, SNAP_DISTANCE, StatusBar,
+};
+use std::time::{Duration, Instant};
 
 // ==================== ModalPosition Tests ====================
 
@@ -136,6 +141,97 @@ fn test_modal_position_names() {
     assert_eq!(ModalPosition::Center.name(), "center");
 }
 
+#[test]
+fn test_modal_position_all_matches_cycle_order() {
+    let all = ModalPosition::all();
+    for (i, position) in all.iter().enumerate() {
+        assert_eq!(position.next(), all[(i + 1) % all.len()]);
+    }
+}
+
+#[test]
+fn test_modal_position_custom_rect() {
+    let container = Rect {
+        x: 0,
+        y: 0,
+        width: 80,
+        height: 24,
+    };
+
+    // Travel is 80 - 20 - 2 = 58 columns and 24 - 10 - 2 = 12 rows
+    let rect = ModalPosition::Custom(500, 250).calculate_rect(container, 20, 10);
+    assert_eq!((rect.x, rect.y), (30, 4));
+    assert_eq!((rect.width, rect.height), (20, 10));
+
+    // The extremes line up with the corner presets
+    assert_eq!(
+        ModalPosition::Custom(0, 0).calculate_rect(container, 20, 10),
+        ModalPosition::TopLeft.calculate_rect(container, 20, 10)
+    );
+    assert_eq!(
+        ModalPosition::Custom(1000, 1000).calculate_rect(container, 20, 10),
+        ModalPosition::BottomRight.calculate_rect(container, 20, 10)
+    );
+}
+
+#[test]
+fn test_modal_position_custom_rect_offset_and_tight_container() {
+    let container = Rect {
+        x: 10,
+        y: 5,
+        width: 20,
+        height: 10,
+    };
+    let rect = ModalPosition::Custom(1000, 1000).calculate_rect(container, 40, 20);
+    assert_eq!((rect.x, rect.y), (11, 6));
+    assert_eq!((rect.width, rect.height), (18, 8));
+}
+
+#[test]
+fn test_modal_position_snaps_within_threshold() {
+    assert_eq!(ModalPosition::snapped(0, 0), ModalPosition::TopLeft);
+    assert_eq!(
+        ModalPosition::snapped(1000 - SNAP_DISTANCE, SNAP_DISTANCE),
+        ModalPosition::TopRight
+    );
+    assert_eq!(ModalPosition::snapped(520, 480), ModalPosition::Center);
+
+    // Just outside the snap distance on one axis stays custom
+    assert_eq!(
+        ModalPosition::snapped(SNAP_DISTANCE + 1, 0),
+        ModalPosition::Custom(SNAP_DISTANCE + 1, 0)
+    );
+    assert_eq!(
+        ModalPosition::snapped(250, 750),
+        ModalPosition::Custom(250, 750)
+    );
+}
+
+#[test]
+fn test_modal_position_nudge() {
+    let pos = ModalPosition::TopLeft.nudge(1, 0);
+    assert_eq!(pos, ModalPosition::Custom(NUDGE_STEP as u16, 0));
+    assert_eq!(pos.name(), "custom");
+
+    // Nudging back lands on the anchor and snaps
+    assert_eq!(pos.nudge(-1, 0), ModalPosition::TopLeft);
+
+    // Clamped at the container edges
+    assert_eq!(ModalPosition::TopLeft.nudge(-1, -1), ModalPosition::TopLeft);
+    assert_eq!(
+        ModalPosition::Center.nudge(0, 1),
+        ModalPosition::Custom(500, 500 + NUDGE_STEP as u16)
+    );
+}
+
+#[test]
+fn test_modal_position_custom_cycles_to_top_left() {
+    assert_eq!(
+        ModalPosition::Custom(300, 300).next(),
+        ModalPosition::TopLeft
+    );
+}
+
 // ==================== ModalSize Tests ====================
 
 #[test]
@@ -167,6 +263,14 @@ fn test_modal_size_next_cycle() {
     assert_eq!(ModalSize::Huge.next(), ModalSize::Small);
 }
 
+#[test]
+fn test_modal_size_all_matches_cycle_order() {
+    let all = ModalSize::all();
+    for (i, size) in all.iter().enumerate() {
+        assert_eq!(size.next(), all[(i + 1) % all.len()]);
+    }
+}
+
 #[test]
 fn test_modal_size_names() {
     assert_eq!(ModalSize::Small.name(), "small");
@@ -176,6 +280,45 @@ fn test_modal_size_names() {
     assert_eq!(ModalSize::Huge.name(), "huge");
 }
 
+#[test]
+fn test_modal_size_custom_dimensions() {
+    let size = ModalSize::Custom(30, 14);
+    assert_eq!(size.dimensions(), (30, 14));
+    assert_eq!(size.inner_dimensions(), (28, 12));
+    assert_eq!(size.next(), ModalSize::Small);
+    assert_eq!(ModalSize::Custom(1, 1).dimensions(), MIN_CUSTOM_SIZE);
+}
+
+// ==================== Free Placement Tests ====================
+
+#[test]
+fn test_position_at_round_trips_through_calculate_rect() {
+    let container = Rect::new(0, 0, 100, 40);
+    for (x, y) in [(1, 1), (7, 3), (40, 20), (77, 27)] {
+        let position = ModalPosition::at(container, x, y, 22, 12);
+        let rect = position.calculate_rect(container, 22, 12);
+        assert_eq!((rect.x, rect.y), (x, y), "{:?}", position);
+    }
+}
+
+#[test]
+fn test_place_clamps_off_screen_rect_to_container() {
+    let container = Rect::new(0, 0, 80, 24);
+    let mut modal = CameraModal::new();
+
+    // Dragged past the bottom-right edge: pulled back inside the margin
+    modal.place(container, Rect::new(75, 20, 30, 10));
+    let rect = modal.calculate_rect(container);
+    assert_eq!(modal.size, ModalSize::Custom(30, 10));
+    assert_eq!((rect.x, rect.y, rect.width, rect.height), (49, 13, 30, 10));
+
+    // Wider than the container: shrunk to fit between the margins
+    modal.place(container, Rect::new(0, 0, 200, 10));
+    let rect = modal.calculate_rect(container);
+    assert_eq!(modal.size, ModalSize::Custom(78, 10));
+    assert_eq!((rect.x, rect.width), (1, 78));
+}
+
 // ==================== CameraModal Tests ====================
 
 #[test]
@@ -187,6 +330,36 @@ fn test_camera_modal_new() {
     assert!(modal.frame.is_none());
     assert!(!modal.border);
     assert_eq!(modal.charset, CharSet::Standard);
+    assert_eq!(modal.supersample, 1);
+    assert_eq!(modal.refresh_interval, DEFAULT_OVERLAY_REFRESH_INTERVAL);
+    assert_eq!(modal.refresh_rate(), 15);
+}
+
+#[test]
+fn test_camera_modal_set_refresh_rate() {
+    let mut modal = CameraModal::new();
+
+    modal.set_refresh_rate(10);
+    assert_eq!(modal.refresh_interval, Duration::from_millis(100));
+    assert_eq!(modal.refresh_rate(), 10);
+
+    modal.set_refresh_rate(30);
+    assert_eq!(modal.refresh_rate(), 30);
+
+    // Zero is clamped rather than dividing by zero
+    modal.set_refresh_rate(0);
+    assert_eq!(modal.refresh_rate(), 1);
+}
+
+#[test]
+fn test_camera_modal_refresh_rate_round_trips() {
+    let mut modal = CameraModal::new();
+    for fps in [60, 30, 24, 144] {
+        modal.set_refresh_rate(fps);
+        assert_eq!(modal.refresh_rate(), fps);
+    }
+    modal.set_refresh_rate(60);
+    assert_eq!(modal.refresh_interval, Duration::from_nanos(16_666_666));
 }
 
 #[test]
@@ -208,6 +381,131 @@ fn test_camera_modal_toggle() {
     assert!(!modal.visible);
 }
 
+#[test]
+fn test_camera_modal_set_custom_charset_cycles_through_it() {
+    let mut modal = CameraModal::new();
+    let custom = CharSet::from_ramp(" -=#").unwrap();
+    modal.set_charset(custom.clone());
+    assert_eq!(modal.charset, custom);
+
+    let mut seen = Vec::new();
+    for _ in 0..6 {
+        modal.cycle_charset();
+        seen.push(modal.charset.clone());
+    }
+    assert_eq!(
+        seen,
+        vec![
+            CharSet::Standard,
+            CharSet::Blocks,
+            CharSet::Minimal,
+            CharSet::Braille,
+            CharSet::Extended,
+            custom
+        ]
+    );
+}
+
+#[test]
+fn test_camera_modal_toggle_invert() {
+    let mut modal = CameraModal::new();
+    assert!(!modal.invert);
+    modal.toggle_invert();
+    assert!(modal.invert);
+    modal.toggle_invert();
+    assert!(!modal.invert);
+}
+
+#[test]
+fn test_camera_modal_toggle_color() {
+    let mut modal = CameraModal::new();
+    assert!(!modal.color);
+    modal.toggle_color();
+    assert!(modal.color);
+    modal.toggle_color();
+    assert!(!modal.color);
+}
+
+#[test]
+fn test_camera_modal_effective_color_depth() {
+    let mut modal = CameraModal::new();
+    modal.color_depth = ColorDepth::Indexed256;
+    assert_eq!(modal.effective_color_depth(), ColorDepth::Monochrome);
+    modal.toggle_color();
+    assert_eq!(modal.effective_color_depth(), ColorDepth::Indexed256);
+
+    // Forcing color on where none was detected falls back to truecolor
+    modal.color_depth = ColorDepth::Monochrome;
+    assert_eq!(modal.effective_color_depth(), ColorDepth::TrueColor);
+}
+
+#[test]
+fn test_camera_modal_grid_dimensions() {
+    let mut modal = CameraModal::new();
+    modal.size = ModalSize::Large;
+
+    // Without a char aspect the grid fills the modal
+    assert_eq!(modal.grid_dimensions(1280, 720), (60, 30));
+
+    // Typical 0.5 cells: 16:9 at 60 columns needs ~17 rows
+    modal.set_cell_aspect(0.5);
+    assert_eq!(modal.grid_dimensions(1280, 720), (60, 17));
+
+    // Square cells need twice the rows, capped by the modal height
+    modal.set_cell_aspect(1.0);
+    assert_eq!(modal.grid_dimensions(1280, 720), (53, 30));
+}
+
+#[test]
+fn test_camera_modal_toggle_auto_levels() {
+    let mut modal = CameraModal::new();
+    assert!(!modal.auto_levels);
+
+    modal.toggle_auto_levels();
+    assert!(modal.auto_levels);
+    modal.toggle_auto_levels();
+    assert!(!modal.auto_levels);
+}
+
+#[test]
+fn test_camera_modal_toggle_sharpen() {
+    let mut modal = CameraModal::new();
+    assert_eq!(modal.effective_sharpen(), 0.0);
+
+    modal.sharpen_amount = 2.5;
+    modal.toggle_sharpen();
+    assert_eq!(modal.effective_sharpen(), 2.5);
+
+    modal.toggle_sharpen();
+    assert_eq!(modal.effective_sharpen(), 0.0);
+}
+
+#[test]
+fn test_camera_modal_cycle_gamma() {
+    let mut modal = CameraModal::new();
+    assert_eq!(modal.gamma, 2.2);
+
+    modal.cycle_gamma();
+    assert_eq!(modal.gamma, 2.5);
+    modal.cycle_gamma();
+    assert_eq!(modal.gamma, 1.0);
+    modal.cycle_gamma();
+    assert_eq!(modal.gamma, 1.5);
+}
+
+#[test]
+fn test_camera_modal_cycle_dither() {
+    let mut modal = CameraModal::new();
+    assert_eq!(modal.dither, DitherMode::None);
+
+    modal.cycle_dither();
+    assert_eq!(modal.dither, DitherMode::Ordered);
+    modal.cycle_dither();
+    assert_eq!(modal.dither, DitherMode::FloydSteinberg);
+    modal.cycle_dither();
+    assert_eq!(modal.dither, DitherMode::None);
+}
+
 #[test]
 fn test_camera_modal_cycle_position() {
     let mut modal = CameraModal::new();
@@ -245,6 +543,8 @@ fn test_camera_modal_cycle_charset() {
     modal.cycle_charset();
     assert_eq!(modal.charset, CharSet::Braille);
     modal.cycle_charset();
+    assert_eq!(modal.charset, CharSet::Extended);
+    modal.cycle_charset();
     assert_eq!(modal.charset, CharSet::Standard);
 }
 
@@ -288,6 +588,18 @@ fn test_camera_modal_clear_frame() {
     assert!(modal.frame.is_none());
 }
 
+#[test]
+fn test_camera_modal_set_frame_keeps_old_frame_intact() {
+    let mut modal = CameraModal::new();
+    modal.set_frame(AsciiFrame::from_chars(vec!['#'; 6], 3, 2));
+    let held = modal.frame.clone().unwrap();
+
+    modal.set_frame(AsciiFrame::from_chars(vec!['@'; 200], 20, 10));
+
+    assert_eq!((held.width, held.height, held.chars.len()), (3, 2, 6));
+    assert_eq!(modal.frame.as_ref().unwrap().chars.len(), 200);
+}
+
 #[test]
 fn test_camera_modal_with_frame_visible() {
     let mut modal = CameraModal::new();
@@ -299,6 +611,60 @@ fn test_camera_modal_with_frame_visible() {
     assert!(f.chars.iter().all(|&c| c == '@'));
 }
 
+#[test]
+fn test_camera_modal_transparency_fine_steps() {
+    let mut modal = CameraModal::new();
+    assert_eq!(modal.transparency_step, DEFAULT_TRANSPARENCY_STEP);
+    modal.transparency = 0;
+    modal.transparency_step = 2;
+
+    for _ in 0..7 {
+        modal.cycle_transparency(false);
+    }
+    assert_eq!(modal.transparency, 14);
+}
+
+#[test]
+fn test_camera_modal_transparency_coarse_step_clamps() {
+    let mut modal = CameraModal::new();
+    modal.transparency_step = 10;
+    modal.transparency = 80;
+
+    // 80 + 50 stops at 100 instead of overshooting
+    modal.cycle_transparency(true);
+    assert_eq!(modal.transparency, 100);
+    // At the top, the next press wraps to fully opaque
+    modal.cycle_transparency(true);
+    assert_eq!(modal.transparency, 0);
+    modal.cycle_transparency(true);
+    assert_eq!(modal.transparency, 50);
+
+    // A large step saturates rather than overflowing u8
+    modal.transparency_step = 50;
+    modal.transparency = 90;
+    modal.cycle_transparency(true);
+    assert_eq!(modal.transparency, 100);
+}
+
+#[test]
+fn test_camera_modal_notice_shows_text() {
+    let mut modal = CameraModal::new();
+    assert!(modal.current_notice().is_none());
+
+    modal.show_notice("transparency 40%");
+    assert_eq!(modal.current_notice(), Some("transparency 40%"));
+}
+
+#[test]
+fn test_camera_modal_notice_expires() {
+    let mut modal = CameraModal::new();
+    let Some(shown_at) = Instant::now().checked_sub(NOTICE_DURATION * 2) else {
+        return; // Monotonic clock too close to its origin to backdate
+    };
+    modal.notice = Some(("transparency 40%".to_string(), shown_at));
+    assert!(modal.current_notice().is_none());
+}
+
 // ==================== StatusBar Tests ====================
 
 #[test]
@@ -408,13 +774,46 @@ fn test_status_bar_format_reflects_charset() {
     assert!(sb.format(&modal).contains("braille"));
 }
 
+#[test]
+fn test_status_bar_format_reflects_refresh_rate() {
+    let sb = StatusBar::new();
+    let mut modal = CameraModal::new();
+    assert!(sb.format(&modal).contains("15fps"));
+
+    modal.set_refresh_rate(5);
+    assert!(sb.format(&modal).contains("5fps"));
+}
+
+#[test]
+fn test_status_bar_format_shows_perf_mode() {
+    let sb = StatusBar::new();
+    let mut modal = CameraModal::new();
+    assert!(!sb.format(&modal).contains("perf"));
+
+    modal.toggle_perf();
+    assert!(sb.format(&modal).ends_with(" | perf "));
+}
+
+#[test]
+fn test_status_bar_format_shows_camera_fps_after_charset() {
+    let sb = StatusBar::new();
+    let mut modal = CameraModal::new();
+    assert!(!sb.format(&modal).contains("cam "));
+
+    modal.camera_fps = Some(29.7);
+    assert_eq!(
+        sb.format(&modal),
+        " cam:off | bottom-right | small | standard | cam 30fps | 15fps "
+    );
+}
+
 #[test]
 fn test_status_bar_format_has_separators() {
     let sb = StatusBar::new();
     let modal = CameraModal::new();
 
     let text = sb.format(&modal);
-    assert_eq!(text.matches('|').count(), 3);
+    assert_eq!(text.matches('|').count(), 4);
 }
 
 #[test]
@@ -17,6 +17,9 @@ use space_recorder::pty::{PtyHost, PtySize, select_shell};
 use space_recorder::terminal::{
     AsciiFrame, CameraModal, ModalPosition, ModalSize, PtyBuffer, RawModeGuard, StatusBar, Tui,
 };
+use std::collections::HashMap;
+use std::io::Read;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::thread;
 use std::time::{Duration, Instant};
 
@@ -330,6 +333,9 @@ fn test_hotkey_cycle_charset() {
     modal.cycle_charset();
     assert_eq!(modal.charset, CharSet::Braille);
 
+    modal.cycle_charset();
+    assert_eq!(modal.charset, CharSet::Extended);
+
     modal.cycle_charset();
     assert_eq!(modal.charset, CharSet::Standard); // full cycle
 }
@@ -343,35 +349,35 @@ fn test_all_hotkeys_in_sequence() {
     // Initial state
     assert_eq!(
         status_bar.format(&modal),
-        " cam:off | bottom-right | small | standard "
+        " cam:off | bottom-right | small | standard | 15fps "
     );
 
     // Toggle on
     modal.toggle();
     assert_eq!(
         status_bar.format(&modal),
-        " cam:on | bottom-right | small | standard "
+        " cam:on | bottom-right | small | standard | 15fps "
     );
 
     // Change position
     modal.cycle_position();
     assert_eq!(
         status_bar.format(&modal),
-        " cam:on | bottom-left | small | standard "
+        " cam:on | bottom-left | small | standard | 15fps "
     );
 
     // Change size
     modal.cycle_size();
     assert_eq!(
         status_bar.format(&modal),
-        " cam:on | bottom-left | medium | standard "
+        " cam:on | bottom-left | medium | standard | 15fps "
     );
 
     // Change charset
     modal.cycle_charset();
     assert_eq!(
         status_bar.format(&modal),
-        " cam:on | bottom-left | medium | blocks "
+        " cam:on | bottom-left | medium | blocks | 15fps "
     );
 }
 
@@ -485,6 +491,89 @@ fn test_pty_clean_exit() {
     }
 }
 
+/// Run the space-recorder binary in a PTY with `args`, returning everything
+/// it wrote and its exit code.
+fn run_space_recorder(args: &[&str]) -> (Vec<u8>, u32) {
+    // An empty config, so the user's own doesn't change the run
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+    let config = std::env::temp_dir().join(format!(
+        "space-recorder-e2e-{}-{}.toml",
+        std::process::id(),
+        RUNS.fetch_add(1, Ordering::Relaxed)
+    ));
+    std::fs::write(&config, "").expect("Should write config");
+    let mut argv = vec!["--config".to_string(), config.display().to_string()];
+    argv.extend(args.iter().map(|arg| arg.to_string()));
+
+    let pty = PtyHost::spawn_with_args(
+        env!("CARGO_BIN_EXE_space-recorder"),
+        &argv,
+        &HashMap::new(),
+        PtySize::default(),
+    )
+    .expect("Should spawn space-recorder");
+    let (mut reader, mut pty) = pty.split();
+
+    // Reads until the PTY hangs up (EIO on Linux) once the app has exited
+    let mut output = Vec::new();
+    let mut buf = [0u8; 4096];
+    while let Ok(n) = reader.read(&mut buf) {
+        if n == 0 {
+            break;
+        }
+        output.extend_from_slice(&buf[..n]);
+    }
+    let status = pty.wait().expect("Should reap space-recorder");
+    let _ = std::fs::remove_file(&config);
+    (output, status.exit_code())
+}
+
+/// The `o` event data of an asciicast recording, still JSON-escaped.
+fn cast_output(cast: &str) -> String {
+    cast.lines()
+        .filter_map(|line| line.split_once(", \"o\", \""))
+        .filter_map(|(_, data)| data.strip_suffix("\"]"))
+        .collect()
+}
+
+#[test]
+fn test_exec_records_all_output() {
+    let cast = std::env::temp_dir().join(format!("space-recorder-e2e-{}.cast", std::process::id()));
+    let (_, code) = run_space_recorder(&[
+        "--no-camera",
+        "--record-session",
+        &cast.display().to_string(),
+        "--exec",
+        "seq 1 100000",
+    ]);
+    let recorded = std::fs::read_to_string(&cast).expect("Should write recording");
+    let _ = std::fs::remove_file(&cast);
+
+    assert_eq!(code, 0);
+    // Written out after the shell is reaped, but must not be lost with it
+    assert!(
+        cast_output(&recorded).ends_with("\\r\\n99999\\r\\n100000\\r\\n"),
+        "Recording should end with the command's last line"
+    );
+}
+
+#[test]
+fn test_exec_output_reaches_stdout() {
+    let (output, code) =
+        run_space_recorder(&["--no-camera", "--no-status", "--exec", "seq 1 100000"]);
+    let output = String::from_utf8_lossy(&output);
+
+    assert_eq!(code, 0);
+    assert!(output.contains("1\r\n2\r\n3\r\n"));
+    assert!(
+        output.contains("\r\n99999\r\n100000\r\n"),
+        "Output should run to the command's last line"
+    );
+
+    let (_, code) = run_space_recorder(&["--no-camera", "--exec", "exit 3"]);
+    assert_eq!(code, 3, "Should exit with the command's status");
+}
+
 #[test]
 fn test_raw_mode_cleanup_on_drop() {
     // Skip if no TTY